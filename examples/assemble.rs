@@ -0,0 +1,59 @@
+use std::{fs, path};
+
+use color_eyre::eyre::Result;
+use periscvcope::assembler::Assembler;
+
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct ProgramArguments {
+    source_path: String,
+
+    /// Where to write the ELF executable.
+    #[arg(short, long, default_value = "a.out")]
+    output: String,
+
+    /// Address of .text.
+    #[arg(long, default_value_t = 0x1000)]
+    text_base: u64,
+
+    /// Address of .data, the page after .text by default.
+    #[arg(long)]
+    data_base: Option<u64>,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let arguments = ProgramArguments::parse();
+
+    let path = path::PathBuf::from(&arguments.source_path);
+
+    if !path.exists() {
+        panic!("File {} does not exist!", arguments.source_path);
+    }
+
+    let source = fs::read_to_string(path).expect("Unable to open file for reading.");
+
+    let mut assembler = Assembler::default();
+    assembler.set_text_base(arguments.text_base);
+    if let Some(base) = arguments.data_base {
+        assembler.set_data_base(base);
+    }
+    let program = assembler.assemble(&source)?;
+    fs::write(&arguments.output, program.to_elf())?;
+
+    println!(
+        "Wrote {}: .text {:#X}..{:#X}, .data {:#X}..{:#X}, .bss {:#X}..{:#X}, entry {:#X}.",
+        arguments.output,
+        program.text.base,
+        program.text.end(),
+        program.data.base,
+        program.data.end(),
+        program.bss.start,
+        program.bss.end,
+        program.entry
+    );
+
+    Ok(())
+}
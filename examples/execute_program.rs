@@ -1,33 +1,148 @@
 use std::{fs, path};
 
 use color_eyre::eyre::Result;
-use periscvcope::machine::Machine;
+use periscvcope::{
+    isa::Isa,
+    loader::{Binary, Entry, Format, Loader},
+    machine::{Machine, MemoryLayout, MisalignedAccess},
+};
 
 use clap::Parser;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct ProgramArguments {
-    program_path: String,
+    /// Programs loaded into the same memory, e.g. a bootloader and a kernel.
+    #[arg(required = true)]
+    program_paths: Vec<String>,
+
+    /// Format of the programs: elf, ihex, srec or bin. Detected from the contents if not given.
+    #[arg(long)]
+    format: Option<Format>,
+
+    /// Address a raw binary program is loaded at.
+    #[arg(long, default_value = "0", value_parser = address)]
+    base: u64,
+
+    /// Index of the program whose entry point execution starts at.
+    #[arg(long, default_value_t = 0)]
+    boot: usize,
+
+    /// Address execution starts at, instead of the entry point of the boot program.
+    #[arg(long, value_parser = address)]
+    entry: Option<u64>,
+
+    /// A file loaded as it is at an address, e.g. a device tree: FILE@ADDRESS.
+    #[arg(long, value_parser = blob)]
+    blob: Vec<(String, u64)>,
+
+    /// Address memory starts at, e.g. 0x80000000.
+    #[arg(long, default_value = "0", value_parser = address)]
+    memory_base: u64,
+
+    /// Size of memory in bytes.
+    #[arg(long, default_value = "0x400000", value_parser = address)]
+    memory_size: u64,
+
+    /// Address of tohost, for images without a symbol table.
+    #[arg(long, value_parser = address)]
+    tohost: Option<u64>,
+
+    /// Extensions the machine decodes, e.g. rv32ifd_zicsr_zbb.
+    #[arg(long)]
+    isa: Option<Isa>,
+
+    /// What misaligned loads and stores do: emulate, warn or trap.
+    #[arg(long, default_value = "emulate")]
+    misaligned: MisalignedAccess,
+
+    /// Report signed overflow in guest arithmetic.
+    #[arg(long)]
+    sanitize: bool,
+
+    /// An environment variable of the program: NAME=VALUE.
+    #[arg(long)]
+    env: Vec<String>,
+
+    /// Arguments of the program, after `--`. argv[0] is the path of the first program.
+    #[arg(last = true)]
+    args: Vec<String>,
+}
+
+/* Parses a decimal or 0x-prefixed hexadecimal address. */
+fn address(s: &str) -> Result<u64, String> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .map_err(|err| format!("invalid address {s}: {err}"))
+}
+
+fn blob(s: &str) -> Result<(String, u64), String> {
+    let (path, addr) = s
+        .rsplit_once('@')
+        .ok_or_else(|| format!("expected FILE@ADDRESS, got {s}"))?;
+
+    Ok((path.to_string(), address(addr)?))
+}
+
+fn read(path: &str) -> Vec<u8> {
+    if !path::Path::new(path).exists() {
+        panic!("File {path} does not exist!");
+    }
+
+    fs::read(path).expect("Unable to open file for reading.")
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let arguments = ProgramArguments::parse();
 
-    let path = path::PathBuf::from(&arguments.program_path);
-
-    if !path.exists() {
-        panic!("File {} does not exist!", arguments.program_path);
+    let mut images = Vec::new();
+    for path in &arguments.program_paths {
+        let data = read(path);
+        let format = arguments.format.unwrap_or_else(|| Format::detect(&data));
+        images.push(format.loader(arguments.base, arguments.base).load(&data)?);
+    }
+    for (path, addr) in &arguments.blob {
+        let blob = Binary {
+            base: *addr,
+            entry: *addr,
+        };
+        images.push(blob.load(&read(path))?);
+    }
+    if let Some(tohost) = arguments.tohost {
+        images[0].symbols.insert("tohost".to_string(), tohost);
     }
 
-    let file_data = fs::read(path).expect("Unable to open file for reading.");
-    let slice = file_data.as_slice();
-
-    let mut machine = Machine::new(slice)?;
+    let entry = match arguments.entry {
+        Some(addr) => Entry::Address(addr),
+        None => Entry::Image(arguments.boot),
+    };
+    let layout = MemoryLayout {
+        base: arguments.memory_base,
+        size: arguments.memory_size,
+    };
+    let mut machine = Machine::from_images_in(&images, &entry, layout)?;
+    if let Some(isa) = arguments.isa {
+        machine.set_isa(isa)?;
+    }
+    machine.set_misaligned_access(arguments.misaligned);
+    machine.set_sanitizer(arguments.sanitize);
+    if !arguments.args.is_empty() || !arguments.env.is_empty() {
+        let args: Vec<&str> = std::iter::once(&arguments.program_paths[0])
+            .chain(&arguments.args)
+            .map(String::as_str)
+            .collect();
+        let env: Vec<&str> = arguments.env.iter().map(String::as_str).collect();
+        machine.set_arguments(&args, &env)?;
+    }
     machine.execute_until_loop()?;
 
-    println!("Execution complete.");
+    match machine.exit_code() {
+        Some(code) => println!("Execution complete, exit code {code}."),
+        None => println!("Execution complete."),
+    }
 
     Ok(())
 }
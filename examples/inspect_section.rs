@@ -28,8 +28,8 @@ fn main() -> Result<()> {
     let elf_file = file_parser::ElfFile::from_buffer(slice)?;
     let section = elf_file.find_section_by_name(arguments.segment_to_dump)?;
 
-    let mut memory = elf_file.load_memory(4 * 1024 * 1024);
-    let instructions = ElfFile::load_section(section, &mut memory)?;
+    let memory = elf_file.load_memory(4 * 1024 * 1024)?;
+    let instructions = ElfFile::load_section(section, &memory, elf_file.xlen())?;
 
     let mut sorted: Vec<_> = instructions.iter().collect();
     sorted.sort_by_key(|(addr, _)| *addr);
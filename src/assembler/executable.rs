@@ -0,0 +1,312 @@
+use elf::abi;
+
+use crate::{
+    assembler::{PAGE_SIZE, Program, SectionKind},
+    file_parser::RiscvAttributes,
+};
+
+const EHDR_SIZE: usize = 52;
+const PHDR_SIZE: usize = 32;
+const SHDR_SIZE: usize = 40;
+const SYM_SIZE: usize = 16;
+
+/* Section header indices, in the order sections are written. */
+const TEXT: u16 = 1;
+const DATA: u16 = 2;
+const BSS: u16 = 3;
+const STRTAB: u16 = 5;
+const SHSTRTAB: u16 = 6;
+
+/// A string table under construction.
+struct Strings(Vec<u8>);
+
+impl Strings {
+    fn new() -> Self {
+        Strings(vec![0])
+    }
+
+    /* Appends name and returns its offset in the table. */
+    fn add(&mut self, name: &str) -> u32 {
+        let offset = self.0.len() as u32;
+        self.0.extend_from_slice(name.as_bytes());
+        self.0.push(0);
+
+        offset
+    }
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+/* Pads out with zeros up to offset. */
+fn pad_to(out: &mut Vec<u8>, offset: usize) {
+    debug_assert!(out.len() <= offset);
+    out.resize(offset, 0);
+}
+
+/* The first file offset at or after start that is congruent to addr modulo the page
+ * size, as PT_LOAD segments need. */
+fn segment_offset(start: usize, addr: u64) -> usize {
+    start.next_multiple_of(PAGE_SIZE as usize) + (addr % PAGE_SIZE) as usize
+}
+
+/// A program header.
+struct Segment {
+    offset: usize,
+    addr: u64,
+    file_size: u64,
+    memory_size: u64,
+    flags: u32,
+}
+
+/// A section header.
+struct SectionHeader {
+    name: u32,
+    kind: u32,
+    flags: u64,
+    addr: u64,
+    offset: usize,
+    size: u64,
+    link: u32,
+    info: u32,
+    align: u64,
+    entry_size: u64,
+}
+
+impl Segment {
+    fn write(&self, out: &mut Vec<u8>) {
+        put_u32(out, abi::PT_LOAD as u64);
+        put_u32(out, self.offset as u64);
+        put_u32(out, self.addr);
+        put_u32(out, self.addr);
+        put_u32(out, self.file_size);
+        put_u32(out, self.memory_size);
+        put_u32(out, self.flags as u64);
+        put_u32(out, PAGE_SIZE);
+    }
+}
+
+impl SectionHeader {
+    fn write(&self, out: &mut Vec<u8>) {
+        put_u32(out, self.name as u64);
+        put_u32(out, self.kind as u64);
+        put_u32(out, self.flags);
+        put_u32(out, self.addr);
+        put_u32(out, self.offset as u64);
+        put_u32(out, self.size);
+        put_u32(out, self.link as u64);
+        put_u32(out, self.info as u64);
+        put_u32(out, self.align);
+        put_u32(out, self.entry_size);
+    }
+}
+
+impl Program {
+    /* The program as an ELF32 executable: a read-execute segment for .text, a
+     * read-write one for .data and .bss, a symbol table with every label, and
+     * .riscv.attributes if the program gave any. */
+    pub fn to_elf(&self) -> Vec<u8> {
+        let text = &self.text;
+        let data = &self.data;
+        let bss_size = self.bss.end - self.bss.start;
+
+        let segments_end = EHDR_SIZE + 2 * PHDR_SIZE;
+        let text_offset = segment_offset(segments_end, text.base);
+        let data_offset = segment_offset(text_offset + text.bytes.len(), data.base);
+        let segments = [
+            Segment {
+                offset: text_offset,
+                addr: text.base,
+                file_size: text.bytes.len() as u64,
+                memory_size: text.bytes.len() as u64,
+                flags: abi::PF_R | abi::PF_X,
+            },
+            Segment {
+                offset: data_offset,
+                addr: data.base,
+                file_size: data.bytes.len() as u64,
+                memory_size: self.bss.end.max(data.end()) - data.base,
+                flags: abi::PF_R | abi::PF_W,
+            },
+        ];
+
+        // locals come before globals, as sh_info of .symtab gives the first global.
+        let mut strings = Strings::new();
+        let mut symbols = vec![0; SYM_SIZE];
+        let mut first_global = 1;
+        for global in [false, true] {
+            let binding = if global {
+                abi::STB_GLOBAL
+            } else {
+                abi::STB_LOCAL
+            };
+            for (name, symbol) in self.symbols.iter().filter(|(_, s)| s.global == global) {
+                put_u32(&mut symbols, strings.add(name) as u64);
+                put_u32(&mut symbols, symbol.addr);
+                put_u32(&mut symbols, 0);
+                symbols.push(binding << 4 | abi::STT_NOTYPE);
+                symbols.push(abi::STV_DEFAULT);
+                put_u16(&mut symbols, section_index(symbol.section));
+            }
+            if !global {
+                first_global = symbols.len() / SYM_SIZE;
+            }
+        }
+
+        let attributes = match self.attributes == RiscvAttributes::default() {
+            true => Vec::new(),
+            false => self.attributes.to_bytes(),
+        };
+
+        let mut names = Strings::new();
+        let symbols_offset = (data_offset + data.bytes.len()).next_multiple_of(4);
+        let strings_offset = symbols_offset + symbols.len();
+        let attributes_offset = strings_offset + strings.0.len();
+        let names_offset = attributes_offset + attributes.len();
+        let mut headers = vec![
+            SectionHeader {
+                name: names.add(".text"),
+                kind: abi::SHT_PROGBITS,
+                flags: (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64,
+                addr: text.base,
+                offset: text_offset,
+                size: text.bytes.len() as u64,
+                link: 0,
+                info: 0,
+                align: 4,
+                entry_size: 0,
+            },
+            SectionHeader {
+                name: names.add(".data"),
+                kind: abi::SHT_PROGBITS,
+                flags: (abi::SHF_ALLOC | abi::SHF_WRITE) as u64,
+                addr: data.base,
+                offset: data_offset,
+                size: data.bytes.len() as u64,
+                link: 0,
+                info: 0,
+                align: 1,
+                entry_size: 0,
+            },
+            SectionHeader {
+                name: names.add(".bss"),
+                kind: abi::SHT_NOBITS,
+                flags: (abi::SHF_ALLOC | abi::SHF_WRITE) as u64,
+                addr: self.bss.start,
+                offset: data_offset + (self.bss.start - data.base) as usize,
+                size: bss_size,
+                link: 0,
+                info: 0,
+                align: 1,
+                entry_size: 0,
+            },
+            SectionHeader {
+                name: names.add(".symtab"),
+                kind: abi::SHT_SYMTAB,
+                flags: 0,
+                addr: 0,
+                offset: symbols_offset,
+                size: symbols.len() as u64,
+                link: STRTAB as u32,
+                info: first_global as u32,
+                align: 4,
+                entry_size: SYM_SIZE as u64,
+            },
+            SectionHeader {
+                name: names.add(".strtab"),
+                kind: abi::SHT_STRTAB,
+                flags: 0,
+                addr: 0,
+                offset: strings_offset,
+                size: strings.0.len() as u64,
+                link: 0,
+                info: 0,
+                align: 1,
+                entry_size: 0,
+            },
+            SectionHeader {
+                name: names.add(".shstrtab"),
+                kind: abi::SHT_STRTAB,
+                flags: 0,
+                addr: 0,
+                offset: names_offset,
+                size: 0,
+                link: 0,
+                info: 0,
+                align: 1,
+                entry_size: 0,
+            },
+        ];
+        if !attributes.is_empty() {
+            headers.push(SectionHeader {
+                name: names.add(".riscv.attributes"),
+                kind: abi::SHT_RISCV_ATTRIBUTES,
+                flags: 0,
+                addr: 0,
+                offset: attributes_offset,
+                size: attributes.len() as u64,
+                link: 0,
+                info: 0,
+                align: 1,
+                entry_size: 0,
+            });
+        }
+        // .shstrtab holds its own name, so its size is only known now.
+        headers[SHSTRTAB as usize - 1].size = names.0.len() as u64;
+        let section_headers_offset = (names_offset + names.0.len()).next_multiple_of(4);
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&abi::ELFMAGIC);
+        out.extend_from_slice(&[abi::ELFCLASS32, abi::ELFDATA2LSB, abi::EV_CURRENT]);
+        out.push(abi::ELFOSABI_SYSV);
+        pad_to(&mut out, abi::EI_NIDENT);
+        put_u16(&mut out, abi::ET_EXEC);
+        put_u16(&mut out, abi::EM_RISCV);
+        put_u32(&mut out, abi::EV_CURRENT as u64);
+        put_u32(&mut out, self.entry);
+        put_u32(&mut out, EHDR_SIZE as u64);
+        put_u32(&mut out, section_headers_offset as u64);
+        // e_flags: no compressed instructions, soft-float ABI.
+        put_u32(&mut out, 0);
+        put_u16(&mut out, EHDR_SIZE as u16);
+        put_u16(&mut out, PHDR_SIZE as u16);
+        put_u16(&mut out, segments.len() as u16);
+        put_u16(&mut out, SHDR_SIZE as u16);
+        put_u16(&mut out, headers.len() as u16 + 1);
+        put_u16(&mut out, SHSTRTAB);
+
+        for segment in &segments {
+            segment.write(&mut out);
+        }
+        pad_to(&mut out, text_offset);
+        out.extend_from_slice(&text.bytes);
+        pad_to(&mut out, data_offset);
+        out.extend_from_slice(&data.bytes);
+        pad_to(&mut out, symbols_offset);
+        out.extend_from_slice(&symbols);
+        out.extend_from_slice(&strings.0);
+        out.extend_from_slice(&attributes);
+        out.extend_from_slice(&names.0);
+
+        pad_to(&mut out, section_headers_offset);
+        out.extend_from_slice(&[0; SHDR_SIZE]);
+        for header in &headers {
+            header.write(&mut out);
+        }
+
+        out
+    }
+}
+
+fn section_index(section: SectionKind) -> u16 {
+    match section {
+        SectionKind::Text => TEXT,
+        SectionKind::Data => DATA,
+        SectionKind::Bss => BSS,
+    }
+}
@@ -0,0 +1,792 @@
+mod executable;
+mod parser;
+mod pseudo;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
+
+use bilge::prelude::u5;
+use thiserror::Error;
+
+use crate::{
+    assembler::parser::Statement,
+    file_parser::RiscvAttributes,
+    instruction::{
+        self, Instruction,
+        definitions::{Extension, Op},
+        formats::Format,
+    },
+    isa::Xlen,
+    loader::{Image, Segment},
+    machine::Permissions,
+};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("line {0}: cannot parse {1:?}.")]
+    InvalidOperand(usize, String),
+
+    #[error("line {0}: invalid operands for {1}.")]
+    InvalidOperands(usize, String),
+
+    #[error("line {0}: {1} is not a register.")]
+    InvalidRegister(usize, String),
+
+    #[error("line {0}: unknown instruction {1}.")]
+    UnknownInstruction(usize, String),
+
+    #[error("line {0}: the assembler does not support {1}.")]
+    UnsupportedInstruction(usize, Op),
+
+    #[error("line {0}: unknown directive {1}.")]
+    UnknownDirective(usize, String),
+
+    #[error("line {0}: undefined symbol {1}.")]
+    UndefinedSymbol(usize, String),
+
+    #[error("line {0}: symbol {1} is already defined.")]
+    DuplicateSymbol(usize, String),
+
+    #[error("line {0}: {1} cannot be placed in .bss, which only reserves zeroed space.")]
+    InitializedBss(usize, String),
+
+    #[error("line {0}: {1} is out of range.")]
+    ValueOutOfRange(usize, String),
+
+    #[error("line {0}: {1}")]
+    Encoding(usize, instruction::Error),
+}
+
+/* Extensions whose instructions only take integer registers and immediates. */
+const SUPPORTED: &[Extension] = &[
+    Extension::I,
+    Extension::M,
+    Extension::Zmmul,
+    Extension::Zicsr,
+    Extension::Zifencei,
+    Extension::Zba,
+    Extension::Zbb,
+    Extension::Zbs,
+];
+
+const PAGE_SIZE: u64 = 4096;
+const NOP: u32 = 0x0000_0013; // addi x0, x0, 0
+
+/* CSRs that can be named in csr operands. */
+const CSRS: &[(&str, u16)] = &[
+    ("fflags", 0x001),
+    ("frm", 0x002),
+    ("fcsr", 0x003),
+    ("sstatus", 0x100),
+    ("sie", 0x104),
+    ("stvec", 0x105),
+    ("scounteren", 0x106),
+    ("sscratch", 0x140),
+    ("sepc", 0x141),
+    ("scause", 0x142),
+    ("stval", 0x143),
+    ("sip", 0x144),
+    ("satp", 0x180),
+    ("mstatus", 0x300),
+    ("misa", 0x301),
+    ("medeleg", 0x302),
+    ("mideleg", 0x303),
+    ("mie", 0x304),
+    ("mtvec", 0x305),
+    ("mcounteren", 0x306),
+    ("mscratch", 0x340),
+    ("mepc", 0x341),
+    ("mcause", 0x342),
+    ("mtval", 0x343),
+    ("mip", 0x344),
+    ("mcycle", 0xB00),
+    ("minstret", 0xB02),
+    ("mcycleh", 0xB80),
+    ("minstreth", 0xB82),
+    ("cycle", 0xC00),
+    ("time", 0xC01),
+    ("instret", 0xC02),
+    ("cycleh", 0xC80),
+    ("timeh", 0xC81),
+    ("instreth", 0xC82),
+    ("mvendorid", 0xF11),
+    ("marchid", 0xF12),
+    ("mimpid", 0xF13),
+    ("mhartid", 0xF14),
+];
+
+fn csr_number(name: &str) -> Option<u16> {
+    let numbered = |prefix: &str, base: u16, count: u16| {
+        let index: u16 = name.strip_prefix(prefix)?.parse().ok()?;
+        (index < count).then_some(base + index)
+    };
+
+    CSRS.iter()
+        .find(|(csr, _)| *csr == name)
+        .map(|&(_, number)| number)
+        .or_else(|| numbered("pmpcfg", 0x3A0, 4))
+        .or_else(|| numbered("pmpaddr", 0x3B0, 16))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SectionKind {
+    Text,
+    Data,
+    Bss,
+}
+
+/// Bytes placed at an address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub base: u64,
+    pub bytes: Vec<u8>,
+}
+
+impl Section {
+    pub fn end(&self) -> u64 {
+        self.base + self.bytes.len() as u64
+    }
+}
+
+/// A label of the program.
+#[derive(Debug, Clone, Copy)]
+struct Symbol {
+    addr: u64,
+    section: SectionKind,
+    /// Declared with `.globl`.
+    global: bool,
+}
+
+/// An assembled program: its sections, where it starts, and the address of every label.
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub text: Section,
+    pub data: Section,
+    /// Zeroed space right after data.
+    pub bss: Range<u64>,
+    pub entry: u64,
+    /// Given with `.attribute`, and written to .riscv.attributes of the ELF file.
+    pub attributes: RiscvAttributes,
+    symbols: BTreeMap<String, Symbol>,
+}
+
+impl Program {
+    pub fn symbol(&self, name: &str) -> Option<u64> {
+        self.symbols.get(name).map(|symbol| symbol.addr)
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.symbols
+            .iter()
+            .map(|(name, symbol)| (name.as_str(), symbol.addr))
+    }
+
+    /* The program as one raw image starting at the lowest section, with the space
+     * between sections zero-filled. .bss is left out, as loaders zero it. */
+    pub fn image(&self) -> (u64, Vec<u8>) {
+        let sections = [&self.text, &self.data];
+        let base = sections
+            .iter()
+            .map(|section| section.base)
+            .min()
+            .unwrap_or(0);
+        let end = sections
+            .iter()
+            .map(|section| section.end())
+            .max()
+            .unwrap_or(0);
+
+        let mut image = vec![0; (end - base) as usize];
+        for section in sections {
+            let start = (section.base - base) as usize;
+            image[start..start + section.bytes.len()].copy_from_slice(&section.bytes);
+        }
+
+        (base, image)
+    }
+}
+
+/* The segments an ELF file of the program would have: text is readable and executable,
+ * and data, followed by bss, readable and writable. */
+impl From<&Program> for Image {
+    fn from(program: &Program) -> Self {
+        let text = Segment {
+            addr: program.text.base,
+            data: program.text.bytes.clone(),
+            size: program.text.bytes.len() as u64,
+            permissions: Permissions {
+                read: true,
+                write: false,
+                execute: true,
+            },
+        };
+        let data = Segment {
+            addr: program.data.base,
+            data: program.data.bytes.clone(),
+            size: program.bss.end.max(program.data.end()) - program.data.base,
+            permissions: Permissions {
+                read: true,
+                write: true,
+                execute: false,
+            },
+        };
+
+        Image {
+            segments: [text, data]
+                .into_iter()
+                .filter(|segment| segment.size > 0)
+                .collect(),
+            entry: Some(program.entry),
+            symbols: program
+                .symbols()
+                .map(|(name, addr)| (name.to_string(), addr))
+                .collect(),
+            ..Image::default()
+        }
+    }
+}
+
+/// A statement placed in a section by the first pass.
+struct Item {
+    line: usize,
+    section: SectionKind,
+    offset: u64,
+    size: u64,
+    statement: Statement,
+}
+
+/// Where labels were defined, relative to the start of their section.
+#[derive(Default)]
+struct Labels {
+    named: HashMap<String, (SectionKind, u64)>,
+    /// Labels declared with `.globl`, with the line of the declaration.
+    globals: HashMap<String, usize>,
+    /// Numeric labels such as `1:`, which may be defined many times and are referred
+    /// to as `1b` or `1f`, with the line of every definition.
+    numeric: HashMap<String, Vec<(usize, SectionKind, u64)>>,
+}
+
+/// The result of the first pass.
+struct Layout {
+    items: Vec<Item>,
+    labels: Labels,
+    /// The size of each section.
+    sizes: HashMap<SectionKind, u64>,
+    /// The largest alignment asked for in .bss.
+    bss_alignment: u64,
+    attributes: RiscvAttributes,
+}
+
+/// Assembles RV32 integer programs: the base ISA, M, Zicsr, Zifencei and the bit
+/// manipulation extensions, with the usual pseudo-instructions and directives.
+pub struct Assembler {
+    text_base: u64,
+    data_base: Option<u64>,
+}
+
+/* Text starts at 0x1000 and data on the page after it. */
+impl Default for Assembler {
+    fn default() -> Self {
+        Assembler {
+            text_base: 0x1000,
+            data_base: None,
+        }
+    }
+}
+
+impl Assembler {
+    pub fn set_text_base(&mut self, base: u64) {
+        self.text_base = base;
+    }
+
+    /* Places data at base instead of on the page after text. */
+    pub fn set_data_base(&mut self, base: u64) {
+        self.data_base = Some(base);
+    }
+
+    pub fn assemble(&self, source: &str) -> Result<Program, Error> {
+        let lines = source
+            .lines()
+            .enumerate()
+            .map(|(i, text)| parser::parse_line(i + 1, text))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let Layout {
+            items,
+            labels,
+            sizes,
+            bss_alignment,
+            attributes,
+        } = Self::layout(lines)?;
+
+        let text_base = self.text_base;
+        let data_base = self
+            .data_base
+            .unwrap_or((text_base + sizes[&SectionKind::Text]).next_multiple_of(PAGE_SIZE));
+        let bss_base = (data_base + sizes[&SectionKind::Data]).next_multiple_of(bss_alignment);
+        let base = |section| match section {
+            SectionKind::Text => text_base,
+            SectionKind::Data => data_base,
+            SectionKind::Bss => bss_base,
+        };
+
+        if let Some((name, &line)) = labels
+            .globals
+            .iter()
+            .find(|(name, _)| !labels.named.contains_key(*name))
+        {
+            return Err(Error::UndefinedSymbol(line, name.clone()));
+        }
+        let symbols: BTreeMap<String, Symbol> = labels
+            .named
+            .iter()
+            .map(|(name, &(section, offset))| {
+                let symbol = Symbol {
+                    addr: base(section) + offset,
+                    section,
+                    global: labels.globals.contains_key(name),
+                };
+                (name.clone(), symbol)
+            })
+            .collect();
+
+        let mut text = vec![0; sizes[&SectionKind::Text] as usize];
+        let mut data = vec![0; sizes[&SectionKind::Data] as usize];
+        for item in items {
+            let resolve = |name: &str| {
+                symbols
+                    .get(name)
+                    .map(|symbol| symbol.addr)
+                    .or_else(|| Self::numeric_label(&labels, name, item.line, base))
+            };
+            let pc = base(item.section) + item.offset;
+            let bytes = Self::emit(&item, pc, &resolve)?;
+            debug_assert_eq!(bytes.len() as u64, item.size, "line {}", item.line);
+
+            let section = match item.section {
+                SectionKind::Text => &mut text,
+                SectionKind::Data => &mut data,
+                SectionKind::Bss => unreachable!("nothing is emitted into .bss"),
+            };
+            let start = item.offset as usize;
+            section[start..start + bytes.len()].copy_from_slice(&bytes);
+        }
+
+        Ok(Program {
+            entry: symbols
+                .get("_start")
+                .map_or(text_base, |symbol| symbol.addr),
+            text: Section {
+                base: text_base,
+                bytes: text,
+            },
+            data: Section {
+                base: data_base,
+                bytes: data,
+            },
+            bss: bss_base..bss_base + sizes[&SectionKind::Bss],
+            attributes,
+            symbols,
+        })
+    }
+
+    /* `1b` is the closest `1:` at or before line, and `1f` the closest one after it. */
+    fn numeric_label(
+        labels: &Labels,
+        name: &str,
+        line: usize,
+        base: impl Fn(SectionKind) -> u64,
+    ) -> Option<u64> {
+        let (label, direction) = name.split_at(name.len().checked_sub(1)?);
+        let definitions = labels.numeric.get(label)?;
+        let (_, section, offset) = match direction {
+            "b" => definitions
+                .iter()
+                .rev()
+                .find(|(defined, ..)| *defined <= line)?,
+            "f" => definitions.iter().find(|(defined, ..)| *defined > line)?,
+            _ => return None,
+        };
+
+        Some(base(*section) + offset)
+    }
+
+    /* The first pass: places every statement in its section and defines labels. */
+    fn layout(lines: Vec<parser::Line>) -> Result<Layout, Error> {
+        let mut items = Vec::new();
+        let mut labels = Labels::default();
+        let mut offsets = HashMap::from([
+            (SectionKind::Text, 0),
+            (SectionKind::Data, 0),
+            (SectionKind::Bss, 0),
+        ]);
+        let mut section = SectionKind::Text;
+        let mut bss_alignment = 1;
+        let mut attributes = RiscvAttributes::default();
+
+        for line in lines {
+            let number = line.number;
+            for label in line.labels {
+                let offset = offsets[&section];
+                if label.chars().all(|c| c.is_ascii_digit()) {
+                    let definitions = labels.numeric.entry(label.to_string()).or_default();
+                    definitions.push((number, section, offset));
+                } else if labels
+                    .named
+                    .insert(label.to_string(), (section, offset))
+                    .is_some()
+                {
+                    return Err(Error::DuplicateSymbol(number, label.to_string()));
+                }
+            }
+
+            let Some(statement) = line.statement else {
+                continue;
+            };
+            let operands: Vec<&str> = statement.operands.iter().map(String::as_str).collect();
+            let offset = offsets[&section];
+
+            let size = match (statement.name.as_str(), operands.as_slice()) {
+                (".text", []) => {
+                    section = SectionKind::Text;
+                    continue;
+                }
+                (".data", []) => {
+                    section = SectionKind::Data;
+                    continue;
+                }
+                (".bss", []) => {
+                    section = SectionKind::Bss;
+                    continue;
+                }
+                (".section", [name, ..]) => {
+                    section = match *name {
+                        ".text" => SectionKind::Text,
+                        ".data" | ".rodata" | ".sdata" => SectionKind::Data,
+                        ".bss" | ".sbss" => SectionKind::Bss,
+                        _ => return Err(Error::UnknownDirective(number, name.to_string())),
+                    };
+                    continue;
+                }
+                (".globl" | ".global", names) => {
+                    for name in names {
+                        labels.globals.insert(name.to_string(), number);
+                    }
+                    continue;
+                }
+                (".local" | ".type" | ".size" | ".file" | ".ident", _) => continue,
+                (".attribute", [tag, value]) => {
+                    Self::attribute(number, &mut attributes, tag, value)?;
+                    continue;
+                }
+                (".align" | ".p2align", [power]) => {
+                    let power = Self::constant(number, power)?;
+                    if !(0..=12).contains(&power) {
+                        return Err(Error::ValueOutOfRange(number, power.to_string()));
+                    }
+                    if section == SectionKind::Bss {
+                        bss_alignment = bss_alignment.max(1 << power);
+                    }
+                    offset.next_multiple_of(1 << power) - offset
+                }
+                (".balign", [alignment]) => {
+                    let alignment = Self::constant(number, alignment)?;
+                    if !(1..=PAGE_SIZE as i64).contains(&alignment) {
+                        return Err(Error::ValueOutOfRange(number, alignment.to_string()));
+                    }
+                    if section == SectionKind::Bss {
+                        bss_alignment = bss_alignment.max(alignment as u64);
+                    }
+                    offset.next_multiple_of(alignment as u64) - offset
+                }
+                (".zero" | ".space", _) if section == SectionKind::Bss => {
+                    Self::size(number, &statement, &operands)?
+                }
+                (name, _) if section == SectionKind::Bss => {
+                    return Err(Error::InitializedBss(number, name.to_string()));
+                }
+                _ => Self::size(number, &statement, &operands)?,
+            };
+            *offsets.get_mut(&section).unwrap() += size;
+            if section == SectionKind::Bss {
+                continue;
+            }
+
+            items.push(Item {
+                line: number,
+                section,
+                offset,
+                size,
+                statement,
+            });
+        }
+
+        Ok(Layout {
+            items,
+            labels,
+            sizes: offsets,
+            bss_alignment,
+            attributes,
+        })
+    }
+
+    /* Records `.attribute tag, value`, where tag is the name GNU as gives it or its
+     * number. */
+    fn attribute(
+        line: usize,
+        attributes: &mut RiscvAttributes,
+        tag: &str,
+        value: &str,
+    ) -> Result<(), Error> {
+        let number = || {
+            u64::try_from(Self::constant(line, value)?)
+                .map_err(|_| Error::ValueOutOfRange(line, value.to_string()))
+        };
+        match tag {
+            "stack_align" | "4" => {
+                let align = number()?;
+                if !align.is_power_of_two() {
+                    return Err(Error::ValueOutOfRange(line, value.to_string()));
+                }
+                attributes.stack_align = Some(align);
+            }
+            "arch" | "5" => {
+                let arch = String::from_utf8(Self::string_literal(line, value)?)
+                    .map_err(|_| Error::InvalidOperand(line, value.to_string()))?;
+                attributes.arch = Some(arch);
+            }
+            "unaligned_access" | "6" => attributes.unaligned_access = Some(number()? != 0),
+            // the privileged spec version does not change what the machine runs.
+            "priv_spec" | "priv_spec_minor" | "priv_spec_revision" | "8" | "10" | "12" => {}
+            _ => return Err(Error::InvalidOperand(line, tag.to_string())),
+        }
+
+        Ok(())
+    }
+
+    fn constant(line: usize, text: &str) -> Result<i64, Error> {
+        parser::evaluate(line, text, &|_| None).map(|value| value.value)
+    }
+
+    fn string_literal(line: usize, text: &str) -> Result<Vec<u8>, Error> {
+        parser::string_literal(text).ok_or_else(|| Error::InvalidOperand(line, text.to_string()))
+    }
+
+    /* The number of bytes a statement takes. */
+    fn size(line: usize, statement: &Statement, operands: &[&str]) -> Result<u64, Error> {
+        let count = operands.len() as u64;
+        let strings = || -> Result<u64, Error> {
+            operands.iter().try_fold(0, |total, text| {
+                Ok(total + Self::string_literal(line, text)?.len() as u64)
+            })
+        };
+
+        Ok(match statement.name.as_str() {
+            ".byte" => count,
+            ".half" | ".short" | ".2byte" => 2 * count,
+            ".word" | ".long" | ".4byte" => 4 * count,
+            ".dword" | ".quad" | ".8byte" => 8 * count,
+            ".ascii" => strings()?,
+            ".asciz" | ".string" => strings()? + count,
+            ".zero" | ".space" => match operands {
+                [size] => u64::try_from(Self::constant(line, size)?)
+                    .map_err(|_| Error::ValueOutOfRange(line, size.to_string()))?,
+                _ => return Err(Error::InvalidOperands(line, statement.name.clone())),
+            },
+            name if name.starts_with('.') => {
+                return Err(Error::UnknownDirective(line, name.to_string()));
+            }
+            // symbols are not known yet, but do not change the size of an expansion.
+            _ => {
+                let evaluate = |text: &str| parser::evaluate(line, text, &|_| Some(0));
+                4 * pseudo::expand(line, statement, 0, &evaluate)?.len() as u64
+            }
+        })
+    }
+
+    /* The second pass: the bytes of a statement placed at pc. */
+    fn emit(item: &Item, pc: u64, resolve: &dyn Fn(&str) -> Option<u64>) -> Result<Vec<u8>, Error> {
+        let line = item.line;
+        let statement = &item.statement;
+        let evaluate = |text: &str| parser::evaluate(line, text, resolve);
+        let values = |width: usize| -> Result<Vec<u8>, Error> {
+            let bits = 8 * width as u32;
+            let mut bytes = Vec::new();
+            for text in &statement.operands {
+                let value = evaluate(text)?.value;
+                // a value fits if it is a signed or an unsigned number of that width.
+                if bits < 64 && !(-(1 << (bits - 1))..1 << bits).contains(&value) {
+                    return Err(Error::ValueOutOfRange(line, text.clone()));
+                }
+                bytes.extend_from_slice(&value.to_le_bytes()[..width]);
+            }
+
+            Ok(bytes)
+        };
+        let strings = |terminated: bool| -> Result<Vec<u8>, Error> {
+            let mut bytes = Vec::new();
+            for text in &statement.operands {
+                bytes.extend(Self::string_literal(line, text)?);
+                if terminated {
+                    bytes.push(0);
+                }
+            }
+
+            Ok(bytes)
+        };
+
+        match statement.name.as_str() {
+            ".byte" => values(1),
+            ".half" | ".short" | ".2byte" => values(2),
+            ".word" | ".long" | ".4byte" => values(4),
+            ".dword" | ".quad" | ".8byte" => values(8),
+            ".ascii" => strings(false),
+            ".asciz" | ".string" => strings(true),
+            // padding: nops where instructions may follow, zeros elsewhere.
+            ".align" | ".p2align" | ".balign" if item.section == SectionKind::Text => {
+                let mut bytes = vec![0; item.size as usize];
+                let skip = ((pc.next_multiple_of(4) - pc) as usize).min(bytes.len());
+                for chunk in bytes[skip..].chunks_exact_mut(4) {
+                    chunk.copy_from_slice(&NOP.to_le_bytes());
+                }
+                Ok(bytes)
+            }
+            ".align" | ".p2align" | ".balign" | ".zero" | ".space" => {
+                Ok(vec![0; item.size as usize])
+            }
+            _ => {
+                let mut bytes = Vec::new();
+                for (i, base) in pseudo::expand(line, statement, pc, &evaluate)?
+                    .iter()
+                    .enumerate()
+                {
+                    let word = Self::encode(line, base, pc + 4 * i as u64, resolve)?;
+                    bytes.extend_from_slice(&word.to_le_bytes());
+                }
+                Ok(bytes)
+            }
+        }
+    }
+
+    /* Encodes a base instruction at pc. */
+    fn encode(
+        line: usize,
+        statement: &Statement,
+        pc: u64,
+        resolve: &dyn Fn(&str) -> Option<u64>,
+    ) -> Result<u32, Error> {
+        let name = &statement.name;
+        let op = Op::ALL
+            .iter()
+            .copied()
+            .find(|op| op.to_string() == *name)
+            .ok_or_else(|| Error::UnknownInstruction(line, name.clone()))?;
+        if !SUPPORTED.contains(&op.extension()) {
+            return Err(Error::UnsupportedInstruction(line, op));
+        }
+        let encoding = op.encodings()[0];
+
+        let zero = u5::new(0);
+        let register = |text: &str| {
+            parser::register(text).ok_or_else(|| Error::InvalidRegister(line, text.to_string()))
+        };
+        let immediate = |text: &str| -> Result<i32, Error> {
+            let value = parser::evaluate(line, text, resolve)?.value;
+            i32::try_from(value).map_err(|_| Error::ValueOutOfRange(line, text.to_string()))
+        };
+        // branch and jump targets are addresses if they use a symbol, offsets if not.
+        let offset = |text: &str| -> Result<i32, Error> {
+            let target = parser::evaluate(line, text, resolve)?;
+            let offset = match target.relocatable {
+                true => target.value.wrapping_sub(pc as i64),
+                false => target.value,
+            };
+            i32::try_from(offset).map_err(|_| Error::ValueOutOfRange(line, text.to_string()))
+        };
+        let memory = |text: &str| -> Result<(u5, i32), Error> {
+            let (offset, base) = parser::memory_operand(text)
+                .ok_or_else(|| Error::InvalidOperand(line, text.to_string()))?;
+            let offset = if offset.is_empty() {
+                0
+            } else {
+                immediate(offset)?
+            };
+            Ok((register(base)?, offset))
+        };
+        let csr = |text: &str| match csr_number(text) {
+            Some(number) => Ok(number as i32),
+            None => immediate(text),
+        };
+        let fence_set = |text: &str| {
+            text.chars().try_fold(0, |set, access| {
+                let bit = match access {
+                    'i' => 8,
+                    'o' => 4,
+                    'r' => 2,
+                    'w' => 1,
+                    _ => return Err(Error::InvalidOperand(line, text.to_string())),
+                };
+                Ok(set | bit)
+            })
+        };
+
+        let operands: Vec<&str> = statement.operands.iter().map(String::as_str).collect();
+        let (rd, rs1, rs2, imm) = match (op.format(), operands.as_slice()) {
+            (Format::R, &[rs1, rs2]) if op == Op::sfence_vma => {
+                (zero, register(rs1)?, register(rs2)?, 0)
+            }
+            (Format::R, &[rd, rs1]) if encoding.rs2.is_some() => {
+                (register(rd)?, register(rs1)?, zero, 0)
+            }
+            (Format::R, &[rd, rs1, rs2]) => (register(rd)?, register(rs1)?, register(rs2)?, 0),
+
+            (Format::I, &[]) if op != Op::fence => (zero, zero, zero, 0),
+            (Format::I, &[pred, succ]) if op == Op::fence => {
+                (zero, zero, zero, fence_set(pred)? << 4 | fence_set(succ)?)
+            }
+            (Format::I, &[rd, number, source]) if op.extension() == Extension::Zicsr => {
+                let rs1 = match op {
+                    Op::csrrwi | Op::csrrsi | Op::csrrci => {
+                        let uimm = immediate(source)?;
+                        u8::try_from(uimm)
+                            .ok()
+                            .filter(|&uimm| uimm < 32)
+                            .map(u5::new)
+                            .ok_or_else(|| Error::ValueOutOfRange(line, source.to_string()))?
+                    }
+                    _ => register(source)?,
+                };
+                (register(rd)?, rs1, zero, csr(number)?)
+            }
+            (Format::I, &[rd, rs1]) if encoding.imm.is_some() => {
+                (register(rd)?, register(rs1)?, zero, 0)
+            }
+            (Format::I, &[rd, address]) if address.ends_with(')') => {
+                let (rs1, offset) = memory(address)?;
+                (register(rd)?, rs1, zero, offset)
+            }
+            (Format::I, &[rd, rs1, imm]) => (register(rd)?, register(rs1)?, zero, immediate(imm)?),
+
+            (Format::S, &[rs2, address]) => {
+                let (rs1, offset) = memory(address)?;
+                (zero, rs1, register(rs2)?, offset)
+            }
+            (Format::B, &[rs1, rs2, target]) => {
+                (zero, register(rs1)?, register(rs2)?, offset(target)?)
+            }
+            (Format::U, &[rd, imm]) => (register(rd)?, zero, zero, immediate(imm)?),
+            (Format::J, &[rd, target]) => (register(rd)?, zero, zero, offset(target)?),
+            _ => return Err(Error::InvalidOperands(line, name.clone())),
+        };
+
+        Instruction::encode(op, rd, rs1, rs2, imm, Xlen::Rv32)
+            .map_err(|err| Error::Encoding(line, err))
+    }
+}
+
+/* Assembles source with text at 0x1000 and data on the page after it. */
+pub fn assemble(source: &str) -> Result<Program, Error> {
+    Assembler::default().assemble(source)
+}
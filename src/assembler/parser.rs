@@ -0,0 +1,268 @@
+use bilge::prelude::u5;
+
+use crate::assembler::Error;
+
+/// A source line split into its labels and the statement after them, if any.
+#[derive(Debug)]
+pub(super) struct Line<'a> {
+    pub number: usize,
+    pub labels: Vec<&'a str>,
+    pub statement: Option<Statement>,
+}
+
+/// An instruction or a directive, e.g. `addi` or `.word`, with its operands.
+#[derive(Debug, Clone)]
+pub(super) struct Statement {
+    pub name: String,
+    pub operands: Vec<String>,
+}
+
+/// The value of an expression, and whether it depends on the address of a symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Value {
+    pub value: i64,
+    pub relocatable: bool,
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$')
+}
+
+/* Strips a trailing `#` comment, except inside string and character literals. */
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), _) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &text[..i],
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/* Splits operands at the commas outside of string literals. */
+fn split_operands(text: &str) -> Vec<&str> {
+    let mut operands = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                operands.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !text[start..].trim().is_empty() || !operands.is_empty() {
+        operands.push(text[start..].trim());
+    }
+
+    operands
+}
+
+pub(super) fn parse_line(number: usize, text: &str) -> Result<Line<'_>, Error> {
+    let mut rest = strip_comment(text).trim();
+    let mut labels = Vec::new();
+
+    // a line starts with any number of `label:`.
+    while let Some((label, after)) = rest.split_once(':') {
+        if label.is_empty() || !label.chars().all(is_symbol_char) {
+            break;
+        }
+        labels.push(label);
+        rest = after.trim_start();
+    }
+
+    if rest.is_empty() {
+        return Ok(Line {
+            number,
+            labels,
+            statement: None,
+        });
+    }
+
+    let (name, operands) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let operands = split_operands(operands);
+    if operands.iter().any(|operand| operand.is_empty()) {
+        return Err(Error::InvalidOperand(number, rest.to_string()));
+    }
+
+    Ok(Line {
+        number,
+        labels,
+        statement: Some(Statement {
+            name: name.to_lowercase(),
+            operands: operands.into_iter().map(str::to_string).collect(),
+        }),
+    })
+}
+
+/* x0-x31 or an ABI name. */
+pub(super) fn register(name: &str) -> Option<u5> {
+    const ABI: [&str; 32] = [
+        "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+        "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+        "t5", "t6",
+    ];
+
+    let index = match name {
+        "fp" => 8,
+        _ => match name.strip_prefix('x').and_then(|index| index.parse().ok()) {
+            Some(index) if index < 32 => index,
+            Some(_) => return None,
+            None => ABI.iter().position(|&abi| abi == name)? as u8,
+        },
+    };
+
+    Some(u5::new(index))
+}
+
+/* Splits a memory operand such as `-4(sp)`, `(a0)` or `%lo(x)(a0)` into its offset
+ * and base register. */
+pub(super) fn memory_operand(operand: &str) -> Option<(&str, &str)> {
+    let inner = operand.strip_suffix(')')?;
+    let open = inner.rfind('(')?;
+
+    Some((inner[..open].trim(), inner[open + 1..].trim()))
+}
+
+/* Parses a string literal, e.g. "hello\n", into its bytes. */
+pub(super) fn string_literal(text: &str) -> Option<Vec<u8>> {
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    let mut bytes = Vec::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        bytes.push(escape(chars.next()?)?);
+    }
+
+    Some(bytes)
+}
+
+fn escape(c: char) -> Option<u8> {
+    Some(match c {
+        'n' => b'\n',
+        't' => b'\t',
+        'r' => b'\r',
+        '0' => 0,
+        '\\' => b'\\',
+        '"' => b'"',
+        '\'' => b'\'',
+        _ => return None,
+    })
+}
+
+fn number(text: &str) -> Option<i64> {
+    let (digits, radix) = if let Some(hex) = text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+        (hex, 16)
+    } else if let Some(binary) = text.strip_prefix("0b").or(text.strip_prefix("0B")) {
+        (binary, 2)
+    } else {
+        (text, 10)
+    };
+
+    // hexadecimal up to u64::MAX is accepted, and wraps like in GNU as.
+    u64::from_str_radix(&digits.replace('_', ""), radix)
+        .ok()
+        .map(|value| value as i64)
+}
+
+fn character(text: &str) -> Option<i64> {
+    let inner = text.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut chars = inner.chars();
+    let c = match chars.next()? {
+        '\\' => escape(chars.next()?)? as char,
+        c => c,
+    };
+
+    chars.next().is_none().then_some(c as i64)
+}
+
+/* Evaluates `term (+|- term)*`, where a term is a number, a character or a symbol.
+ * %hi and %lo select the parts of a value that lui and addi take. */
+pub(super) fn evaluate(
+    line: usize,
+    text: &str,
+    resolve: &dyn Fn(&str) -> Option<u64>,
+) -> Result<Value, Error> {
+    let invalid = || Error::InvalidOperand(line, text.to_string());
+
+    for (modifier, part) in [("%hi(", hi as fn(i64) -> i64), ("%lo(", lo)] {
+        if let Some(inner) = text.strip_prefix(modifier) {
+            let inner = inner.strip_suffix(')').ok_or_else(invalid)?;
+            let value = evaluate(line, inner, resolve)?;
+            return Ok(Value {
+                value: part(value.value),
+                ..value
+            });
+        }
+    }
+
+    let mut total = Value {
+        value: 0,
+        relocatable: false,
+    };
+    let mut rest = text.trim();
+    let mut negate = false;
+    if let Some(after) = rest.strip_prefix('-') {
+        negate = true;
+        rest = after.trim_start();
+    }
+
+    loop {
+        let end = if let Some(after) = rest.strip_prefix('\'') {
+            after.find('\'').map_or(rest.len(), |end| end + 2)
+        } else {
+            rest.find(['+', '-']).unwrap_or(rest.len())
+        };
+        let term = rest[..end].trim();
+
+        let value = if let Some(value) = number(term).or_else(|| character(term)) {
+            value
+        } else if !term.is_empty() && term.chars().all(is_symbol_char) {
+            total.relocatable = true;
+            resolve(term).ok_or_else(|| Error::UndefinedSymbol(line, term.to_string()))? as i64
+        } else {
+            return Err(invalid());
+        };
+        total.value = if negate {
+            total.value.wrapping_sub(value)
+        } else {
+            total.value.wrapping_add(value)
+        };
+
+        rest = rest[end..].trim_start();
+        match rest.chars().next() {
+            None => return Ok(total),
+            Some(sign) => {
+                negate = sign == '-';
+                rest = rest[1..].trim_start();
+            }
+        }
+    }
+}
+
+/* The upper 20 bits for lui or auipc, rounded so that adding lo gives the value. */
+pub(super) fn hi(value: i64) -> i64 {
+    (value.wrapping_add(0x800) >> 12) & 0xFFFFF
+}
+
+/* The lower 12 bits, sign-extended as addi and loads take them. */
+pub(super) fn lo(value: i64) -> i64 {
+    ((value & 0xFFF) ^ 0x800) - 0x800
+}
@@ -0,0 +1,132 @@
+use crate::assembler::{
+    Error,
+    parser::{Statement, Value, hi, lo},
+};
+
+fn base(name: &str, operands: &[&str]) -> Statement {
+    Statement {
+        name: name.to_string(),
+        operands: operands.iter().map(|operand| operand.to_string()).collect(),
+    }
+}
+
+/* auipc and a second instruction that reach target from pc together, as in la, call
+ * and tail. */
+fn pc_relative(
+    target: Value,
+    pc: u64,
+    auipc_rd: &str,
+    name: &str,
+    operands: impl Fn(&str) -> Vec<String>,
+) -> Vec<Statement> {
+    let offset = target.value.wrapping_sub(pc as i64);
+
+    vec![
+        base("auipc", &[auipc_rd, &hi(offset).to_string()]),
+        Statement {
+            name: name.to_string(),
+            operands: operands(&lo(offset).to_string()),
+        },
+    ]
+}
+
+/* li takes one instruction if the value fits in 12 bits, or lui and addi otherwise.
+ * Values that depend on a symbol always take both, so that the size is known before
+ * symbols are. */
+fn load_immediate(
+    line: usize,
+    rd: &str,
+    text: &str,
+    value: Value,
+) -> Result<Vec<Statement>, Error> {
+    if !(i32::MIN as i64..=u32::MAX as i64).contains(&value.value) {
+        return Err(Error::ValueOutOfRange(line, text.to_string()));
+    }
+    let constant = value.value as i32 as i64;
+
+    if (-2048..2048).contains(&constant) && !value.relocatable {
+        return Ok(vec![base("addi", &[rd, "x0", &constant.to_string()])]);
+    }
+
+    let mut expanded = vec![base("lui", &[rd, &hi(constant).to_string()])];
+    if lo(constant) != 0 || value.relocatable {
+        expanded.push(base("addi", &[rd, rd, &lo(constant).to_string()]));
+    }
+
+    Ok(expanded)
+}
+
+/* Expands a pseudo-instruction into the base instructions it stands for, and returns
+ * any other instruction as it is. evaluate gives the value of an operand. */
+pub(super) fn expand(
+    line: usize,
+    statement: &Statement,
+    pc: u64,
+    evaluate: &dyn Fn(&str) -> Result<Value, Error>,
+) -> Result<Vec<Statement>, Error> {
+    let operands: Vec<&str> = statement.operands.iter().map(String::as_str).collect();
+
+    let expanded = match (statement.name.as_str(), operands.as_slice()) {
+        ("nop", []) => base("addi", &["x0", "x0", "0"]),
+        ("mv", [rd, rs]) => base("addi", &[rd, rs, "0"]),
+        ("not", [rd, rs]) => base("xori", &[rd, rs, "-1"]),
+        ("neg", [rd, rs]) => base("sub", &[rd, "x0", rs]),
+        ("seqz", [rd, rs]) => base("sltiu", &[rd, rs, "1"]),
+        ("snez", [rd, rs]) => base("sltu", &[rd, "x0", rs]),
+        ("sltz", [rd, rs]) => base("slt", &[rd, rs, "x0"]),
+        ("sgtz", [rd, rs]) => base("slt", &[rd, "x0", rs]),
+
+        ("beqz", [rs, target]) => base("beq", &[rs, "x0", target]),
+        ("bnez", [rs, target]) => base("bne", &[rs, "x0", target]),
+        ("blez", [rs, target]) => base("bge", &["x0", rs, target]),
+        ("bgez", [rs, target]) => base("bge", &[rs, "x0", target]),
+        ("bltz", [rs, target]) => base("blt", &[rs, "x0", target]),
+        ("bgtz", [rs, target]) => base("blt", &["x0", rs, target]),
+        ("bgt", [rs, rt, target]) => base("blt", &[rt, rs, target]),
+        ("ble", [rs, rt, target]) => base("bge", &[rt, rs, target]),
+        ("bgtu", [rs, rt, target]) => base("bltu", &[rt, rs, target]),
+        ("bleu", [rs, rt, target]) => base("bgeu", &[rt, rs, target]),
+
+        ("j", [target]) => base("jal", &["x0", target]),
+        ("jal", [target]) => base("jal", &["ra", target]),
+        ("jr", [rs]) => base("jalr", &["x0", rs, "0"]),
+        ("jalr", [rs]) => base("jalr", &["ra", rs, "0"]),
+        ("ret", []) => base("jalr", &["x0", "ra", "0"]),
+        ("call", [target]) => {
+            return Ok(pc_relative(evaluate(target)?, pc, "ra", "jalr", |lo| {
+                vec!["ra".into(), "ra".into(), lo.into()]
+            }));
+        }
+        ("tail", [target]) => {
+            return Ok(pc_relative(evaluate(target)?, pc, "t1", "jalr", |lo| {
+                vec!["x0".into(), "t1".into(), lo.into()]
+            }));
+        }
+        ("la" | "lla", [rd, symbol]) => {
+            return Ok(pc_relative(evaluate(symbol)?, pc, rd, "addi", |lo| {
+                vec![rd.to_string(), rd.to_string(), lo.into()]
+            }));
+        }
+        ("li", [rd, value]) => return load_immediate(line, rd, value, evaluate(value)?),
+
+        ("csrr", [rd, csr]) => base("csrrs", &[rd, csr, "x0"]),
+        ("csrw", [csr, rs]) => base("csrrw", &["x0", csr, rs]),
+        ("csrs", [csr, rs]) => base("csrrs", &["x0", csr, rs]),
+        ("csrc", [csr, rs]) => base("csrrc", &["x0", csr, rs]),
+        ("csrwi", [csr, imm]) => base("csrrwi", &["x0", csr, imm]),
+        ("csrsi", [csr, imm]) => base("csrrsi", &["x0", csr, imm]),
+        ("csrci", [csr, imm]) => base("csrrci", &["x0", csr, imm]),
+        ("rdcycle", [rd]) => base("csrrs", &[rd, "cycle", "x0"]),
+        ("rdtime", [rd]) => base("csrrs", &[rd, "time", "x0"]),
+        ("rdinstret", [rd]) => base("csrrs", &[rd, "instret", "x0"]),
+        ("rdcycleh", [rd]) => base("csrrs", &[rd, "cycleh", "x0"]),
+        ("rdtimeh", [rd]) => base("csrrs", &[rd, "timeh", "x0"]),
+        ("rdinstreth", [rd]) => base("csrrs", &[rd, "instreth", "x0"]),
+
+        ("fence", []) => base("fence", &["iorw", "iorw"]),
+
+        _ => statement.clone(),
+    };
+
+    Ok(vec![expanded])
+}
@@ -1,17 +1,22 @@
 use std::collections::HashMap;
 
-use elf::{ElfBytes, abi, endian::LittleEndian, section::SectionHeader};
+use elf::{ElfBytes, abi, endian::LittleEndian, section::SectionHeader, segment::ProgramHeader};
 use thiserror::Error;
 
-use crate::instruction::Instruction;
+use crate::{
+    instruction::{Instruction, definitions::Extension},
+    isa::{Isa, Xlen},
+    loader::Segment,
+    machine::{MemoryRegion, Permissions},
+};
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("ELF file supplied is not built for RISCV.")]
     NotRISCV(),
 
-    #[error("ELF file supplied is not ELF32.")]
-    NotELF32(),
+    #[error("ELF file is {0} but the machine is {1}.")]
+    XlenMismatch(Xlen, Xlen),
 
     #[error("ELF file does not contain a section header.")]
     NoSectionHeader(),
@@ -19,12 +24,33 @@ pub enum Error {
     #[error("ELF file does not contain a segment header.")]
     NoSegmentHeader(),
 
+    #[error("Segment at {0:#X} has {1:#X} bytes in the file, more than its {2:#X} in memory.")]
+    SegmentSizeMismatch(u64, u64, u64),
+
+    #[error("Segment {0:#X}..{1:#X} does not fit in {2:#X} bytes of memory.")]
+    SegmentOutOfRange(u64, u64, u64),
+
+    #[error("Segment at {0:#X} of {1:#X} bytes runs past the end of the address space.")]
+    SegmentOverflow(u64, u64),
+
+    #[error("Section at {0:#X} of {1:#X} bytes does not fit in {2:#X} bytes of memory.")]
+    SectionOutOfRange(u64, u64, u64),
+
     #[error("Section {0} does not exists.")]
     SectionNotFound(String),
 
     #[error("Error while parsing ELF file: {0}")]
     ParseError(#[from] elf::ParseError),
 
+    #[error("Malformed .riscv.attributes section: {0}")]
+    MalformedAttributes(String),
+
+    #[error("ELF file requires an unsupported ISA: {0}")]
+    UnsupportedIsa(#[from] crate::isa::Error),
+
+    #[error("ELF file requires {0}, which the machine does not provide.")]
+    MissingFeature(String),
+
     #[error("Error while parsing instruction: {0}")]
     InstructionParseError(#[from] crate::instruction::Error),
 
@@ -33,6 +59,267 @@ pub enum Error {
     Unknown(String),
 }
 
+/// Floating point calling convention, from `EF_RISCV_FLOAT_ABI`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatAbi {
+    Soft,
+    Single,
+    Double,
+    Quad,
+}
+
+/// The RISC-V specific bits of the ELF header `e_flags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfFlags {
+    /// EF_RISCV_RVC: the file contains compressed instructions.
+    pub rvc: bool,
+    pub float_abi: FloatAbi,
+    /// EF_RISCV_RVE: the file targets the 16 register embedded base.
+    pub rve: bool,
+    /// EF_RISCV_TSO: the file requires the RVTSO memory model.
+    pub tso: bool,
+}
+
+impl ElfFlags {
+    const RVC: u32 = 0x0001;
+    const FLOAT_ABI: u32 = 0x0006;
+    const RVE: u32 = 0x0008;
+    const TSO: u32 = 0x0010;
+
+    pub fn from_bits(e_flags: u32) -> Self {
+        ElfFlags {
+            rvc: e_flags & Self::RVC != 0,
+            float_abi: match e_flags & Self::FLOAT_ABI {
+                0x0 => FloatAbi::Soft,
+                0x2 => FloatAbi::Single,
+                0x4 => FloatAbi::Double,
+                _ => FloatAbi::Quad,
+            },
+            rve: e_flags & Self::RVE != 0,
+            tso: e_flags & Self::TSO != 0,
+        }
+    }
+}
+
+/// Contents of the `.riscv.attributes` section that the machine cares about.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RiscvAttributes {
+    /// Tag_RISCV_stack_align, the required stack alignment in bytes.
+    pub stack_align: Option<u64>,
+    /// Tag_RISCV_arch, the ISA string the file was built for.
+    pub arch: Option<String>,
+    /// Tag_RISCV_unaligned_access, whether the code performs misaligned accesses.
+    pub unaligned_access: Option<bool>,
+}
+
+/// What a file asks of the machine that runs it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirements {
+    pub xlen: Xlen,
+    pub flags: ElfFlags,
+    /// The extensions of Tag_RISCV_arch, if the file has one, without C.
+    pub arch: Option<Isa>,
+    /// Tag_RISCV_arch includes C, which arch leaves out.
+    pub compressed: bool,
+    /// Tag_RISCV_unaligned_access: the code performs misaligned accesses.
+    pub unaligned_access: bool,
+}
+
+impl Requirements {
+    /* Checks that a machine decoding `isa` can run the file. */
+    pub fn check(&self, isa: &Isa) -> Result<(), Error> {
+        if self.xlen != isa.xlen() {
+            return Err(Error::XlenMismatch(self.xlen, isa.xlen()));
+        }
+
+        let flags = self.flags;
+        let missing = |feature: &str| Err(Error::MissingFeature(feature.to_string()));
+
+        if flags.rvc {
+            return missing("compressed instructions (EF_RISCV_RVC)");
+        }
+        match flags.float_abi {
+            FloatAbi::Soft => {}
+            FloatAbi::Single if isa.supports(Extension::F) => {}
+            FloatAbi::Double if isa.supports(Extension::D) => {}
+            FloatAbi::Single => return missing("the single precision float ABI (F extension)"),
+            FloatAbi::Double => return missing("the double precision float ABI (D extension)"),
+            FloatAbi::Quad => return missing("the quad precision float ABI (Q extension)"),
+        }
+        // a single in-order hart already provides TSO ordering.
+
+        let mut arch = self.arch.iter().flat_map(|arch| arch.extensions());
+        if let Some(extension) = arch.find(|&extension| !isa.supports(extension)) {
+            return missing(&format!("the {extension} extension (Tag_RISCV_arch)"));
+        }
+
+        Ok(())
+    }
+}
+
+mod tag {
+    pub const FILE: u8 = 1;
+    pub const RISCV_STACK_ALIGN: u64 = 4;
+    pub const RISCV_ARCH: u64 = 5;
+    pub const RISCV_UNALIGNED_ACCESS: u64 = 6;
+}
+
+/* Reads an unsigned LEB128 value, advancing data past it. */
+fn read_uleb128(data: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = data
+            .split_first()
+            .ok_or_else(|| Error::MalformedAttributes("truncated ULEB128".to_string()))?;
+        *data = rest;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(Error::MalformedAttributes("ULEB128 too long".to_string()))
+}
+
+fn write_uleb128(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/* Reads a NUL terminated string, advancing data past it. */
+fn read_ntbs(data: &mut &[u8]) -> Result<String, Error> {
+    let end = data
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or_else(|| Error::MalformedAttributes("unterminated string".to_string()))?;
+    let value = String::from_utf8_lossy(&data[..end]).into_owned();
+    *data = &data[end + 1..];
+
+    Ok(value)
+}
+
+fn read_u32(data: &mut &[u8]) -> Result<u32, Error> {
+    if data.len() < 4 {
+        return Err(Error::MalformedAttributes("truncated length".to_string()));
+    }
+    let (bytes, rest) = data.split_at(4);
+    *data = rest;
+
+    Ok(u32::from_le_bytes(
+        bytes.try_into().expect("split at 4 bytes"),
+    ))
+}
+
+impl RiscvAttributes {
+    /* Parses the build attributes format: a version byte followed by vendor subsections. */
+    pub fn parse(section: &[u8]) -> Result<Self, Error> {
+        let mut attributes = RiscvAttributes::default();
+        let Some((&b'A', mut data)) = section.split_first() else {
+            return Err(Error::MalformedAttributes(
+                "unknown format version".to_string(),
+            ));
+        };
+
+        while !data.is_empty() {
+            let length = read_u32(&mut data)? as usize;
+            if length < 4 || length - 4 > data.len() {
+                return Err(Error::MalformedAttributes(
+                    "bad subsection length".to_string(),
+                ));
+            }
+            let (mut subsection, rest) = data.split_at(length - 4);
+            data = rest;
+
+            if read_ntbs(&mut subsection)? != "riscv" {
+                continue;
+            }
+
+            while let Some((&tag, mut rest)) = subsection.split_first() {
+                let size = read_u32(&mut rest)? as usize;
+                if size < 5 || size - 5 > rest.len() {
+                    return Err(Error::MalformedAttributes(
+                        "bad attribute block size".to_string(),
+                    ));
+                }
+                let (mut block, next) = rest.split_at(size - 5);
+                subsection = next;
+
+                if tag == tag::FILE {
+                    attributes.parse_file_attributes(&mut block)?;
+                }
+            }
+        }
+
+        Ok(attributes)
+    }
+
+    /* Odd tags hold strings and even tags hold ULEB128 integers. */
+    fn parse_file_attributes(&mut self, data: &mut &[u8]) -> Result<(), Error> {
+        while !data.is_empty() {
+            let tag = read_uleb128(data)?;
+            if tag % 2 == 1 {
+                let value = read_ntbs(data)?;
+                if tag == tag::RISCV_ARCH {
+                    self.arch = Some(value);
+                }
+            } else {
+                let value = read_uleb128(data)?;
+                match tag {
+                    // sp is aligned by masking, which needs a power of two.
+                    tag::RISCV_STACK_ALIGN if !value.is_power_of_two() => {
+                        return Err(Error::MalformedAttributes(format!(
+                            "stack alignment {value} is not a power of two"
+                        )));
+                    }
+                    tag::RISCV_STACK_ALIGN => self.stack_align = Some(value),
+                    tag::RISCV_UNALIGNED_ACCESS => self.unaligned_access = Some(value != 0),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /* The section as parse reads it: one "riscv" subsection holding the attributes of
+     * the whole file. */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut attributes = Vec::new();
+        if let Some(align) = self.stack_align {
+            write_uleb128(&mut attributes, tag::RISCV_STACK_ALIGN);
+            write_uleb128(&mut attributes, align);
+        }
+        if let Some(arch) = &self.arch {
+            write_uleb128(&mut attributes, tag::RISCV_ARCH);
+            attributes.extend_from_slice(arch.as_bytes());
+            attributes.push(0);
+        }
+        if let Some(unaligned) = self.unaligned_access {
+            write_uleb128(&mut attributes, tag::RISCV_UNALIGNED_ACCESS);
+            write_uleb128(&mut attributes, unaligned as u64);
+        }
+
+        let vendor = b"riscv\0";
+        let block_size = 5 + attributes.len();
+        let subsection_size = 4 + vendor.len() + block_size;
+        let mut section = vec![b'A'];
+        section.extend_from_slice(&(subsection_size as u32).to_le_bytes());
+        section.extend_from_slice(vendor);
+        section.push(tag::FILE);
+        section.extend_from_slice(&(block_size as u32).to_le_bytes());
+        section.extend_from_slice(&attributes);
+
+        section
+    }
+}
+
 pub struct ElfFile<'a> {
     parser: ElfBytes<'a, LittleEndian>,
     sections: elf::section::SectionHeaderTable<'a, LittleEndian>,
@@ -41,7 +328,7 @@ pub struct ElfFile<'a> {
 
 #[allow(dead_code)] // TODO: remove
 impl<'a> ElfFile<'a> {
-    /* Creates a new ElfFile from a ELF32 RISCV file buffer */
+    /* Creates a new ElfFile from a ELF32 or ELF64 RISCV file buffer */
     pub fn from_buffer(buffer: &'a [u8]) -> Result<Self, Error> {
         let parser = ElfBytes::<LittleEndian>::minimal_parse(buffer)?;
         let sections = parser.section_headers().ok_or(Error::NoSectionHeader())?;
@@ -53,10 +340,6 @@ impl<'a> ElfFile<'a> {
             segments,
         };
 
-        if !file.check_elf32() {
-            return Err(Error::NotELF32());
-        }
-
         if !file.check_riscv() {
             return Err(Error::NotRISCV());
         }
@@ -64,52 +347,177 @@ impl<'a> ElfFile<'a> {
         Ok(file)
     }
 
-    pub fn entry_point(&self) -> u32 {
-        self.parser.ehdr.e_entry as u32
+    pub fn flags(&self) -> ElfFlags {
+        ElfFlags::from_bits(self.parser.ehdr.e_flags)
+    }
+
+    /* The base width the file is built for, given by its ELF class. */
+    pub fn xlen(&self) -> Xlen {
+        match self.parser.ehdr.class {
+            elf::file::Class::ELF32 => Xlen::Rv32,
+            elf::file::Class::ELF64 => Xlen::Rv64,
+        }
+    }
+
+    /* What the file asks of the machine, from its header and its attributes. */
+    pub fn requirements(&self) -> Result<Requirements, Error> {
+        let attributes = self.riscv_attributes()?;
+        // an arch with C is not supported, but a file that contains no compressed
+        // instructions does not set EF_RISCV_RVC: the check of the flags decides.
+        let (arch, compressed) = match attributes.arch.as_deref() {
+            Some(arch) => {
+                let (isa, compressed) = Isa::parse_without_compressed(arch)?;
+                (Some(isa), compressed)
+            }
+            None => (None, false),
+        };
+
+        Ok(Requirements {
+            xlen: self.xlen(),
+            flags: self.flags(),
+            arch,
+            compressed,
+            unaligned_access: attributes.unaligned_access.unwrap_or(false),
+        })
+    }
+
+    /* Checks that a machine decoding `isa` can run this file. */
+    pub fn check_requirements(&self, isa: &Isa) -> Result<(), Error> {
+        self.requirements()?.check(isa)
+    }
+
+    pub fn entry_point(&self) -> u64 {
+        self.parser.ehdr.e_entry
     }
 
-    pub fn load_memory(&self, size: usize) -> Vec<u8> {
+    /* A memory of size bytes holding every loadable segment. */
+    pub fn load_memory(&self, size: usize) -> Result<Vec<u8>, Error> {
         let mut memory = vec![0u8; size];
+        self.load_into(&mut memory)?;
+
+        Ok(memory)
+    }
+
+    /* Copies every loadable segment into memory, which may still hold an earlier program:
+     * the p_memsz - p_filesz bytes after the contents of a segment, e.g. .bss, are zeroed. */
+    pub fn load_into(&self, memory: &mut [u8]) -> Result<(), Error> {
+        for phdr in self
+            .segments
+            .iter()
+            .filter(|phdr| phdr.p_type == abi::PT_LOAD)
+        {
+            let data = self.segment_data(&phdr)?;
+            let end = Self::segment_end(&phdr)?;
+            if end > memory.len() as u64 {
+                return Err(Error::SegmentOutOfRange(
+                    phdr.p_vaddr,
+                    end,
+                    memory.len() as u64,
+                ));
+            }
+
+            let (contents, bss) =
+                memory[phdr.p_vaddr as usize..end as usize].split_at_mut(data.len());
+            contents.copy_from_slice(data);
+            bss.fill(0);
+        }
+
+        Ok(())
+    }
+
+    /* The p_filesz bytes of a segment in the file, which must fit in its p_memsz. */
+    fn segment_data(&self, phdr: &ProgramHeader) -> Result<&[u8], Error> {
+        if phdr.p_filesz > phdr.p_memsz {
+            return Err(Error::SegmentSizeMismatch(
+                phdr.p_vaddr,
+                phdr.p_filesz,
+                phdr.p_memsz,
+            ));
+        }
 
+        Ok(self.parser.segment_data(phdr)?)
+    }
+
+    /* The address after the last byte of a segment in memory. */
+    fn segment_end(phdr: &ProgramHeader) -> Result<u64, Error> {
+        phdr.p_vaddr
+            .checked_add(phdr.p_memsz)
+            .ok_or(Error::SegmentOverflow(phdr.p_vaddr, phdr.p_memsz))
+    }
+
+    /* Returns the address range and R/W/X permissions of every loadable segment. */
+    pub fn memory_regions(&self) -> Result<Vec<MemoryRegion>, Error> {
         self.segments
             .iter()
             .filter(|phdr| phdr.p_type == abi::PT_LOAD)
             .map(|phdr| {
-                (
-                    phdr.p_vaddr,
-                    self.parser
-                        .segment_data(&phdr)
-                        .expect("IntegerOverflow error"),
-                )
+                Ok(MemoryRegion {
+                    start: phdr.p_vaddr,
+                    end: Self::segment_end(&phdr)?,
+                    permissions: Permissions::from_elf_flags(phdr.p_flags),
+                })
             })
-            .for_each(|(addr, data)| {
-                let start = addr as usize;
-                let end = start + data.len();
-                memory[start..end].copy_from_slice(data);
-            });
+            .collect()
+    }
 
-        memory
+    /* The contents, size and permissions of every loadable segment. */
+    pub fn load_segments(&self) -> Result<Vec<Segment>, Error> {
+        self.segments
+            .iter()
+            .filter(|phdr| phdr.p_type == abi::PT_LOAD)
+            .map(|phdr| {
+                Self::segment_end(&phdr)?;
+                Ok(Segment {
+                    addr: phdr.p_vaddr,
+                    data: self.segment_data(&phdr)?.to_vec(),
+                    size: phdr.p_memsz,
+                    permissions: Permissions::from_elf_flags(phdr.p_flags),
+                })
+            })
+            .collect()
     }
 
     pub fn load_section(
         section: SectionHeader,
         memory: &[u8],
-    ) -> Result<HashMap<u32, Instruction>, Error> {
+        xlen: Xlen,
+    ) -> Result<HashMap<u64, Instruction>, Error> {
         let start = section.sh_addr as usize;
-        let size = section.sh_size as usize;
-        let data = &memory[start..start + size];
+        let data = start
+            .checked_add(section.sh_size as usize)
+            .and_then(|end| memory.get(start..end))
+            .ok_or(Error::SectionOutOfRange(
+                section.sh_addr,
+                section.sh_size,
+                memory.len() as u64,
+            ))?;
 
         data.chunks_exact(4)
             .enumerate()
             .map(|(i, bytes)| {
                 let addr = start + (i * 4);
-                let instr = Instruction::from_bytes(bytes.try_into().expect("it should work"))?;
+                let bytes = bytes.try_into().expect("it should work");
+                let instr = Instruction::from_bytes(bytes, xlen)?;
 
-                Ok((addr as u32, instr))
+                Ok((addr as u64, instr))
             })
             .collect::<Result<_, _>>()
     }
 
+    /* Parses .riscv.attributes. Files without the section have no attributes. */
+    pub fn riscv_attributes(&self) -> Result<RiscvAttributes, Error> {
+        let Some(section) = self
+            .parser
+            .section_header_by_name(".riscv.attributes")
+            .expect("Already checked the existance for section headers.")
+        else {
+            return Ok(RiscvAttributes::default());
+        };
+
+        let (data, _) = self.parser.section_data(&section)?;
+        RiscvAttributes::parse(data)
+    }
+
     pub fn find_section_by_name(
         &self,
         segment_name: impl Into<String>,
@@ -124,16 +532,48 @@ impl<'a> ElfFile<'a> {
         Ok(section)
     }
 
+    /* The value of a symbol in .symtab, e.g. the address of `tohost`. Stripped files
+     * have no symbols. */
+    pub fn symbol(&self, name: &str) -> Result<Option<u64>, Error> {
+        let Some((symbols, strings)) = self.parser.symbol_table()? else {
+            return Ok(None);
+        };
+
+        for symbol in symbols.iter() {
+            if symbol.st_name != 0 && strings.get(symbol.st_name as usize)? == name {
+                return Ok(Some(symbol.st_value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /* The value of every named symbol in .symtab, other than section and file symbols.
+     * Where a local name is defined more than once, the first definition is kept. */
+    pub fn symbols(&self) -> Result<HashMap<String, u64>, Error> {
+        let mut named = HashMap::new();
+        let Some((symbols, strings)) = self.parser.symbol_table()? else {
+            return Ok(named);
+        };
+
+        for symbol in symbols.iter() {
+            if symbol.st_name == 0
+                || matches!(symbol.st_symtype(), abi::STT_SECTION | abi::STT_FILE)
+            {
+                continue;
+            }
+            let name = strings.get(symbol.st_name as usize)?;
+            named.entry(name.to_string()).or_insert(symbol.st_value);
+        }
+
+        Ok(named)
+    }
+
     /* Checks if the ElfFile is built for RISCV. */
     fn check_riscv(&self) -> bool {
         self.parser.ehdr.e_machine == abi::EM_RISCV
     }
 
-    /* Checks if the ElfFile is an ELF32 program. */
-    fn check_elf32(&self) -> bool {
-        self.parser.ehdr.class == elf::file::Class::ELF32
-    }
-
     pub fn sections(&self) -> elf::parse::ParsingTable<'a, LittleEndian, SectionHeader> {
         self.sections
     }
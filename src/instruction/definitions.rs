@@ -1,41 +1,105 @@
 #![allow(dead_code)]
-use crate::instruction::formats::*;
+use crate::{instruction::formats::*, isa::Xlen};
 
 macro_rules! define_instructions {
     (
         $(
-            $variant:ident : $format:ident {
-                opcode: $opcode:expr
-                $(, funct3: $funct3:expr)?
-                $(, funct7: $funct7:expr)?
+            $extension:ident {
+                $(
+                    $variant:ident : $format:ident $({
+                        opcode: $opcode:expr
+                        $(, funct3: $funct3:expr)?
+                        $(, funct7: $funct7:expr)?
+                        $(, funct6: $funct6:expr)?
+                        $(, funct5: $funct5:expr)?
+                        $(, funct2: $funct2:expr)?
+                        $(, rs2: $rs2:expr)?
+                        $(, imm: $imm:expr)?
+                        $(, xlen: $xlen:expr)?
+                    })|+
+                ),*
+                $(,)?
             }
-        ),*
-        $(,)?
+        )*
     ) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum Extension {
+            $($extension),*
+        }
+
+        impl Extension {
+            pub const ALL: &[Extension] = &[$(Extension::$extension),*];
+        }
+
         #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         #[allow(non_camel_case_types)]
         pub enum Op {
-            $($variant),*
+            $($($variant,)*)*
         }
 
         impl Op {
+            pub const ALL: &[Op] = &[$($(Op::$variant,)*)*];
+
+            /* Every encoding of the op, one per base width if they differ. */
+            pub fn encodings(self) -> &'static [Encoding] {
+                match self {
+                    $($(Op::$variant => &[$(
+                        Encoding {
+                            opcode: $opcode,
+                            funct3: define_instructions!(@option $($funct3)?),
+                            funct7: define_instructions!(@option $($funct7)?),
+                            funct6: define_instructions!(@option $($funct6)?),
+                            funct5: define_instructions!(@option $($funct5)?),
+                            funct2: define_instructions!(@option $($funct2)?),
+                            rs2: define_instructions!(@option $($rs2)?),
+                            imm: define_instructions!(@option $($imm)?),
+                            xlen: define_instructions!(@option $($xlen)?),
+                        }
+                    ),+],)*)*
+                }
+            }
+
             pub fn format(self) -> Format {
                 match self {
-                    $(Op::$variant => Format::$format),*
+                    $($(Op::$variant => Format::$format,)*)*
+                }
+            }
+
+            pub fn extension(self) -> Extension {
+                match self {
+                    $($(Op::$variant => Extension::$extension,)*)*
                 }
             }
         }
 
+        /* Arms are tried in table order, so more specific encodings must come first.
+         * An entry restricted with `xlen` only decodes on that base width, and an entry
+         * may list one encoding per width separated by `|`. */
+        pub fn decode_op(raw: u32, xlen: Xlen) -> Option<Op> {
+            let opcode = (raw & 0x7F) as u8;
+            let funct3 = ((raw >> 12) & 0x7) as u8;
+            let funct7 = (raw >> 25) as u8;
+            let funct6 = (raw >> 26) as u8;
+            let funct5 = (raw >> 27) as u8;
+            let funct2 = ((raw >> 25) & 0x3) as u8;
+            let rs2 = ((raw >> 20) & 0x1F) as u8;
+            let imm = (raw >> 20) as u16;
+            let xlen = xlen.bits();
 
-        pub fn decode_op(opcode: u8, funct3: u8, funct7: u8) -> Option<Op> {
-            match (opcode, funct3, funct7) {
-                $(
+            match (opcode, funct3, funct7, funct6, funct5, funct2, rs2, imm, xlen) {
+                $($($(
                     (
                         $opcode,
                         define_instructions!(@field $($funct3)?),
-                        define_instructions!(@field $($funct7)?)
+                        define_instructions!(@field $($funct7)?),
+                        define_instructions!(@field $($funct6)?),
+                        define_instructions!(@field $($funct5)?),
+                        define_instructions!(@field $($funct2)?),
+                        define_instructions!(@field $($rs2)?),
+                        define_instructions!(@field $($imm)?),
+                        define_instructions!(@field $($xlen)?)
                     ) => Some(Op::$variant),
-                )*
+                )+)*)*
                 _ => None,
             }
         }
@@ -43,61 +107,330 @@ macro_rules! define_instructions {
 
     (@field $val:expr) => { $val };
     (@field) => { _ };
+    (@option $val:expr) => { Some($val) };
+    (@option) => { None };
+}
+
+/// The fields an op fixes in its encoding. The others hold its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoding {
+    pub opcode: u8,
+    pub funct3: Option<u8>,
+    pub funct7: Option<u8>,
+    pub funct6: Option<u8>,
+    pub funct5: Option<u8>,
+    pub funct2: Option<u8>,
+    pub rs2: Option<u8>,
+    pub imm: Option<u16>,
+    /// The only base width the encoding exists on, if any.
+    pub xlen: Option<u32>,
+}
+
+impl Op {
+    /* The encoding of the op on a base width, if the op exists there. */
+    pub fn encoding(self, xlen: Xlen) -> Option<Encoding> {
+        self.encodings()
+            .iter()
+            .copied()
+            .find(|encoding| encoding.xlen.is_none_or(|bits| bits == xlen.bits()))
+    }
 }
 
 impl std::fmt::Display for Op {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        // `fence_i` is spelled `fence.i` in assembly.
+        f.write_str(&format!("{:?}", self).replace('_', "."))
+    }
+}
+
+impl std::fmt::Display for Extension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("{:?}", self).to_lowercase())
     }
 }
 
 /* From RISCV_CARD.pdf */
 define_instructions! {
-    add  : R { opcode: 0b0110011, funct3: 0x0, funct7: 0x00 },
-    sub  : R { opcode: 0b0110011, funct3: 0x0, funct7: 0x20 },
-    xor  : R { opcode: 0b0110011, funct3: 0x4, funct7: 0x00 },
-    or   : R { opcode: 0b0110011, funct3: 0x6, funct7: 0x00 },
-    and  : R { opcode: 0b0110011, funct3: 0x7, funct7: 0x00 },
-    sll  : R { opcode: 0b0110011, funct3: 0x1, funct7: 0x00 },
-    srl  : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x00 },
-    sra  : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x20 },
-    slt  : R { opcode: 0b0110011, funct3: 0x2, funct7: 0x00 },
-    sltu : R { opcode: 0b0110011, funct3: 0x3, funct7: 0x00 },
-
-    addi  : I { opcode: 0b0010011, funct3: 0x0 },
-    xori  : I { opcode: 0b0010011, funct3: 0x4 },
-    ori   : I { opcode: 0b0010011, funct3: 0x6 },
-    andi  : I { opcode: 0b0010011, funct3: 0x7 },
-    slli  : I { opcode: 0b0010011, funct3: 0x1, funct7: 0x00 },
-    srli  : I { opcode: 0b0010011, funct3: 0x5, funct7: 0x00 },
-    srai  : I { opcode: 0b0010011, funct3: 0x5, funct7: 0x20 },
-    slti  : I { opcode: 0b0010011, funct3: 0x2 },
-    sltiu : I { opcode: 0b0010011, funct3: 0x3 },
-
-    lb  : I { opcode: 0b0000011, funct3: 0x0 },
-    lh  : I { opcode: 0b0000011, funct3: 0x1 },
-    lw  : I { opcode: 0b0000011, funct3: 0x2 },
-    lbu : I { opcode: 0b0000011, funct3: 0x4 },
-    lhu : I { opcode: 0b0000011, funct3: 0x5 },
-
-    sb : S { opcode: 0b0100011, funct3: 0x0 },
-    sh : S { opcode: 0b0100011, funct3: 0x1 },
-    sw : S { opcode: 0b0100011, funct3: 0x2 },
-
-    beq  : B { opcode: 0b1100011, funct3: 0x0 },
-    bne  : B { opcode: 0b1100011, funct3: 0x1 },
-    blt  : B { opcode: 0b1100011, funct3: 0x4 },
-    bge  : B { opcode: 0b1100011, funct3: 0x5 },
-    bltu : B { opcode: 0b1100011, funct3: 0x6 },
-    bgeu : B { opcode: 0b1100011, funct3: 0x7 },
-
-    lui   : U { opcode: 0b0110111 },
-    auipc : U { opcode: 0b0010111 },
-
-    jal  : J { opcode: 0b1101111 },
-
-    jalr : I { opcode: 0b1100111, funct3: 0x0 },
-
-    ecall : I { opcode: 0b1110011, funct3: 0x0, funct7: 0x0 },
-    ebreak : I { opcode: 0b1110011, funct3: 0x0, funct7: 0x1 },
+    I {
+        add  : R { opcode: 0b0110011, funct3: 0x0, funct7: 0x00 },
+        sub  : R { opcode: 0b0110011, funct3: 0x0, funct7: 0x20 },
+        xor  : R { opcode: 0b0110011, funct3: 0x4, funct7: 0x00 },
+        or   : R { opcode: 0b0110011, funct3: 0x6, funct7: 0x00 },
+        and  : R { opcode: 0b0110011, funct3: 0x7, funct7: 0x00 },
+        sll  : R { opcode: 0b0110011, funct3: 0x1, funct7: 0x00 },
+        srl  : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x00 },
+        sra  : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x20 },
+        slt  : R { opcode: 0b0110011, funct3: 0x2, funct7: 0x00 },
+        sltu : R { opcode: 0b0110011, funct3: 0x3, funct7: 0x00 },
+
+        addi  : I { opcode: 0b0010011, funct3: 0x0 },
+        xori  : I { opcode: 0b0010011, funct3: 0x4 },
+        ori   : I { opcode: 0b0010011, funct3: 0x6 },
+        andi  : I { opcode: 0b0010011, funct3: 0x7 },
+        slli  : I { opcode: 0b0010011, funct3: 0x1, funct7: 0x00, xlen: 32 }
+              | { opcode: 0b0010011, funct3: 0x1, funct6: 0x00, xlen: 64 },
+        srli  : I { opcode: 0b0010011, funct3: 0x5, funct7: 0x00, xlen: 32 }
+              | { opcode: 0b0010011, funct3: 0x5, funct6: 0x00, xlen: 64 },
+        srai  : I { opcode: 0b0010011, funct3: 0x5, funct7: 0x20, xlen: 32 }
+              | { opcode: 0b0010011, funct3: 0x5, funct6: 0x10, xlen: 64 },
+        slti  : I { opcode: 0b0010011, funct3: 0x2 },
+        sltiu : I { opcode: 0b0010011, funct3: 0x3 },
+
+        lb  : I { opcode: 0b0000011, funct3: 0x0 },
+        lh  : I { opcode: 0b0000011, funct3: 0x1 },
+        lw  : I { opcode: 0b0000011, funct3: 0x2 },
+        lbu : I { opcode: 0b0000011, funct3: 0x4 },
+        lhu : I { opcode: 0b0000011, funct3: 0x5 },
+        lwu : I { opcode: 0b0000011, funct3: 0x6, xlen: 64 },
+        ld  : I { opcode: 0b0000011, funct3: 0x3, xlen: 64 },
+
+        sb : S { opcode: 0b0100011, funct3: 0x0 },
+        sh : S { opcode: 0b0100011, funct3: 0x1 },
+        sw : S { opcode: 0b0100011, funct3: 0x2 },
+        sd : S { opcode: 0b0100011, funct3: 0x3, xlen: 64 },
+
+        beq  : B { opcode: 0b1100011, funct3: 0x0 },
+        bne  : B { opcode: 0b1100011, funct3: 0x1 },
+        blt  : B { opcode: 0b1100011, funct3: 0x4 },
+        bge  : B { opcode: 0b1100011, funct3: 0x5 },
+        bltu : B { opcode: 0b1100011, funct3: 0x6 },
+        bgeu : B { opcode: 0b1100011, funct3: 0x7 },
+
+        lui   : U { opcode: 0b0110111 },
+        auipc : U { opcode: 0b0010111 },
+
+        jal  : J { opcode: 0b1101111 },
+
+        jalr : I { opcode: 0b1100111, funct3: 0x0 },
+
+        fence_tso : I { opcode: 0b0001111, funct3: 0x0, imm: 0x833 },
+        pause     : I { opcode: 0b0001111, funct3: 0x0, imm: 0x010 },
+        fence     : I { opcode: 0b0001111, funct3: 0x0 },
+
+        ecall  : I { opcode: 0b1110011, funct3: 0x0, imm: 0x000 },
+        ebreak : I { opcode: 0b1110011, funct3: 0x0, imm: 0x001 },
+
+        sret       : I { opcode: 0b1110011, funct3: 0x0, imm: 0x102 },
+        wfi        : I { opcode: 0b1110011, funct3: 0x0, imm: 0x105 },
+        mret       : I { opcode: 0b1110011, funct3: 0x0, imm: 0x302 },
+        sfence_vma : R { opcode: 0b1110011, funct3: 0x0, funct7: 0x09 },
+
+        addiw : I { opcode: 0b0011011, funct3: 0x0, xlen: 64 },
+        slliw : I { opcode: 0b0011011, funct3: 0x1, funct7: 0x00, xlen: 64 },
+        srliw : I { opcode: 0b0011011, funct3: 0x5, funct7: 0x00, xlen: 64 },
+        sraiw : I { opcode: 0b0011011, funct3: 0x5, funct7: 0x20, xlen: 64 },
+
+        addw : R { opcode: 0b0111011, funct3: 0x0, funct7: 0x00, xlen: 64 },
+        subw : R { opcode: 0b0111011, funct3: 0x0, funct7: 0x20, xlen: 64 },
+        sllw : R { opcode: 0b0111011, funct3: 0x1, funct7: 0x00, xlen: 64 },
+        srlw : R { opcode: 0b0111011, funct3: 0x5, funct7: 0x00, xlen: 64 },
+        sraw : R { opcode: 0b0111011, funct3: 0x5, funct7: 0x20, xlen: 64 },
+    }
+
+    // multiplication is in Zmmul, which M implies.
+    M {
+        div    : R { opcode: 0b0110011, funct3: 0x4, funct7: 0x01 },
+        divu   : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x01 },
+        rem    : R { opcode: 0b0110011, funct3: 0x6, funct7: 0x01 },
+        remu   : R { opcode: 0b0110011, funct3: 0x7, funct7: 0x01 },
+
+        divw  : R { opcode: 0b0111011, funct3: 0x4, funct7: 0x01, xlen: 64 },
+        divuw : R { opcode: 0b0111011, funct3: 0x5, funct7: 0x01, xlen: 64 },
+        remw  : R { opcode: 0b0111011, funct3: 0x6, funct7: 0x01, xlen: 64 },
+        remuw : R { opcode: 0b0111011, funct3: 0x7, funct7: 0x01, xlen: 64 },
+    }
+
+    // the atomics are all in Zaamo and Zalrsc, which A implies.
+    A {}
+
+    F {
+        flw : I { opcode: 0b0000111, funct3: 0x2 },
+        fsw : S { opcode: 0b0100111, funct3: 0x2 },
+
+        fmadd_s  : R4 { opcode: 0b1000011, funct2: 0x0 },
+        fmsub_s  : R4 { opcode: 0b1000111, funct2: 0x0 },
+        fnmsub_s : R4 { opcode: 0b1001011, funct2: 0x0 },
+        fnmadd_s : R4 { opcode: 0b1001111, funct2: 0x0 },
+
+        fadd_s   : R { opcode: 0b1010011, funct7: 0x00 },
+        fsub_s   : R { opcode: 0b1010011, funct7: 0x04 },
+        fmul_s   : R { opcode: 0b1010011, funct7: 0x08 },
+        fdiv_s   : R { opcode: 0b1010011, funct7: 0x0C },
+        fsqrt_s  : R { opcode: 0b1010011, funct7: 0x2C, rs2: 0x0 },
+        fsgnj_s  : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x10 },
+        fsgnjn_s : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x10 },
+        fsgnjx_s : R { opcode: 0b1010011, funct3: 0x2, funct7: 0x10 },
+        fmin_s   : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x14 },
+        fmax_s   : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x14 },
+
+        fcvt_w_s  : R { opcode: 0b1010011, funct7: 0x60, rs2: 0x0 },
+        fcvt_wu_s : R { opcode: 0b1010011, funct7: 0x60, rs2: 0x1 },
+        fcvt_s_w  : R { opcode: 0b1010011, funct7: 0x68, rs2: 0x0 },
+        fcvt_s_wu : R { opcode: 0b1010011, funct7: 0x68, rs2: 0x1 },
+        fcvt_l_s  : R { opcode: 0b1010011, funct7: 0x60, rs2: 0x2, xlen: 64 },
+        fcvt_lu_s : R { opcode: 0b1010011, funct7: 0x60, rs2: 0x3, xlen: 64 },
+        fcvt_s_l  : R { opcode: 0b1010011, funct7: 0x68, rs2: 0x2, xlen: 64 },
+        fcvt_s_lu : R { opcode: 0b1010011, funct7: 0x68, rs2: 0x3, xlen: 64 },
+
+        fmv_x_w  : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x70, rs2: 0x0 },
+        fclass_s : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x70, rs2: 0x0 },
+        fmv_w_x  : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x78, rs2: 0x0 },
+
+        feq_s : R { opcode: 0b1010011, funct3: 0x2, funct7: 0x50 },
+        flt_s : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x50 },
+        fle_s : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x50 },
+    }
+
+    D {
+        fld : I { opcode: 0b0000111, funct3: 0x3 },
+        fsd : S { opcode: 0b0100111, funct3: 0x3 },
+
+        fmadd_d  : R4 { opcode: 0b1000011, funct2: 0x1 },
+        fmsub_d  : R4 { opcode: 0b1000111, funct2: 0x1 },
+        fnmsub_d : R4 { opcode: 0b1001011, funct2: 0x1 },
+        fnmadd_d : R4 { opcode: 0b1001111, funct2: 0x1 },
+
+        fadd_d   : R { opcode: 0b1010011, funct7: 0x01 },
+        fsub_d   : R { opcode: 0b1010011, funct7: 0x05 },
+        fmul_d   : R { opcode: 0b1010011, funct7: 0x09 },
+        fdiv_d   : R { opcode: 0b1010011, funct7: 0x0D },
+        fsqrt_d  : R { opcode: 0b1010011, funct7: 0x2D, rs2: 0x0 },
+        fsgnj_d  : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x11 },
+        fsgnjn_d : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x11 },
+        fsgnjx_d : R { opcode: 0b1010011, funct3: 0x2, funct7: 0x11 },
+        fmin_d   : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x15 },
+        fmax_d   : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x15 },
+
+        fcvt_s_d  : R { opcode: 0b1010011, funct7: 0x20, rs2: 0x1 },
+        fcvt_d_s  : R { opcode: 0b1010011, funct7: 0x21, rs2: 0x0 },
+        fcvt_w_d  : R { opcode: 0b1010011, funct7: 0x61, rs2: 0x0 },
+        fcvt_wu_d : R { opcode: 0b1010011, funct7: 0x61, rs2: 0x1 },
+        fcvt_d_w  : R { opcode: 0b1010011, funct7: 0x69, rs2: 0x0 },
+        fcvt_d_wu : R { opcode: 0b1010011, funct7: 0x69, rs2: 0x1 },
+        fcvt_l_d  : R { opcode: 0b1010011, funct7: 0x61, rs2: 0x2, xlen: 64 },
+        fcvt_lu_d : R { opcode: 0b1010011, funct7: 0x61, rs2: 0x3, xlen: 64 },
+        fcvt_d_l  : R { opcode: 0b1010011, funct7: 0x69, rs2: 0x2, xlen: 64 },
+        fcvt_d_lu : R { opcode: 0b1010011, funct7: 0x69, rs2: 0x3, xlen: 64 },
+
+        fmv_x_d  : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x71, rs2: 0x0, xlen: 64 },
+        fclass_d : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x71, rs2: 0x0 },
+        fmv_d_x  : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x79, rs2: 0x0, xlen: 64 },
+
+        feq_d : R { opcode: 0b1010011, funct3: 0x2, funct7: 0x51 },
+        flt_d : R { opcode: 0b1010011, funct3: 0x1, funct7: 0x51 },
+        fle_d : R { opcode: 0b1010011, funct3: 0x0, funct7: 0x51 },
+    }
+
+    Zicsr {
+        csrrw  : I { opcode: 0b1110011, funct3: 0x1 },
+        csrrs  : I { opcode: 0b1110011, funct3: 0x2 },
+        csrrc  : I { opcode: 0b1110011, funct3: 0x3 },
+        csrrwi : I { opcode: 0b1110011, funct3: 0x5 },
+        csrrsi : I { opcode: 0b1110011, funct3: 0x6 },
+        csrrci : I { opcode: 0b1110011, funct3: 0x7 },
+    }
+
+    Zifencei {
+        fence_i : I { opcode: 0b0001111, funct3: 0x1 },
+    }
+
+    Zmmul {
+        mul    : R { opcode: 0b0110011, funct3: 0x0, funct7: 0x01 },
+        mulh   : R { opcode: 0b0110011, funct3: 0x1, funct7: 0x01 },
+        mulhsu : R { opcode: 0b0110011, funct3: 0x2, funct7: 0x01 },
+        mulhu  : R { opcode: 0b0110011, funct3: 0x3, funct7: 0x01 },
+
+        mulw   : R { opcode: 0b0111011, funct3: 0x0, funct7: 0x01, xlen: 64 },
+    }
+
+    Zaamo {
+        amoswap_w : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x01 },
+        amoadd_w  : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x00 },
+        amoxor_w  : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x04 },
+        amoand_w  : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x0C },
+        amoor_w   : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x08 },
+        amomin_w  : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x10 },
+        amomax_w  : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x14 },
+        amominu_w : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x18 },
+        amomaxu_w : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x1C },
+
+        amoswap_d : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x01, xlen: 64 },
+        amoadd_d  : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x00, xlen: 64 },
+        amoxor_d  : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x04, xlen: 64 },
+        amoand_d  : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x0C, xlen: 64 },
+        amoor_d   : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x08, xlen: 64 },
+        amomin_d  : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x10, xlen: 64 },
+        amomax_d  : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x14, xlen: 64 },
+        amominu_d : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x18, xlen: 64 },
+        amomaxu_d : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x1C, xlen: 64 },
+    }
+
+    Zalrsc {
+        lr_w : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x02, rs2: 0x0 },
+        sc_w : A { opcode: 0b0101111, funct3: 0x2, funct5: 0x03 },
+
+        lr_d : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x02, rs2: 0x0, xlen: 64 },
+        sc_d : A { opcode: 0b0101111, funct3: 0x3, funct5: 0x03, xlen: 64 },
+    }
+
+    Zba {
+        sh1add : R { opcode: 0b0110011, funct3: 0x2, funct7: 0x10 },
+        sh2add : R { opcode: 0b0110011, funct3: 0x4, funct7: 0x10 },
+        sh3add : R { opcode: 0b0110011, funct3: 0x6, funct7: 0x10 },
+
+        add_uw    : R { opcode: 0b0111011, funct3: 0x0, funct7: 0x04, xlen: 64 },
+        sh1add_uw : R { opcode: 0b0111011, funct3: 0x2, funct7: 0x10, xlen: 64 },
+        sh2add_uw : R { opcode: 0b0111011, funct3: 0x4, funct7: 0x10, xlen: 64 },
+        sh3add_uw : R { opcode: 0b0111011, funct3: 0x6, funct7: 0x10, xlen: 64 },
+        slli_uw   : I { opcode: 0b0011011, funct3: 0x1, funct6: 0x02, xlen: 64 },
+    }
+
+    Zbb {
+        andn : R { opcode: 0b0110011, funct3: 0x7, funct7: 0x20 },
+        orn  : R { opcode: 0b0110011, funct3: 0x6, funct7: 0x20 },
+        xnor : R { opcode: 0b0110011, funct3: 0x4, funct7: 0x20 },
+        min  : R { opcode: 0b0110011, funct3: 0x4, funct7: 0x05 },
+        minu : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x05 },
+        max  : R { opcode: 0b0110011, funct3: 0x6, funct7: 0x05 },
+        maxu : R { opcode: 0b0110011, funct3: 0x7, funct7: 0x05 },
+        rol  : R { opcode: 0b0110011, funct3: 0x1, funct7: 0x30 },
+        ror  : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x30 },
+
+        zext_h : R { opcode: 0b0110011, funct3: 0x4, funct7: 0x04, rs2: 0x0, xlen: 32 }
+               | { opcode: 0b0111011, funct3: 0x4, funct7: 0x04, rs2: 0x0, xlen: 64 },
+
+        clz    : I { opcode: 0b0010011, funct3: 0x1, imm: 0x600 },
+        ctz    : I { opcode: 0b0010011, funct3: 0x1, imm: 0x601 },
+        cpop   : I { opcode: 0b0010011, funct3: 0x1, imm: 0x602 },
+        sext_b : I { opcode: 0b0010011, funct3: 0x1, imm: 0x604 },
+        sext_h : I { opcode: 0b0010011, funct3: 0x1, imm: 0x605 },
+        orc_b  : I { opcode: 0b0010011, funct3: 0x5, imm: 0x287 },
+        rev8   : I { opcode: 0b0010011, funct3: 0x5, imm: 0x698, xlen: 32 }
+               | { opcode: 0b0010011, funct3: 0x5, imm: 0x6B8, xlen: 64 },
+        rori   : I { opcode: 0b0010011, funct3: 0x5, funct7: 0x30, xlen: 32 }
+               | { opcode: 0b0010011, funct3: 0x5, funct6: 0x18, xlen: 64 },
+
+        rolw  : R { opcode: 0b0111011, funct3: 0x1, funct7: 0x30, xlen: 64 },
+        rorw  : R { opcode: 0b0111011, funct3: 0x5, funct7: 0x30, xlen: 64 },
+        clzw  : I { opcode: 0b0011011, funct3: 0x1, imm: 0x600, xlen: 64 },
+        ctzw  : I { opcode: 0b0011011, funct3: 0x1, imm: 0x601, xlen: 64 },
+        cpopw : I { opcode: 0b0011011, funct3: 0x1, imm: 0x602, xlen: 64 },
+        roriw : I { opcode: 0b0011011, funct3: 0x5, funct7: 0x30, xlen: 64 },
+    }
+
+    Zbs {
+        bclr : R { opcode: 0b0110011, funct3: 0x1, funct7: 0x24 },
+        bext : R { opcode: 0b0110011, funct3: 0x5, funct7: 0x24 },
+        binv : R { opcode: 0b0110011, funct3: 0x1, funct7: 0x34 },
+        bset : R { opcode: 0b0110011, funct3: 0x1, funct7: 0x14 },
+
+        bclri : I { opcode: 0b0010011, funct3: 0x1, funct7: 0x24, xlen: 32 }
+              | { opcode: 0b0010011, funct3: 0x1, funct6: 0x12, xlen: 64 },
+        bexti : I { opcode: 0b0010011, funct3: 0x5, funct7: 0x24, xlen: 32 }
+              | { opcode: 0b0010011, funct3: 0x5, funct6: 0x12, xlen: 64 },
+        binvi : I { opcode: 0b0010011, funct3: 0x1, funct7: 0x34, xlen: 32 }
+              | { opcode: 0b0010011, funct3: 0x1, funct6: 0x1A, xlen: 64 },
+        bseti : I { opcode: 0b0010011, funct3: 0x1, funct7: 0x14, xlen: 32 }
+              | { opcode: 0b0010011, funct3: 0x1, funct6: 0x0A, xlen: 64 },
+    }
 }
@@ -8,6 +8,8 @@ pub enum Format {
     B,
     U,
     J,
+    A,
+    R4,
 }
 
 impl Format {
@@ -19,6 +21,28 @@ impl Format {
             Format::B => InstructionFormat::B(BType::from(raw)),
             Format::U => InstructionFormat::U(UType::from(raw)),
             Format::J => InstructionFormat::J(JType::from(raw)),
+            Format::A => InstructionFormat::A(AType::from(raw)),
+            Format::R4 => InstructionFormat::R4(R4Type::from(raw)),
+        }
+    }
+
+    /* Places an immediate in the bits the format keeps it in, the inverse of
+     * InstructionFormat::immediate_value. Bits that do not fit are dropped. */
+    pub fn immediate_bits(self, imm: i32) -> u32 {
+        let imm = imm as u32;
+        let bits = |high: u32, low: u32| (imm >> low) & ((1 << (high - low + 1)) - 1);
+
+        match self {
+            Format::I => bits(11, 0) << 20,
+            Format::S => bits(4, 0) << 7 | bits(11, 5) << 25,
+            Format::B => {
+                bits(11, 11) << 7 | bits(4, 1) << 8 | bits(10, 5) << 25 | bits(12, 12) << 31
+            }
+            Format::U => bits(19, 0) << 12,
+            Format::J => {
+                bits(19, 12) << 12 | bits(11, 11) << 20 | bits(10, 1) << 21 | bits(20, 20) << 31
+            }
+            Format::R | Format::A | Format::R4 => 0,
         }
     }
 }
@@ -31,6 +55,8 @@ pub enum InstructionFormat {
     U(UType),
     B(BType),
     J(JType),
+    A(AType),
+    R4(R4Type),
 }
 
 fn sign_extend(value: u32, bits: u32) -> i32 {
@@ -47,6 +73,8 @@ impl InstructionFormat {
             InstructionFormat::U(utype) => utype.opcode(),
             InstructionFormat::B(btype) => btype.opcode(),
             InstructionFormat::J(jtype) => jtype.opcode(),
+            InstructionFormat::A(atype) => atype.opcode(),
+            InstructionFormat::R4(r4type) => r4type.opcode(),
         }
     }
     pub fn rd(&self) -> Option<u5> {
@@ -57,6 +85,22 @@ impl InstructionFormat {
             InstructionFormat::U(utype) => Some(utype.rd()),
             InstructionFormat::B(..) => None,
             InstructionFormat::J(jtype) => Some(jtype.rd()),
+            InstructionFormat::A(atype) => Some(atype.rd()),
+            InstructionFormat::R4(r4type) => Some(r4type.rd()),
+        }
+    }
+
+    /* funct3, which holds the rounding mode in floating point instructions. */
+    pub fn funct3(&self) -> Option<u3> {
+        match self {
+            InstructionFormat::R(rtype) => Some(rtype.funct3()),
+            InstructionFormat::I(itype) => Some(itype.funct3()),
+            InstructionFormat::S(stype) => Some(stype.funct3()),
+            InstructionFormat::U(..) => None,
+            InstructionFormat::B(btype) => Some(btype.funct3()),
+            InstructionFormat::J(..) => None,
+            InstructionFormat::A(atype) => Some(atype.funct3()),
+            InstructionFormat::R4(r4type) => Some(r4type.funct3()),
         }
     }
 
@@ -68,6 +112,8 @@ impl InstructionFormat {
             InstructionFormat::U(..) => None,
             InstructionFormat::B(btype) => Some(btype.rs1()),
             InstructionFormat::J(..) => None,
+            InstructionFormat::A(atype) => Some(atype.rs1()),
+            InstructionFormat::R4(r4type) => Some(r4type.rs1()),
         }
     }
 
@@ -79,10 +125,18 @@ impl InstructionFormat {
             InstructionFormat::U(..) => None,
             InstructionFormat::B(btype) => Some(btype.rs2()),
             InstructionFormat::J(..) => None,
+            InstructionFormat::A(atype) => Some(atype.rs2()),
+            InstructionFormat::R4(r4type) => Some(r4type.rs2()),
+        }
+    }
+
+    pub fn rs3(&self) -> Option<u5> {
+        match self {
+            InstructionFormat::R4(r4type) => Some(r4type.rs3()),
+            _ => None,
         }
     }
 
-    // TODO: unit testing
     pub fn immediate_value(&self) -> Option<i32> {
         match self {
             InstructionFormat::R(..) => None,
@@ -109,7 +163,33 @@ impl InstructionFormat {
 
                 sign_extend(value, 21)
             }),
+            InstructionFormat::A(..) => None,
+            InstructionFormat::R4(..) => None,
+        }
+    }
+}
+
+/* Lists the operand fields present in the format, e.g. "rd=1, rs1=2, imm=-4". */
+impl std::fmt::Display for InstructionFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut fields = Vec::new();
+        if let Some(rd) = self.rd() {
+            fields.push(format!("rd={rd}"));
+        }
+        if let Some(rs1) = self.rs1() {
+            fields.push(format!("rs1={rs1}"));
+        }
+        if let Some(rs2) = self.rs2() {
+            fields.push(format!("rs2={rs2}"));
         }
+        if let Some(rs3) = self.rs3() {
+            fields.push(format!("rs3={rs3}"));
+        }
+        if let Some(imm) = self.immediate_value() {
+            fields.push(format!("imm={imm}"));
+        }
+
+        write!(f, "{}", fields.join(", "))
     }
 }
 
@@ -176,3 +256,30 @@ pub struct JType {
     pub imm3: u10,
     pub imm4: u1,
 }
+
+/* R-type with funct7 split into funct5 and the aq/rl ordering bits. */
+#[bitsize(32)]
+#[derive(FromBits, DebugBits, Clone, Copy)]
+pub struct AType {
+    pub opcode: u7,
+    pub rd: u5,
+    pub funct3: u3,
+    pub rs1: u5,
+    pub rs2: u5,
+    pub rl: u1,
+    pub aq: u1,
+    pub funct5: u5,
+}
+
+/* Fused multiply-add: funct7 is split into the third source register and funct2. */
+#[bitsize(32)]
+#[derive(FromBits, DebugBits, Clone, Copy)]
+pub struct R4Type {
+    pub opcode: u7,
+    pub rd: u5,
+    pub funct3: u3,
+    pub rs1: u5,
+    pub rs2: u5,
+    pub funct2: u2,
+    pub rs3: u5,
+}
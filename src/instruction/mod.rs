@@ -1,17 +1,96 @@
 pub mod definitions;
 pub mod formats;
 
+use std::ops::RangeInclusive;
+
+use bilge::prelude::{Integer, u5};
 use thiserror::Error;
 
-use crate::instruction::{
-    definitions::Op,
-    formats::{InstructionFormat, RType},
+use crate::{
+    instruction::{
+        definitions::{Encoding, Extension, Op},
+        formats::{Format, InstructionFormat},
+    },
+    isa::{Isa, Xlen},
 };
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Unknown instruction: {0}")]
     UnknownInstruction(String),
+
+    #[error("{0} requires the {1} extension, which is disabled.")]
+    ExtensionDisabled(Op, Extension),
+
+    #[error("{0} uses register x{1}, which does not exist in RV32E or RV64E.")]
+    RegisterUnavailable(Op, u8),
+
+    #[error("{0} does not exist on {1}.")]
+    UnavailableOnXlen(Op, Xlen),
+
+    #[error("{0} has no {1} operand.")]
+    UnexpectedOperand(Op, &'static str),
+
+    #[error("{1} is out of range for the immediate of {0}.")]
+    ImmediateOutOfRange(Op, i32),
+
+    #[error("{0} takes an offset that is a multiple of 2, not {1}.")]
+    MisalignedImmediate(Op, i32),
+
+    #[error("{0} with these operands is encoded as {1}.")]
+    EncodedAsOther(Op, Op),
+}
+
+/// What the imm operand of `Instruction::encode` holds for an op.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Immediate {
+    /// The op has no immediate operand.
+    None,
+    /// A signed immediate of this many bits.
+    Signed(u32),
+    /// An unsigned immediate of this many bits: a CSR, the fence fields or an upper
+    /// immediate.
+    Unsigned(u32),
+    /// A shift amount of this many bits.
+    Shamt(u32),
+    /// The rounding mode, in funct3.
+    RoundingMode,
+    /// `rs3 << 3 | rm`.
+    FusedMultiplyAdd,
+    /// `aq << 1 | rl`.
+    Ordering,
+}
+
+impl Immediate {
+    fn of(op: Op, encoding: &Encoding) -> Immediate {
+        match op.format() {
+            _ if encoding.imm.is_some() => Immediate::None,
+            Format::I if op == Op::fence || op.extension() == Extension::Zicsr => {
+                Immediate::Unsigned(12)
+            }
+            Format::I if encoding.funct6.is_some() => Immediate::Shamt(6),
+            Format::I if encoding.funct7.is_some() => Immediate::Shamt(5),
+            Format::I | Format::S => Immediate::Signed(12),
+            Format::B => Immediate::Signed(13),
+            Format::J => Immediate::Signed(21),
+            Format::U => Immediate::Unsigned(20),
+            Format::R if encoding.funct3.is_none() => Immediate::RoundingMode,
+            Format::R => Immediate::None,
+            Format::R4 => Immediate::FusedMultiplyAdd,
+            Format::A => Immediate::Ordering,
+        }
+    }
+
+    fn range(self) -> RangeInclusive<i32> {
+        match self {
+            Immediate::None => 0..=0,
+            Immediate::Signed(bits) => -(1 << (bits - 1))..=(1 << (bits - 1)) - 1,
+            Immediate::Unsigned(bits) | Immediate::Shamt(bits) => 0..=(1 << bits) - 1,
+            Immediate::RoundingMode => 0..=0x7,
+            Immediate::FusedMultiplyAdd => 0..=0xFF,
+            Immediate::Ordering => 0..=0x3,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Copy)]
@@ -23,16 +102,11 @@ pub struct Instruction {
 }
 
 impl Instruction {
-    pub fn from_bytes(bytes: [u8; 4]) -> Result<Instruction, Error> {
+    pub fn from_bytes(bytes: [u8; 4], xlen: Xlen) -> Result<Instruction, Error> {
         let raw = u32::from_le_bytes(bytes);
 
-        let rtype = RType::from(raw);
-        let opcode = rtype.opcode().value();
-        let funct3 = rtype.funct3().value();
-        let funct7 = rtype.funct7().value();
-
-        let op = definitions::decode_op(opcode, funct3, funct7)
-            .ok_or(Error::UnknownInstruction(opcode.to_string()))?;
+        let op = definitions::decode_op(raw, xlen)
+            .ok_or(Error::UnknownInstruction(format!("{raw:#010X}")))?;
 
         Ok(Instruction {
             op,
@@ -41,6 +115,156 @@ impl Instruction {
         })
     }
 
+    /* Builds the instruction word of op, the inverse of from_bytes. Registers the op
+     * does not use must be 0. imm is the immediate as assembly writes it, e.g. the
+     * offset of a branch or the CSR of a Zicsr op. Ops without one take the fields
+     * their encoding leaves over instead: the rounding mode, `rs3 << 3 | rm` for fused
+     * multiply-add, or `aq << 1 | rl` for atomics. */
+    pub fn encode(op: Op, rd: u5, rs1: u5, rs2: u5, imm: i32, xlen: Xlen) -> Result<u32, Error> {
+        let encoding = op
+            .encoding(xlen)
+            .ok_or(Error::UnavailableOnXlen(op, xlen))?;
+        let format = op.format();
+
+        let (has_rd, has_rs1, has_rs2) = match format {
+            Format::R | Format::A | Format::R4 => (true, true, encoding.rs2.is_none()),
+            Format::I => (true, true, false),
+            Format::S | Format::B => (false, true, true),
+            Format::U | Format::J => (true, false, false),
+        };
+        for (name, used, reg) in [
+            ("rd", has_rd, rd),
+            ("rs1", has_rs1, rs1),
+            ("rs2", has_rs2, rs2),
+        ] {
+            if !used && reg.value() != 0 {
+                return Err(Error::UnexpectedOperand(op, name));
+            }
+        }
+
+        let kind = Immediate::of(op, &encoding);
+        if !kind.range().contains(&imm) {
+            return Err(Error::ImmediateOutOfRange(op, imm));
+        }
+        if matches!(format, Format::B | Format::J) && imm % 2 != 0 {
+            return Err(Error::MisalignedImmediate(op, imm));
+        }
+
+        let field =
+            |value: Option<u8>, shift: u32| value.map_or(0, |value| (value as u32) << shift);
+        let imm_bits = match kind {
+            Immediate::RoundingMode => (imm as u32) << 12,
+            Immediate::FusedMultiplyAdd => (imm as u32 & 0x7) << 12 | (imm as u32 >> 3) << 27,
+            Immediate::Ordering => (imm as u32) << 25,
+            _ => format.immediate_bits(imm),
+        };
+        let raw = encoding.opcode as u32
+            | (rd.value() as u32) << 7
+            | (rs1.value() as u32) << 15
+            | (rs2.value() as u32) << 20
+            | field(encoding.funct3, 12)
+            | field(encoding.funct7, 25)
+            | field(encoding.funct6, 26)
+            | field(encoding.funct5, 27)
+            | field(encoding.funct2, 25)
+            | field(encoding.rs2, 20)
+            | encoding.imm.map_or(0, |imm| (imm as u32) << 20)
+            | imm_bits;
+
+        // e.g. a fence that orders like fence.tso is fence.tso.
+        match definitions::decode_op(raw, xlen) {
+            Some(decoded) if decoded != op => Err(Error::EncodedAsOther(op, decoded)),
+            _ => Ok(raw),
+        }
+    }
+
+    /* The rd, rs1, rs2 and imm that encode takes to build this instruction again. */
+    pub fn operands(&self) -> (u5, u5, u5, i32) {
+        let encoding = self.op.encodings()[0];
+        let format = self.format;
+        let zero = u5::new(0);
+        let imm = format.immediate_value().unwrap_or(0);
+        let funct3 = format.funct3().map_or(0, |funct3| funct3.value() as i32);
+
+        let imm = match Immediate::of(self.op, &encoding) {
+            Immediate::None => 0,
+            Immediate::Signed(_) => imm,
+            Immediate::Unsigned(bits) => imm & ((1 << bits) - 1),
+            // funct7 forms keep bit 5 clear, so this holds on either width.
+            Immediate::Shamt(_) => imm & 0x3F,
+            Immediate::RoundingMode => funct3,
+            Immediate::FusedMultiplyAdd => {
+                (format.rs3().unwrap_or(zero).value() as i32) << 3 | funct3
+            }
+            Immediate::Ordering => match format {
+                InstructionFormat::A(atype) => {
+                    (atype.aq().value() as i32) << 1 | atype.rl().value() as i32
+                }
+                _ => 0,
+            },
+        };
+        let rs2 = match encoding.rs2 {
+            Some(_) => zero,
+            None => format.rs2().unwrap_or(zero),
+        };
+
+        (
+            format.rd().unwrap_or(zero),
+            format.rs1().unwrap_or(zero),
+            rs2,
+            imm,
+        )
+    }
+
+    /* Decodes an instruction, rejecting those whose extension is not enabled in isa. */
+    pub fn decode(bytes: [u8; 4], isa: &Isa) -> Result<Instruction, Error> {
+        let instr = Self::from_bytes(bytes, isa.xlen())?;
+        let extension = instr.op().extension();
+        if !isa.supports(extension) {
+            return Err(Error::ExtensionDisabled(instr.op(), extension));
+        }
+
+        if let Some(reg) = instr
+            .integer_registers()
+            .find(|reg| reg.as_usize() >= isa.register_count())
+        {
+            return Err(Error::RegisterUnavailable(instr.op(), reg.value()));
+        }
+
+        Ok(instr)
+    }
+
+    /* The operand fields that name integer (x) registers. */
+    pub fn integer_registers(&self) -> impl Iterator<Item = u5> {
+        let format = self.format;
+        let (rd, rs1, rs2) = match self.op {
+            Op::flw | Op::fld | Op::fsw | Op::fsd => (false, true, false),
+            Op::fcvt_w_s | Op::fcvt_wu_s | Op::fcvt_w_d | Op::fcvt_wu_d => (true, false, false),
+            Op::fcvt_l_s | Op::fcvt_lu_s | Op::fcvt_l_d | Op::fcvt_lu_d => (true, false, false),
+            Op::fcvt_s_w | Op::fcvt_s_wu | Op::fcvt_d_w | Op::fcvt_d_wu => (false, true, false),
+            Op::fcvt_s_l | Op::fcvt_s_lu | Op::fcvt_d_l | Op::fcvt_d_lu => (false, true, false),
+            Op::fmv_x_w | Op::fmv_x_d | Op::fclass_s | Op::fclass_d => (true, false, false),
+            Op::feq_s | Op::flt_s | Op::fle_s | Op::feq_d | Op::flt_d | Op::fle_d => {
+                (true, false, false)
+            }
+            Op::fmv_w_x | Op::fmv_d_x => (false, true, false),
+            _ if matches!(self.op.extension(), Extension::F | Extension::D) => {
+                (false, false, false)
+            }
+            // the immediate CSR forms encode a constant in the rs1 field.
+            Op::csrrwi | Op::csrrsi | Op::csrrci => (true, false, false),
+            _ => (true, true, true),
+        };
+
+        [
+            format.rd().filter(|_| rd),
+            format.rs1().filter(|_| rs1),
+            format.rs2().filter(|_| rs2),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
     pub fn op(&self) -> Op {
         self.op
     }
@@ -0,0 +1,324 @@
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use thiserror::Error;
+
+use crate::instruction::definitions::Extension;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("ISA string {0:?} does not start with rv32 or rv64 followed by i, e or g.")]
+    InvalidBase(String),
+
+    #[error("Extension {0:?} is not supported.")]
+    UnsupportedExtension(String),
+
+    #[error("ISA string {0:?} includes C, but compressed instructions are not supported.")]
+    CompressedUnsupported(String),
+
+    #[error("ISA string {0:?} is malformed.")]
+    Malformed(String),
+}
+
+/// Width of the integer registers and of addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Xlen {
+    #[default]
+    Rv32,
+    Rv64,
+}
+
+impl Xlen {
+    pub fn bits(self) -> u32 {
+        match self {
+            Xlen::Rv32 => 32,
+            Xlen::Rv64 => 64,
+        }
+    }
+
+    /* Registers are held as i64: an RV32 value is kept sign-extended from bit 31. */
+    pub fn sext(self, value: i64) -> i64 {
+        match self {
+            Xlen::Rv32 => value as i32 as i64,
+            Xlen::Rv64 => value,
+        }
+    }
+
+    /* The register value as an unsigned XLEN-bit number, e.g. for addresses. */
+    pub fn zext(self, value: i64) -> u64 {
+        match self {
+            Xlen::Rv32 => value as u32 as u64,
+            Xlen::Rv64 => value as u64,
+        }
+    }
+
+    /* Shift amounts use the low 5 bits on RV32 and the low 6 bits on RV64. */
+    pub fn shamt(self, value: i64) -> u32 {
+        value as u32 & (self.bits() - 1)
+    }
+}
+
+impl fmt::Display for Xlen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rv{}", self.bits())
+    }
+}
+
+/// The base width and set of extensions a machine decodes, e.g. `rv32ifd_zicsr_zba`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Isa {
+    xlen: Xlen,
+    extensions: BTreeSet<Extension>,
+    /// RV32E: only x0-x15 exist, encodings naming x16-x31 are illegal.
+    embedded: bool,
+}
+
+impl Isa {
+    /* Only the base integer instruction set. */
+    pub fn base() -> Self {
+        Isa {
+            xlen: Xlen::Rv32,
+            extensions: BTreeSet::from([Extension::I]),
+            embedded: false,
+        }
+    }
+
+    pub fn xlen(&self) -> Xlen {
+        self.xlen
+    }
+
+    pub fn set_xlen(&mut self, xlen: Xlen) {
+        self.xlen = xlen;
+    }
+
+    pub fn is_embedded(&self) -> bool {
+        self.embedded
+    }
+
+    pub fn set_embedded(&mut self, embedded: bool) {
+        self.embedded = embedded;
+    }
+
+    /* Number of integer registers of the base ISA. */
+    pub fn register_count(&self) -> usize {
+        if self.embedded { 16 } else { 32 }
+    }
+
+    pub fn supports(&self, extension: Extension) -> bool {
+        self.extensions.contains(&extension)
+    }
+
+    pub fn enable(&mut self, extension: Extension) {
+        self.extensions.insert(extension);
+    }
+
+    pub fn disable(&mut self, extension: Extension) {
+        if extension != Extension::I {
+            self.extensions.remove(&extension);
+        }
+    }
+
+    pub fn extensions(&self) -> impl Iterator<Item = Extension> + '_ {
+        self.extensions.iter().copied()
+    }
+}
+
+/* Every implemented extension is enabled by default. */
+impl Default for Isa {
+    fn default() -> Self {
+        Isa {
+            xlen: Xlen::Rv32,
+            extensions: Extension::ALL.iter().copied().collect(),
+            embedded: false,
+        }
+    }
+}
+
+/* Extensions enabled by `g`. */
+const GENERAL: &[Extension] = &[
+    Extension::M,
+    Extension::A,
+    Extension::F,
+    Extension::D,
+    Extension::Zicsr,
+    Extension::Zifencei,
+];
+
+/* Hint extensions whose encodings are already decoded as base instructions. */
+const HINTS: &[&str] = &["zihintpause", "zihintntl"];
+
+/* Extensions that an extension requires, e.g. `d` requires `f`, or is made of, e.g. `m`
+ * is Zmmul with division. */
+fn implied(extension: Extension) -> &'static [Extension] {
+    match extension {
+        Extension::M => &[Extension::Zmmul],
+        Extension::A => &[Extension::Zaamo, Extension::Zalrsc],
+        Extension::F => &[Extension::Zicsr],
+        Extension::D => &[Extension::F, Extension::Zicsr],
+        _ => &[],
+    }
+}
+
+fn parse_extension(name: &str) -> Result<Extension, Error> {
+    Extension::ALL
+        .iter()
+        .copied()
+        .find(|extension| extension.to_string() == name)
+        .ok_or_else(|| Error::UnsupportedExtension(name.to_string()))
+}
+
+/* Splits a leading version such as `2p1` or `2` off s, returning the major and minor
+ * numbers. A `p` not between two numbers is not part of the version. */
+fn split_version(s: &str) -> (Option<(u32, u32)>, &str) {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+
+    let major_len = digits(s);
+    let Ok(major) = s[..major_len].parse() else {
+        return (None, s);
+    };
+    let rest = &s[major_len..];
+    match rest.strip_prefix('p') {
+        Some(minor) if digits(minor) > 0 => {
+            let minor_len = digits(minor);
+            let version = (major, minor[..minor_len].parse().unwrap_or(0));
+            (Some(version), &minor[minor_len..])
+        }
+        _ => (Some((major, 0)), rest),
+    }
+}
+
+/* Splits a trailing version such as `2p1` or `2` off an extension name. */
+fn strip_version(name: &str) -> &str {
+    let without_minor = match name.rfind('p') {
+        Some(p) if p + 1 < name.len() && name[p + 1..].bytes().all(|b| b.is_ascii_digit()) => {
+            &name[..p]
+        }
+        _ => name,
+    };
+    let stripped = without_minor.trim_end_matches(|c: char| c.is_ascii_digit());
+
+    if stripped.len() == without_minor.len() && without_minor.len() != name.len() {
+        // a `pN` suffix without a major version is part of the name.
+        return name;
+    }
+    stripped
+}
+
+impl Isa {
+    fn enable_with_implied(&mut self, extension: Extension) {
+        self.enable(extension);
+        for &dependency in implied(extension) {
+            self.enable(dependency);
+        }
+    }
+
+    /* Enables the extension named in the ISA string s. C is left out when skip_compressed
+     * is set, and an error otherwise. */
+    fn enable_named(&mut self, name: &str, s: &str, skip_compressed: bool) -> Result<(), Error> {
+        match name {
+            "c" if skip_compressed => Ok(()),
+            "c" => Err(Error::CompressedUnsupported(s.to_string())),
+            name if HINTS.contains(&name) => Ok(()),
+            name => {
+                self.enable_with_implied(parse_extension(name)?);
+                Ok(())
+            }
+        }
+    }
+
+    /* Parses s like from_str, but leaves out C instead of failing, for files built for a
+     * machine with compressed instructions. Also returns whether s included C. */
+    pub fn parse_without_compressed(s: &str) -> Result<(Isa, bool), Error> {
+        match s.parse() {
+            Err(Error::CompressedUnsupported(_)) => Ok((Self::parse(s, true)?, true)),
+            result => result.map(|isa| (isa, false)),
+        }
+    }
+
+    /* Parses a standard ISA string such as `rv32imaf_zicsr` or `rv32i2p1_m2p0_zba1p0`.
+     * Version numbers are ignored, except that version 2.0 of the base still included
+     * Zicsr and Zifencei, which later versions split off. */
+    fn parse(s: &str, skip_compressed: bool) -> Result<Isa, Error> {
+        let lower = s.to_lowercase();
+        let (xlen, rest) = if let Some(rest) = lower.strip_prefix("rv32") {
+            (Xlen::Rv32, rest)
+        } else if let Some(rest) = lower.strip_prefix("rv64") {
+            (Xlen::Rv64, rest)
+        } else {
+            return Err(Error::InvalidBase(s.to_string()));
+        };
+
+        let mut isa = Isa::base();
+        isa.set_xlen(xlen);
+        let mut chars = rest.chars();
+        let base = chars.next();
+        let (version, rest) = split_version(chars.as_str());
+        match base {
+            Some('i') if version == Some((2, 0)) => {
+                isa.enable(Extension::Zicsr);
+                isa.enable(Extension::Zifencei);
+            }
+            Some('i') => {}
+            Some('e') => isa.set_embedded(true),
+            Some('g') => GENERAL.iter().for_each(|&ext| isa.enable_with_implied(ext)),
+            _ => return Err(Error::InvalidBase(s.to_string())),
+        }
+
+        let mut parts = rest.split('_');
+        let mut single_letters = parts.next().unwrap_or_default();
+
+        // single letter extensions, each optionally followed by a version.
+        let mut multi_letter_tail = None;
+        while let Some(letter) = single_letters.chars().next() {
+            if matches!(letter, 'z' | 's' | 'x') {
+                multi_letter_tail = Some(single_letters);
+                break;
+            }
+            if !letter.is_ascii_lowercase() {
+                return Err(Error::Malformed(s.to_string()));
+            }
+            isa.enable_named(&letter.to_string(), s, skip_compressed)?;
+            single_letters = split_version(&single_letters[1..]).1;
+        }
+
+        for name in multi_letter_tail.into_iter().chain(parts) {
+            let name = strip_version(name);
+            if !name.is_empty() {
+                isa.enable_named(name, s, skip_compressed)?;
+            }
+        }
+
+        Ok(isa)
+    }
+}
+
+impl FromStr for Isa {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Isa::parse(s, false)
+    }
+}
+
+impl fmt::Display for Isa {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (single, multi): (Vec<_>, Vec<_>) = self
+            .extensions()
+            .map(|extension| extension.to_string())
+            .partition(|name| name.len() == 1);
+
+        write!(f, "{}", self.xlen)?;
+        for name in single {
+            let name = if name == "i" && self.embedded {
+                "e"
+            } else {
+                &name
+            };
+            write!(f, "{name}")?;
+        }
+        for name in multi {
+            write!(f, "_{name}")?;
+        }
+
+        Ok(())
+    }
+}
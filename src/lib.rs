@@ -1,3 +1,6 @@
+pub mod assembler;
 pub mod file_parser;
 pub mod instruction;
+pub mod isa;
+pub mod loader;
 pub mod machine;
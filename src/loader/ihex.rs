@@ -0,0 +1,72 @@
+use crate::loader::{Error, Image, Loader, Segments, hex_bytes};
+
+/// Intel HEX: lines of `:LLAAAATT<data>CC` records. Data records hold 16-bit addresses,
+/// which extended segment and extended linear address records widen.
+pub struct IntelHex;
+
+impl IntelHex {
+    const DATA: u8 = 0x00;
+    const END_OF_FILE: u8 = 0x01;
+    const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+    const START_SEGMENT_ADDRESS: u8 = 0x03;
+    const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+    const START_LINEAR_ADDRESS: u8 = 0x05;
+}
+
+impl Loader for IntelHex {
+    fn load(&self, data: &[u8]) -> Result<Image, Error> {
+        let text = String::from_utf8_lossy(data);
+        let mut segments = Segments::default();
+        let mut base = 0;
+        let mut entry = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let (number, line) = (i + 1, line.trim());
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || Error::InvalidRecord(number, line.to_string());
+
+            let digits = line.strip_prefix(':').ok_or_else(invalid)?;
+            let bytes = hex_bytes(number, line, digits)?;
+            // length, address, type, data and checksum.
+            if bytes.len() < 5 || bytes.len() != 5 + bytes[0] as usize {
+                return Err(invalid());
+            }
+            let (record, &[checksum]) = bytes.split_at(bytes.len() - 1) else {
+                unreachable!("records are at least 5 bytes")
+            };
+            let expected = record
+                .iter()
+                .fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+                .wrapping_neg();
+            if checksum != expected {
+                return Err(Error::ChecksumMismatch(number, checksum, expected));
+            }
+
+            let addr = u16::from_be_bytes([record[1], record[2]]) as u64;
+            let word = |bytes: &[u8]| bytes.iter().fold(0, |word, &byte| word << 8 | byte as u64);
+            match (record[3], &record[4..]) {
+                (Self::DATA, payload) => segments.push(base + addr, payload),
+                (Self::END_OF_FILE, []) => {
+                    return Ok(Image {
+                        segments: segments.finish()?,
+                        entry,
+                        ..Image::default()
+                    });
+                }
+                (Self::EXTENDED_SEGMENT_ADDRESS, segment @ [_, _]) => base = word(segment) << 4,
+                (Self::START_SEGMENT_ADDRESS, start @ [_, _, _, _]) => {
+                    // CS:IP, where an x86 program would start in real mode.
+                    entry = Some((word(&start[..2]) << 4) + word(&start[2..]))
+                }
+                (Self::EXTENDED_LINEAR_ADDRESS, upper @ [_, _]) => base = word(upper) << 16,
+                (Self::START_LINEAR_ADDRESS, eip @ [_, _, _, _]) => entry = Some(word(eip)),
+                (Self::END_OF_FILE..=Self::START_LINEAR_ADDRESS, _) => return Err(invalid()),
+                (kind, _) => return Err(Error::UnknownRecordType(number, format!("{kind:02X}"))),
+            }
+        }
+
+        Err(Error::MissingEndOfFile())
+    }
+}
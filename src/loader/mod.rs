@@ -0,0 +1,340 @@
+mod ihex;
+mod srec;
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+pub use ihex::IntelHex;
+pub use srec::SRecord;
+
+use crate::{
+    file_parser::{self, ElfFile, Requirements},
+    isa::Isa,
+    machine::Permissions,
+};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Line {0}: malformed record {1:?}.")]
+    InvalidRecord(usize, String),
+
+    #[error("Line {0}: checksum is {1:#04X}, expected {2:#04X}.")]
+    ChecksumMismatch(usize, u8, u8),
+
+    #[error("Line {0}: unknown record type {1}.")]
+    UnknownRecordType(usize, String),
+
+    #[error("The file ends without an end of file record.")]
+    MissingEndOfFile(),
+
+    #[error("Data at {0:#X} is loaded more than once.")]
+    Overlap(u64),
+
+    #[error("Images {0} and {1} both load data at {2:#X}.")]
+    OverlappingImages(usize, usize, u64),
+
+    #[error("There is no image {0} to start from.")]
+    NoSuchImage(usize),
+
+    #[error("No image defines the entry symbol {0}.")]
+    UndefinedSymbol(String),
+
+    #[error("Segment {0:#X}..{1:#X} does not fit in memory.")]
+    OutOfMemory(u64, u64),
+
+    #[error("Segment at {0:#X} of {1:#X} bytes runs past the end of the address space.")]
+    AddressOverflow(u64, u64),
+
+    #[error("Error loading the ELF file: {0}")]
+    Elf(#[from] file_parser::Error),
+}
+
+/// Bytes loaded at an address. The memory between the end of `data` and `size` is zeroed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub addr: u64,
+    pub data: Vec<u8>,
+    pub size: u64,
+    pub permissions: Permissions,
+}
+
+impl Segment {
+    /* A segment holding exactly data, for formats without permissions. */
+    pub fn new(addr: u64, data: Vec<u8>) -> Self {
+        Segment {
+            addr,
+            size: data.len() as u64,
+            data,
+            permissions: Permissions::ALL,
+        }
+    }
+
+    /* The address after the last byte the segment loads, unless that is past 2^64. */
+    pub fn end(&self) -> Result<u64, Error> {
+        let size = self.size.max(self.data.len() as u64);
+
+        self.addr
+            .checked_add(size)
+            .ok_or(Error::AddressOverflow(self.addr, size))
+    }
+
+    /* Copies the segment into memory starting at address base, which may still hold an
+     * earlier program: the bytes between the end of data and size are zeroed. */
+    pub fn load_into(&self, memory: &mut [u8], base: u64) -> Result<(), Error> {
+        let end = self.end()?;
+        if self.addr < base || end - base > memory.len() as u64 {
+            return Err(Error::OutOfMemory(self.addr, end));
+        }
+
+        let start = (self.addr - base) as usize;
+        let (data, zeros) = memory[start..(end - base) as usize].split_at_mut(self.data.len());
+        data.copy_from_slice(&self.data);
+        zeros.fill(0);
+
+        Ok(())
+    }
+}
+
+/// A program as read from a file, ready to be placed in the memory of a machine.
+#[derive(Debug, Clone, Default)]
+pub struct Image {
+    pub segments: Vec<Segment>,
+    /// Where execution starts, if the file says.
+    pub entry: Option<u64>,
+    /// The extensions the program was built for, if the file says.
+    pub isa: Option<Isa>,
+    /// The alignment the psABI asks of sp, if the file says.
+    pub stack_align: Option<u64>,
+    pub symbols: HashMap<String, u64>,
+    /// What the files read ask of the machine, checked against the ISA it decodes.
+    pub requirements: Vec<Requirements>,
+}
+
+impl Image {
+    /* The entry point, or the lowest loaded address if the file has none. */
+    pub fn entry_point(&self) -> u64 {
+        self.entry.unwrap_or_else(|| {
+            self.segments
+                .iter()
+                .map(|segment| segment.addr)
+                .min()
+                .unwrap_or(0)
+        })
+    }
+}
+
+/// Where a machine loaded with several images starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    /// The entry point of the image at this index.
+    Image(usize),
+    Address(u64),
+    Symbol(String),
+}
+
+impl Image {
+    /* Combines images that share one memory into one, starting at entry. The ISA and the
+     * stack alignment come from the first image that records them, and so does a symbol
+     * defined by more than one image. Segments may not overlap, even within an image. */
+    pub fn combine(images: &[Image], entry: &Entry) -> Result<Image, Error> {
+        let mut segments: Vec<(usize, &Segment)> = images
+            .iter()
+            .enumerate()
+            .flat_map(|(i, image)| image.segments.iter().map(move |segment| (i, segment)))
+            .filter(|(_, segment)| segment.size > 0)
+            .collect();
+        // once sorted, a segment overlapping any other overlaps the one after it.
+        segments.sort_by_key(|(_, segment)| segment.addr);
+        for pair in segments.windows(2) {
+            let [(first, before), (second, after)] = pair else {
+                unreachable!("windows of 2")
+            };
+            if after.addr < before.end()? {
+                return Err(match first == second {
+                    true => Error::Overlap(after.addr),
+                    false => {
+                        Error::OverlappingImages(*first.min(second), *first.max(second), after.addr)
+                    }
+                });
+            }
+        }
+        if let Some((_, last)) = segments.last() {
+            last.end()?;
+        }
+
+        let mut symbols = HashMap::new();
+        for image in images {
+            for (name, &addr) in &image.symbols {
+                symbols.entry(name.clone()).or_insert(addr);
+            }
+        }
+        let entry = match entry {
+            Entry::Image(i) => images.get(*i).ok_or(Error::NoSuchImage(*i))?.entry_point(),
+            Entry::Address(addr) => *addr,
+            Entry::Symbol(name) => *symbols
+                .get(name)
+                .ok_or_else(|| Error::UndefinedSymbol(name.clone()))?,
+        };
+
+        Ok(Image {
+            segments: segments
+                .into_iter()
+                .map(|(_, segment)| segment.clone())
+                .collect(),
+            entry: Some(entry),
+            isa: images.iter().find_map(|image| image.isa.clone()),
+            stack_align: images.iter().find_map(|image| image.stack_align),
+            symbols,
+            requirements: images
+                .iter()
+                .flat_map(|image| image.requirements.iter().cloned())
+                .collect(),
+        })
+    }
+}
+
+/// A file format programs can be loaded from.
+pub trait Loader {
+    fn load(&self, data: &[u8]) -> Result<Image, Error>;
+}
+
+/// Executable and Linkable Format, as produced by compilers and linkers.
+pub struct Elf;
+
+impl Loader for Elf {
+    fn load(&self, data: &[u8]) -> Result<Image, Error> {
+        let file = ElfFile::from_buffer(data)?;
+        let attributes = file.riscv_attributes()?;
+        let requirements = file.requirements()?;
+        if requirements.compressed && !file.flags().rvc {
+            println!(
+                "warning: the file was built for an ISA with C, which is left out: {}",
+                attributes.arch.as_deref().unwrap_or_default()
+            );
+        }
+        let mut isa = requirements.arch.clone().unwrap_or_else(|| {
+            let mut isa = Isa::default();
+            isa.set_xlen(file.xlen());
+            isa
+        });
+        if file.flags().rve {
+            isa.set_embedded(true);
+        }
+
+        Ok(Image {
+            segments: file.load_segments()?,
+            entry: Some(file.entry_point()),
+            isa: Some(isa),
+            stack_align: attributes.stack_align,
+            symbols: file.symbols()?,
+            requirements: vec![requirements],
+        })
+    }
+}
+
+/// The bytes of a program as they are in memory, without any header.
+pub struct Binary {
+    pub base: u64,
+    pub entry: u64,
+}
+
+impl Loader for Binary {
+    fn load(&self, data: &[u8]) -> Result<Image, Error> {
+        Ok(Image {
+            segments: vec![Segment::new(self.base, data.to_vec())],
+            entry: Some(self.entry),
+            ..Image::default()
+        })
+    }
+}
+
+/// The formats programs can be loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Elf,
+    IntelHex,
+    SRecord,
+    Binary,
+}
+
+impl Format {
+    /* Guesses the format from the contents of a file: ELF files start with their magic
+     * number, and text files of records with `:` or `S`. Anything else is raw. */
+    pub fn detect(data: &[u8]) -> Format {
+        let text = data.trim_ascii_start();
+        if data.starts_with(&elf::abi::ELFMAGIC) {
+            Format::Elf
+        } else if text.starts_with(b":") && text.is_ascii() {
+            Format::IntelHex
+        } else if text.len() > 1 && text[0] == b'S' && text[1].is_ascii_digit() && text.is_ascii() {
+            Format::SRecord
+        } else {
+            Format::Binary
+        }
+    }
+
+    /* A loader for the format. Only raw binaries use base and entry, as the other formats
+     * record where they go. */
+    pub fn loader(self, base: u64, entry: u64) -> Box<dyn Loader> {
+        match self {
+            Format::Elf => Box::new(Elf),
+            Format::IntelHex => Box::new(IntelHex),
+            Format::SRecord => Box::new(SRecord),
+            Format::Binary => Box::new(Binary { base, entry }),
+        }
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "elf" => Ok(Format::Elf),
+            "ihex" | "hex" => Ok(Format::IntelHex),
+            "srec" => Ok(Format::SRecord),
+            "bin" | "binary" => Ok(Format::Binary),
+            _ => Err(format!(
+                "unknown image format {s}, expected elf, ihex, srec or bin"
+            )),
+        }
+    }
+}
+
+/// Segments collected from the records of a file, merged where they are contiguous.
+#[derive(Default)]
+struct Segments(Vec<Segment>);
+
+impl Segments {
+    fn push(&mut self, addr: u64, data: &[u8]) {
+        match self.0.last_mut() {
+            Some(last) if last.end().ok() == Some(addr) => {
+                last.data.extend_from_slice(data);
+                last.size = last.data.len() as u64;
+            }
+            _ => self.0.push(Segment::new(addr, data.to_vec())),
+        }
+    }
+
+    /* The segments in address order, once none overlap. */
+    fn finish(mut self) -> Result<Vec<Segment>, Error> {
+        self.0.retain(|segment| segment.size > 0);
+        self.0.sort_by_key(|segment| segment.addr);
+        for pair in self.0.windows(2) {
+            if pair[1].addr < pair[0].end()? {
+                return Err(Error::Overlap(pair[1].addr));
+            }
+        }
+        if let Some(last) = self.0.last() {
+            last.end()?;
+        }
+
+        Ok(self.0)
+    }
+}
+
+/* Decodes the hex digits of a record, e.g. `0300300002337A`. */
+fn hex_bytes(number: usize, line: &str, digits: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(digits).map_err(|_| Error::InvalidRecord(number, line.to_string()))
+}
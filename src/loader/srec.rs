@@ -0,0 +1,67 @@
+use crate::loader::{Error, Image, Loader, Segments, hex_bytes};
+
+/// Motorola S-records: lines of `S<type><count><address><data><checksum>`. S1, S2 and S3
+/// hold data at 16, 24 and 32-bit addresses, and S9, S8 and S7 end the file with the
+/// entry point.
+pub struct SRecord;
+
+impl Loader for SRecord {
+    fn load(&self, data: &[u8]) -> Result<Image, Error> {
+        let text = String::from_utf8_lossy(data);
+        let mut segments = Segments::default();
+        let mut records = 0;
+
+        for (i, line) in text.lines().enumerate() {
+            let (number, line) = (i + 1, line.trim());
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || Error::InvalidRecord(number, line.to_string());
+
+            let kind = line.strip_prefix('S').ok_or_else(invalid)?;
+            let kind = kind.get(..1).ok_or_else(invalid)?;
+            // the size of the address, or of the record count for S5 and S6.
+            let address_size = match kind {
+                "0" | "1" | "5" | "9" => 2,
+                "2" | "6" | "8" => 3,
+                "3" | "7" => 4,
+                _ => return Err(Error::UnknownRecordType(number, format!("S{kind}"))),
+            };
+
+            let bytes = hex_bytes(number, line, &line[2..])?;
+            // count, address, data and checksum; the count includes all but itself.
+            if bytes.len() < address_size + 2 || bytes.len() != 1 + bytes[0] as usize {
+                return Err(invalid());
+            }
+            let (record, &[checksum]) = bytes.split_at(bytes.len() - 1) else {
+                unreachable!("records are at least 4 bytes")
+            };
+            let expected = !record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+            if checksum != expected {
+                return Err(Error::ChecksumMismatch(number, checksum, expected));
+            }
+
+            let (addr, payload) = record[1..].split_at(address_size);
+            let addr = addr.iter().fold(0, |addr, &byte| addr << 8 | byte as u64);
+            match kind {
+                // a header, usually the name of the file.
+                "0" => {}
+                "1" | "2" | "3" => {
+                    segments.push(addr, payload);
+                    records += 1;
+                }
+                "5" | "6" if addr != records => return Err(invalid()),
+                "5" | "6" => {}
+                _ => {
+                    return Ok(Image {
+                        segments: segments.finish()?,
+                        entry: Some(addr),
+                        ..Image::default()
+                    });
+                }
+            }
+        }
+
+        Err(Error::MissingEndOfFile())
+    }
+}
@@ -0,0 +1,353 @@
+use crate::{
+    instruction::definitions::Extension,
+    isa::{Isa, Xlen},
+    machine::{MachineError, pmp::Pmp, trap::Privilege},
+};
+
+pub(crate) mod address {
+    pub const FFLAGS: u16 = 0x001;
+    pub const FRM: u16 = 0x002;
+    pub const FCSR: u16 = 0x003;
+
+    pub const SSTATUS: u16 = 0x100;
+    pub const SIE: u16 = 0x104;
+    pub const STVEC: u16 = 0x105;
+    pub const SSCRATCH: u16 = 0x140;
+    pub const SEPC: u16 = 0x141;
+    pub const SCAUSE: u16 = 0x142;
+    pub const STVAL: u16 = 0x143;
+    pub const SIP: u16 = 0x144;
+    pub const SATP: u16 = 0x180;
+
+    pub const MSTATUS: u16 = 0x300;
+    pub const MISA: u16 = 0x301;
+    pub const MEDELEG: u16 = 0x302;
+    pub const MIDELEG: u16 = 0x303;
+    pub const MIE: u16 = 0x304;
+    pub const MTVEC: u16 = 0x305;
+    pub const MSCRATCH: u16 = 0x340;
+    pub const MEPC: u16 = 0x341;
+    pub const MCAUSE: u16 = 0x342;
+    pub const MTVAL: u16 = 0x343;
+    pub const MIP: u16 = 0x344;
+    pub const PMPCFG0: u16 = 0x3A0;
+    pub const PMPCFG3: u16 = 0x3A3;
+    pub const PMPADDR0: u16 = 0x3B0;
+    pub const PMPADDR15: u16 = 0x3BF;
+
+    pub const MVENDORID: u16 = 0xF11;
+    pub const MARCHID: u16 = 0xF12;
+    pub const MIMPID: u16 = 0xF13;
+    pub const MHARTID: u16 = 0xF14;
+}
+
+/// Fields of mstatus. sstatus is a restricted view of the same register.
+pub(crate) mod status {
+    pub const SIE: u64 = 1 << 1;
+    pub const MIE: u64 = 1 << 3;
+    pub const SPIE: u64 = 1 << 5;
+    pub const MPIE: u64 = 1 << 7;
+    pub const SPP: u64 = 1 << 8;
+    pub const MPP_SHIFT: u64 = 11;
+    pub const MPP: u64 = 0b11 << MPP_SHIFT;
+    /// Off (0), Initial, Clean or Dirty (all set).
+    pub const FS: u64 = 0b11 << 13;
+    pub const FS_INITIAL: u64 = 0b01 << 13;
+    pub const MPRV: u64 = 1 << 17;
+    pub const SUM: u64 = 1 << 18;
+    pub const MXR: u64 = 1 << 19;
+    pub const TVM: u64 = 1 << 20;
+    pub const TW: u64 = 1 << 21;
+    pub const TSR: u64 = 1 << 22;
+
+    pub const SSTATUS_MASK: u64 = SIE | SPIE | SPP | FS | SUM | MXR;
+    pub const MSTATUS_MASK: u64 = SSTATUS_MASK | MIE | MPIE | MPP | MPRV | TVM | TW | TSR;
+}
+
+/// Interrupt numbers, which are both the mcause code and the bit in mip/mie.
+pub(crate) mod interrupt {
+    pub const SSI: u64 = 1;
+    pub const MSI: u64 = 3;
+    pub const STI: u64 = 5;
+    pub const MTI: u64 = 7;
+    pub const SEI: u64 = 9;
+    pub const MEI: u64 = 11;
+
+    /// In the order they are taken when several are pending.
+    pub const PRIORITY: [u64; 6] = [MEI, MSI, MTI, SEI, SSI, STI];
+    /// Interrupts that can be delegated to S-mode.
+    pub const SUPERVISOR: u64 = 1 << SSI | 1 << STI | 1 << SEI;
+    pub const ALL: u64 = SUPERVISOR | 1 << MSI | 1 << MTI | 1 << MEI;
+}
+
+/* Exceptions that can be delegated to S-mode: everything but ecall from M-mode. */
+const DELEGABLE_EXCEPTIONS: u64 = 0xB3FF;
+
+pub(crate) struct Csrs {
+    /// Accrued floating point exception flags (NV, DZ, OF, UF, NX).
+    pub fflags: u8,
+    /// Dynamic floating point rounding mode.
+    pub frm: u8,
+
+    pub mstatus: u64,
+    pub medeleg: u64,
+    pub mideleg: u64,
+    pub mie: u64,
+    pub mip: u64,
+    pub mtvec: u64,
+    pub mscratch: u64,
+    pub mepc: u64,
+    pub mcause: u64,
+    pub mtval: u64,
+
+    pub stvec: u64,
+    pub sscratch: u64,
+    pub sepc: u64,
+    pub scause: u64,
+    pub stval: u64,
+    pub satp: u64,
+
+    pub pmp: Pmp,
+
+    xlen: Xlen,
+    misa: u64,
+}
+
+impl Csrs {
+    pub fn new(isa: &Isa) -> Self {
+        let mut csrs = Csrs {
+            fflags: 0,
+            frm: 0,
+            mstatus: 0,
+            medeleg: 0,
+            mideleg: 0,
+            mie: 0,
+            mip: 0,
+            mtvec: 0,
+            mscratch: 0,
+            mepc: 0,
+            mcause: 0,
+            mtval: 0,
+            stvec: 0,
+            sscratch: 0,
+            sepc: 0,
+            scause: 0,
+            stval: 0,
+            satp: 0,
+            pmp: Pmp::default(),
+            xlen: isa.xlen(),
+            misa: 0,
+        };
+        csrs.set_isa(isa);
+        // like a kernel starting a process, the FPU starts on. Programs may turn it off.
+        if isa.supports(Extension::F) {
+            csrs.mstatus |= status::FS_INITIAL;
+        }
+
+        csrs
+    }
+
+    /* misa reports the base width and every single letter extension, plus S and U. */
+    pub fn set_isa(&mut self, isa: &Isa) {
+        let letter = |c: char| 1 << (c as u8 - b'a');
+        let mxl = match isa.xlen() {
+            Xlen::Rv32 => 1,
+            Xlen::Rv64 => 2,
+        };
+        let base = if isa.is_embedded() {
+            letter('e')
+        } else {
+            letter('i')
+        };
+        let extensions = isa
+            .extensions()
+            .filter(|&extension| extension != Extension::I)
+            .map(|extension| extension.to_string())
+            .filter(|name| name.len() == 1)
+            .fold(base, |bits, name| {
+                bits | letter(name.chars().next().unwrap())
+            });
+
+        self.xlen = isa.xlen();
+        self.misa = mxl << (isa.xlen().bits() - 2) | extensions | letter('s') | letter('u');
+    }
+
+    /* The privilege level in the MPP field. The reserved value 2 is never stored. */
+    pub fn mpp(&self) -> Privilege {
+        Privilege::from_bits((self.mstatus & status::MPP) >> status::MPP_SHIFT)
+    }
+
+    pub fn set_mpp(&mut self, privilege: Privilege) {
+        self.mstatus = (self.mstatus & !status::MPP) | (privilege as u64) << status::MPP_SHIFT;
+    }
+
+    /* With mstatus.FS Off, F and D instructions and the fcsr CSRs are illegal. */
+    pub fn fpu_off(&self) -> bool {
+        self.mstatus & status::FS == 0
+    }
+
+    /* Sets mstatus.FS to Dirty, as the f registers or fcsr changed. */
+    pub fn set_fpu_dirty(&mut self) {
+        self.mstatus |= status::FS;
+    }
+
+    /* SD, the top bit of mstatus, is read-only and set when FS is Dirty. */
+    fn sd(&self) -> u64 {
+        if self.mstatus & status::FS == status::FS {
+            1 << (self.xlen.bits() - 1)
+        } else {
+            0
+        }
+    }
+
+    /* Checks the privilege encoded in the CSR address and that read-only CSRs are not written. */
+    pub fn check_access(
+        &self,
+        csr: u16,
+        privilege: Privilege,
+        write: bool,
+    ) -> Result<(), MachineError> {
+        let required = (csr >> 8) & 0b11;
+        let read_only = (csr >> 10) & 0b11 == 0b11;
+        let trapped_satp = csr == address::SATP
+            && privilege == Privilege::Supervisor
+            && self.mstatus & status::TVM != 0;
+
+        if (privilege as u16) < required || (write && read_only) || trapped_satp {
+            return Err(MachineError::IllegalCsrAccess(csr, privilege));
+        }
+
+        Ok(())
+    }
+
+    pub fn read(&self, csr: u16) -> Result<u64, MachineError> {
+        Ok(match csr {
+            address::FFLAGS => self.fflags as u64,
+            address::FRM => self.frm as u64,
+            address::FCSR => (self.frm as u64) << 5 | self.fflags as u64,
+
+            address::SSTATUS => self.mstatus & status::SSTATUS_MASK | self.sd(),
+            address::SIE => self.mie & self.mideleg,
+            address::STVEC => self.stvec,
+            address::SSCRATCH => self.sscratch,
+            address::SEPC => self.sepc,
+            address::SCAUSE => self.scause,
+            address::STVAL => self.stval,
+            address::SIP => self.mip & self.mideleg,
+            address::SATP => self.satp,
+
+            address::MSTATUS => self.mstatus | self.sd(),
+            address::MISA => self.misa,
+            address::MEDELEG => self.medeleg,
+            address::MIDELEG => self.mideleg,
+            address::MIE => self.mie,
+            address::MTVEC => self.mtvec,
+            address::MSCRATCH => self.mscratch,
+            address::MEPC => self.mepc,
+            address::MCAUSE => self.mcause,
+            address::MTVAL => self.mtval,
+            address::MIP => self.mip,
+            address::PMPCFG0..=address::PMPCFG3 => {
+                let index = self.pmpcfg_index(csr)?;
+                self.pmp.read_cfg(index, self.xlen)
+            }
+            address::PMPADDR0..=address::PMPADDR15 => {
+                self.pmp.read_addr((csr - address::PMPADDR0) as usize)
+            }
+
+            address::MVENDORID | address::MARCHID | address::MIMPID | address::MHARTID => 0,
+            _ => return Err(MachineError::UnsupportedCsr(csr)),
+        })
+    }
+
+    /* Writes ignore read-only bits and keep WARL fields legal. */
+    pub fn write(&mut self, csr: u16, value: u64) -> Result<(), MachineError> {
+        let value = self.xlen.zext(value as i64);
+        let sie_mask = self.mideleg & interrupt::SUPERVISOR;
+
+        match csr {
+            address::FFLAGS => {
+                self.fflags = (value & 0x1F) as u8;
+                self.set_fpu_dirty();
+            }
+            address::FRM => {
+                self.frm = (value & 0x7) as u8;
+                self.set_fpu_dirty();
+            }
+            address::FCSR => {
+                self.fflags = (value & 0x1F) as u8;
+                self.frm = ((value >> 5) & 0x7) as u8;
+                self.set_fpu_dirty();
+            }
+
+            address::SSTATUS => {
+                self.mstatus =
+                    (self.mstatus & !status::SSTATUS_MASK) | (value & status::SSTATUS_MASK)
+            }
+            address::SIE => self.mie = (self.mie & !sie_mask) | (value & sie_mask),
+            address::STVEC => self.stvec = value & !0b10,
+            address::SSCRATCH => self.sscratch = value,
+            address::SEPC => self.sepc = value & !0b11,
+            address::SCAUSE => self.scause = value,
+            address::STVAL => self.stval = value,
+            address::SIP => {
+                let mask = sie_mask & 1 << interrupt::SSI;
+                self.mip = (self.mip & !mask) | (value & mask)
+            }
+            address::SATP => self.write_satp(value),
+
+            address::MSTATUS => {
+                self.mstatus =
+                    (self.mstatus & !status::MSTATUS_MASK) | (value & status::MSTATUS_MASK);
+                if (self.mstatus & status::MPP) >> status::MPP_SHIFT == 0b10 {
+                    self.set_mpp(Privilege::User);
+                }
+            }
+            address::MISA => {}
+            address::MEDELEG => self.medeleg = value & DELEGABLE_EXCEPTIONS,
+            address::MIDELEG => self.mideleg = value & interrupt::SUPERVISOR,
+            address::MIE => self.mie = value & interrupt::ALL,
+            address::MTVEC => self.mtvec = value & !0b10,
+            address::MSCRATCH => self.mscratch = value,
+            address::MEPC => self.mepc = value & !0b11,
+            address::MCAUSE => self.mcause = value,
+            address::MTVAL => self.mtval = value,
+            address::MIP => {
+                let mask = interrupt::SUPERVISOR;
+                self.mip = (self.mip & !mask) | (value & mask)
+            }
+            address::PMPCFG0..=address::PMPCFG3 => {
+                let index = self.pmpcfg_index(csr)?;
+                self.pmp.write_cfg(index, self.xlen, value)
+            }
+            address::PMPADDR0..=address::PMPADDR15 => {
+                let entry = (csr - address::PMPADDR0) as usize;
+                self.pmp.write_addr(entry, self.xlen, value)
+            }
+
+            address::MVENDORID | address::MARCHID | address::MIMPID | address::MHARTID => {}
+            _ => return Err(MachineError::UnsupportedCsr(csr)),
+        }
+
+        Ok(())
+    }
+
+    /* The odd numbered pmpcfg registers only exist on RV32. */
+    fn pmpcfg_index(&self, csr: u16) -> Result<usize, MachineError> {
+        let index = (csr - address::PMPCFG0) as usize;
+        if self.xlen == Xlen::Rv64 && index % 2 == 1 {
+            return Err(MachineError::UnsupportedCsr(csr));
+        }
+
+        Ok(index)
+    }
+
+    /* Only Sv32 is implemented: on RV64 any mode other than Bare is ignored. */
+    fn write_satp(&mut self, value: u64) {
+        match self.xlen {
+            Xlen::Rv32 => self.satp = value,
+            Xlen::Rv64 if value >> 60 == 0 => self.satp = value,
+            Xlen::Rv64 => {}
+        }
+    }
+}
@@ -1,23 +1,73 @@
 use bilge::prelude::{Integer, u5};
 
 use crate::{
-    instruction::definitions::Op,
-    machine::{MachineError, Memory},
+    instruction::definitions::{Extension, Op},
+    isa::Xlen,
+    machine::{
+        Access, MachineError,
+        csr::{Csrs, address},
+        mmu::Bus,
+        trap::Privilege,
+    },
 };
 
+/* Rotates the low XLEN bits of value. */
+fn rotate_right(xlen: Xlen, value: i64, amount: u32) -> i64 {
+    match xlen {
+        Xlen::Rv32 => (value as u32).rotate_right(amount) as i32 as i64,
+        Xlen::Rv64 => (value as u64).rotate_right(amount) as i64,
+    }
+}
+
+fn rotate_left(xlen: Xlen, value: i64, amount: u32) -> i64 {
+    match xlen {
+        Xlen::Rv32 => (value as u32).rotate_left(amount) as i32 as i64,
+        Xlen::Rv64 => (value as u64).rotate_left(amount) as i64,
+    }
+}
+
+/* The sanitizer's check: whether op, computed on the full operands, overflows the signed
+ * range of its result width. The guest still gets the wrapped result. addi is left out,
+ * as it also builds constants after lui and adjusts pointers. */
+pub(crate) fn signed_overflow(op: &Op, xlen: Xlen, lhs: i64, rhs: i64) -> bool {
+    let (lhs, rhs) = (lhs as i128, rhs as i128);
+    let narrow = |value: i64| value as i32 as i128;
+    let (exact, bits) = match op {
+        Op::add => (lhs + rhs, xlen.bits()),
+        Op::sub => (lhs - rhs, xlen.bits()),
+        Op::mul => (lhs * rhs, xlen.bits()),
+        Op::div if rhs != 0 => (lhs / rhs, xlen.bits()),
+        Op::addw | Op::addiw => (narrow(lhs as i64) + narrow(rhs as i64), 32),
+        Op::subw => (narrow(lhs as i64) - narrow(rhs as i64), 32),
+        Op::mulw => (narrow(lhs as i64) * narrow(rhs as i64), 32),
+        Op::divw if narrow(rhs as i64) != 0 => (narrow(lhs as i64) / narrow(rhs as i64), 32),
+        _ => return false,
+    };
+
+    let unused = 128 - bits;
+    (exact << unused) >> unused != exact
+}
+
+/* Results only need to be correct in the low XLEN bits, the machine sign-extends rd. */
 pub(crate) fn execute_rtype(
     op: &Op,
-    rd: &mut i32,
-    rs1: i32,
-    rs2: i32,
-) -> Result<Option<u32>, MachineError> {
+    xlen: Xlen,
+    rd: &mut i64,
+    rs1: i64,
+    rs2: i64,
+) -> Result<Option<u64>, MachineError> {
+    if matches!(op.extension(), Extension::M | Extension::Zmmul) {
+        execute_muldiv(op, xlen, rd, rs1, rs2);
+        return Ok(None);
+    }
+
     Ok(match op {
         Op::add => {
-            *rd = rs1 + rs2;
+            *rd = rs1.wrapping_add(rs2);
             None
         }
         Op::sub => {
-            *rd = rs1 - rs2;
+            *rd = rs1.wrapping_sub(rs2);
             None
         }
         Op::xor => {
@@ -33,15 +83,15 @@ pub(crate) fn execute_rtype(
             None
         }
         Op::sll => {
-            *rd = rs1 << rs2;
+            *rd = rs1 << xlen.shamt(rs2);
             None
         }
         Op::srl => {
-            *rd = (rs1 as u32 >> rs2) as i32;
+            *rd = (xlen.zext(rs1) >> xlen.shamt(rs2)) as i64;
             None
         }
         Op::sra => {
-            *rd = rs1 >> rs2;
+            *rd = rs1 >> xlen.shamt(rs2);
             None
         }
         Op::slt => {
@@ -49,24 +99,180 @@ pub(crate) fn execute_rtype(
             None
         }
         Op::sltu => {
-            *rd = if (rs1 as u32) < (rs2 as u32) { 1 } else { 0 };
+            *rd = if (rs1 as u64) < (rs2 as u64) { 1 } else { 0 };
+            None
+        }
+        Op::addw => {
+            *rd = (rs1 as i32).wrapping_add(rs2 as i32) as i64;
+            None
+        }
+        Op::subw => {
+            *rd = (rs1 as i32).wrapping_sub(rs2 as i32) as i64;
+            None
+        }
+        Op::sllw => {
+            *rd = ((rs1 as i32) << (rs2 & 0x1F)) as i64;
+            None
+        }
+        Op::srlw => {
+            *rd = ((rs1 as u32) >> (rs2 & 0x1F)) as i32 as i64;
+            None
+        }
+        Op::sraw => {
+            *rd = ((rs1 as i32) >> (rs2 & 0x1F)) as i64;
+            None
+        }
+        Op::sh1add => {
+            *rd = (rs1 << 1).wrapping_add(rs2);
+            None
+        }
+        Op::sh2add => {
+            *rd = (rs1 << 2).wrapping_add(rs2);
+            None
+        }
+        Op::sh3add => {
+            *rd = (rs1 << 3).wrapping_add(rs2);
+            None
+        }
+        Op::add_uw => {
+            *rd = (rs1 as u32 as i64).wrapping_add(rs2);
+            None
+        }
+        Op::sh1add_uw => {
+            *rd = ((rs1 as u32 as i64) << 1).wrapping_add(rs2);
+            None
+        }
+        Op::sh2add_uw => {
+            *rd = ((rs1 as u32 as i64) << 2).wrapping_add(rs2);
+            None
+        }
+        Op::sh3add_uw => {
+            *rd = ((rs1 as u32 as i64) << 3).wrapping_add(rs2);
+            None
+        }
+        Op::andn => {
+            *rd = rs1 & !rs2;
+            None
+        }
+        Op::orn => {
+            *rd = rs1 | !rs2;
+            None
+        }
+        Op::xnor => {
+            *rd = !(rs1 ^ rs2);
+            None
+        }
+        // registers hold sign-extended values, so comparing them as u64 orders them as XLEN-bit unsigned.
+        Op::min => {
+            *rd = rs1.min(rs2);
+            None
+        }
+        Op::minu => {
+            *rd = (rs1 as u64).min(rs2 as u64) as i64;
+            None
+        }
+        Op::max => {
+            *rd = rs1.max(rs2);
+            None
+        }
+        Op::maxu => {
+            *rd = (rs1 as u64).max(rs2 as u64) as i64;
+            None
+        }
+        Op::rol => {
+            *rd = rotate_left(xlen, rs1, xlen.shamt(rs2));
+            None
+        }
+        Op::ror => {
+            *rd = rotate_right(xlen, rs1, xlen.shamt(rs2));
+            None
+        }
+        Op::rolw => {
+            *rd = rotate_left(Xlen::Rv32, rs1, Xlen::Rv32.shamt(rs2));
+            None
+        }
+        Op::rorw => {
+            *rd = rotate_right(Xlen::Rv32, rs1, Xlen::Rv32.shamt(rs2));
+            None
+        }
+        Op::zext_h => {
+            *rd = rs1 & 0xFFFF;
+            None
+        }
+        Op::bclr => {
+            *rd = rs1 & !(1 << xlen.shamt(rs2));
+            None
+        }
+        Op::bext => {
+            *rd = (rs1 >> xlen.shamt(rs2)) & 1;
+            None
+        }
+        Op::binv => {
+            *rd = rs1 ^ (1 << xlen.shamt(rs2));
+            None
+        }
+        Op::bset => {
+            *rd = rs1 | (1 << xlen.shamt(rs2));
             None
         }
         _ => panic!("executing operation {} as a R-type.", op),
     })
 }
 
+/* Multiplication, division and remainder of M and Zmmul. Division by zero and overflow
+ * do not trap, they produce fixed results. */
+fn execute_muldiv(op: &Op, xlen: Xlen, rd: &mut i64, rs1: i64, rs2: i64) {
+    *rd = match op {
+        Op::mul => rs1.wrapping_mul(rs2),
+        Op::mulh => ((rs1 as i128 * rs2 as i128) >> xlen.bits()) as i64,
+        Op::mulhsu => ((rs1 as i128 * xlen.zext(rs2) as i128) >> xlen.bits()) as i64,
+        Op::mulhu => ((xlen.zext(rs1) as u128 * xlen.zext(rs2) as u128) >> xlen.bits()) as i64,
+        Op::div if rs2 == 0 => -1,
+        Op::div => rs1.wrapping_div(rs2),
+        Op::divu if rs2 == 0 => -1,
+        Op::divu => (xlen.zext(rs1) / xlen.zext(rs2)) as i64,
+        Op::rem if rs2 == 0 => rs1,
+        Op::rem => rs1.wrapping_rem(rs2),
+        Op::remu if rs2 == 0 => rs1,
+        Op::remu => (xlen.zext(rs1) % xlen.zext(rs2)) as i64,
+        Op::mulw => (rs1 as i32).wrapping_mul(rs2 as i32) as i64,
+        Op::divw if rs2 as i32 == 0 => -1,
+        Op::divw => (rs1 as i32).wrapping_div(rs2 as i32) as i64,
+        Op::divuw if rs2 as u32 == 0 => -1,
+        Op::divuw => ((rs1 as u32) / (rs2 as u32)) as i32 as i64,
+        Op::remw if rs2 as i32 == 0 => rs1 as i32 as i64,
+        Op::remw => (rs1 as i32).wrapping_rem(rs2 as i32) as i64,
+        Op::remuw if rs2 as u32 == 0 => rs1 as i32 as i64,
+        Op::remuw => ((rs1 as u32) % (rs2 as u32)) as i32 as i64,
+        _ => panic!(
+            "executing operation {} as a multiplication or division.",
+            op
+        ),
+    };
+}
+
+/* Jumps and taken branches must target a four byte boundary. The exception is raised
+ * by the jump itself, before it writes rd. */
+fn jump(addr: u64) -> Result<Option<u64>, MachineError> {
+    if !addr.is_multiple_of(4) {
+        return Err(MachineError::InstructionAddressMisaligned(addr));
+    }
+
+    Ok(Some(addr))
+}
+
 pub(crate) fn execute_itype(
     op: &Op,
-    pc: u32,
-    rd: &mut i32,
-    rs1: i32,
-    imm: i32,
-    memory: &Memory,
-) -> Result<Option<u32>, MachineError> {
+    xlen: Xlen,
+    pc: u64,
+    rd: &mut i64,
+    rs1: i64,
+    imm: i64,
+    memory: &mut Bus,
+) -> Result<Option<u64>, MachineError> {
     Ok(match op {
         Op::addi => {
-            *rd = rs1 + imm;
+            *rd = rs1.wrapping_add(imm);
             None
         }
         Op::xori => {
@@ -82,18 +288,15 @@ pub(crate) fn execute_itype(
             None
         }
         Op::slli => {
-            let reduced_imm = u5::extract_u32(imm as u32, 0).as_i32();
-            *rd = rs1 << reduced_imm;
+            *rd = rs1 << xlen.shamt(imm);
             None
         }
         Op::srli => {
-            let reduced_imm = u5::extract_u32(imm as u32, 0).as_i32();
-            *rd = ((rs1 as u32) >> reduced_imm) as i32;
+            *rd = (xlen.zext(rs1) >> xlen.shamt(imm)) as i64;
             None
         }
         Op::srai => {
-            let reduced_imm = u5::extract_u32(imm as u32, 0).as_i32();
-            *rd = rs1 >> reduced_imm;
+            *rd = rs1 >> xlen.shamt(imm);
             None
         }
         Op::slti => {
@@ -101,110 +304,185 @@ pub(crate) fn execute_itype(
             None
         }
         Op::sltiu => {
-            *rd = if (rs1 as u32) < (imm as u32) { 1 } else { 0 };
+            *rd = if (rs1 as u64) < (imm as u64) { 1 } else { 0 };
+            None
+        }
+        Op::addiw => {
+            *rd = (rs1 as i32).wrapping_add(imm as i32) as i64;
+            None
+        }
+        Op::slliw => {
+            *rd = ((rs1 as i32) << Xlen::Rv32.shamt(imm)) as i64;
+            None
+        }
+        Op::srliw => {
+            *rd = ((rs1 as u32) >> Xlen::Rv32.shamt(imm)) as i32 as i64;
+            None
+        }
+        Op::sraiw => {
+            *rd = ((rs1 as i32) >> Xlen::Rv32.shamt(imm)) as i64;
+            None
+        }
+        Op::slli_uw => {
+            *rd = (rs1 as u32 as i64) << xlen.shamt(imm);
+            None
+        }
+        Op::clz => {
+            *rd = (xlen.zext(rs1).leading_zeros() - (64 - xlen.bits())) as i64;
+            None
+        }
+        Op::ctz => {
+            *rd = xlen.zext(rs1).trailing_zeros().min(xlen.bits()) as i64;
+            None
+        }
+        Op::cpop => {
+            *rd = xlen.zext(rs1).count_ones() as i64;
+            None
+        }
+        Op::clzw => {
+            *rd = (rs1 as u32).leading_zeros() as i64;
+            None
+        }
+        Op::ctzw => {
+            *rd = (rs1 as u32).trailing_zeros() as i64;
+            None
+        }
+        Op::cpopw => {
+            *rd = (rs1 as u32).count_ones() as i64;
+            None
+        }
+        Op::sext_b => {
+            *rd = rs1 as i8 as i64;
+            None
+        }
+        Op::sext_h => {
+            *rd = rs1 as i16 as i64;
+            None
+        }
+        Op::orc_b => {
+            let bytes = rs1
+                .to_le_bytes()
+                .map(|byte| if byte != 0 { 0xFF } else { 0 });
+            *rd = i64::from_le_bytes(bytes);
+            None
+        }
+        Op::rev8 => {
+            *rd = match xlen {
+                Xlen::Rv32 => (rs1 as i32).swap_bytes() as i64,
+                Xlen::Rv64 => rs1.swap_bytes(),
+            };
+            None
+        }
+        Op::rori => {
+            *rd = rotate_right(xlen, rs1, xlen.shamt(imm));
+            None
+        }
+        Op::roriw => {
+            *rd = rotate_right(Xlen::Rv32, rs1, Xlen::Rv32.shamt(imm));
+            None
+        }
+        Op::bclri => {
+            *rd = rs1 & !(1 << xlen.shamt(imm));
+            None
+        }
+        Op::bexti => {
+            *rd = (rs1 >> xlen.shamt(imm)) & 1;
+            None
+        }
+        Op::binvi => {
+            *rd = rs1 ^ (1 << xlen.shamt(imm));
+            None
+        }
+        Op::bseti => {
+            *rd = rs1 | (1 << xlen.shamt(imm));
             None
         }
         Op::lb => {
-            let addr = rs1 + imm;
-            let byte = memory
-                .get(addr as usize)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            *rd = *byte as i8 as i32;
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            let bytes = memory.load::<1>(addr)?;
+            *rd = i8::from_le_bytes(bytes) as i64;
             None
         }
         Op::lh => {
-            let addr = rs1 + imm;
-            let b1 = memory
-                .get(addr as usize)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            let b2 = memory
-                .get(addr as usize + 1)
-                .ok_or(MachineError::MemoryError(addr as u32 + 1))?;
-            let value = i16::from_le_bytes([*b1, *b2]);
-            *rd = value as i32;
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            let bytes = memory.load::<2>(addr)?;
+            *rd = i16::from_le_bytes(bytes) as i64;
             None
         }
         Op::lw => {
-            let addr = rs1 + imm;
-            let b1 = memory
-                .get(addr as usize)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            let b2 = memory
-                .get(addr as usize + 1)
-                .ok_or(MachineError::MemoryError(addr as u32 + 1))?;
-            let b3 = memory
-                .get(addr as usize + 2)
-                .ok_or(MachineError::MemoryError(addr as u32 + 2))?;
-            let b4 = memory
-                .get(addr as usize + 3)
-                .ok_or(MachineError::MemoryError(addr as u32 + 3))?;
-            let value = i32::from_le_bytes([*b1, *b2, *b3, *b4]);
-            *rd = value;
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            let bytes = memory.load::<4>(addr)?;
+            *rd = i32::from_le_bytes(bytes) as i64;
+            None
+        }
+        Op::ld => {
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            let bytes = memory.load::<8>(addr)?;
+            *rd = i64::from_le_bytes(bytes);
             None
         }
         Op::lbu => {
-            let addr = rs1 + imm;
-            let byte = memory
-                .get(addr as usize)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            *rd = *byte as i32;
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            let bytes = memory.load::<1>(addr)?;
+            *rd = u8::from_le_bytes(bytes) as i64;
             None
         }
         Op::lhu => {
-            let addr = rs1 + imm;
-            let b1 = memory
-                .get(addr as usize)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            let b2 = memory
-                .get(addr as usize + 1)
-                .ok_or(MachineError::MemoryError(addr as u32 + 1))?;
-            let value = u16::from_le_bytes([*b1, *b2]);
-            *rd = value as i32;
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            let bytes = memory.load::<2>(addr)?;
+            *rd = u16::from_le_bytes(bytes) as i64;
             None
         }
-        Op::jalr => {
-            *rd = (pc + 4) as i32;
-            let addr = rs1 + imm;
-            Some(addr as u32)
+        Op::lwu => {
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            let bytes = memory.load::<4>(addr)?;
+            *rd = u32::from_le_bytes(bytes) as i64;
+            None
         }
-        Op::ecall => unimplemented!("operation ecall is not implemented"),
-        Op::ebreak => unimplemented!("operation ebreak is not implemented"),
+        Op::jalr => {
+            // the lowest bit of the target is cleared.
+            let addr = rs1.wrapping_add(imm) & !1;
+            let target = jump(addr as u64)?;
+            *rd = pc.wrapping_add(4) as i64;
+            target
+        }
+        // a single in-order hart observes its own memory accesses in order.
+        Op::fence | Op::fence_tso | Op::pause => None,
+        // the decoded-instruction cache is flushed by the machine.
+        Op::fence_i => None,
+        Op::ecall => return Err(MachineError::EnvironmentCall(pc)),
+        Op::ebreak => return Err(MachineError::Breakpoint(pc)),
         _ => panic!("executing operation {} as a I-type.", op),
     })
 }
 
 pub(crate) fn execute_stype(
     op: &Op,
-    rs1: i32,
-    rs2: i32,
-    imm: i32,
-    memory: &mut Memory,
-) -> Result<Option<u32>, MachineError> {
+    xlen: Xlen,
+    rs1: i64,
+    rs2: i64,
+    imm: i64,
+    memory: &mut Bus,
+) -> Result<Option<u64>, MachineError> {
     Ok(match op {
         Op::sb => {
-            let addr = rs1 + imm;
-            let value = memory
-                .get_mut(addr as usize)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            *value = rs2 as u8;
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            memory.store(addr, &(rs2 as u8).to_le_bytes())?;
             None
         }
         Op::sh => {
-            let addr = (rs1 + imm) as usize;
-            let slice = memory
-                .get_mut(addr..addr + 2)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            slice.copy_from_slice(&(rs2 as i16).to_le_bytes());
-
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            memory.store(addr, &(rs2 as i16).to_le_bytes())?;
             None
         }
         Op::sw => {
-            let addr = (rs1 + imm) as usize;
-            let slice = memory
-                .get_mut(addr..addr + 4)
-                .ok_or(MachineError::MemoryError(addr as u32))?;
-            slice.copy_from_slice(&rs2.to_le_bytes());
-
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            memory.store(addr, &(rs2 as i32).to_le_bytes())?;
+            None
+        }
+        Op::sd => {
+            let addr = xlen.zext(rs1.wrapping_add(imm));
+            memory.store(addr, &rs2.to_le_bytes())?;
             None
         }
         _ => panic!("executing operation {} as a S-type.", op),
@@ -213,56 +491,50 @@ pub(crate) fn execute_stype(
 
 pub(crate) fn execute_btype(
     op: &Op,
-    pc: u32,
-    rs1: i32,
-    rs2: i32,
-    imm: i32,
-) -> Result<Option<u32>, MachineError> {
+    pc: u64,
+    rs1: i64,
+    rs2: i64,
+    imm: i64,
+) -> Result<Option<u64>, MachineError> {
     Ok(match op {
         Op::beq => {
             if rs1 == rs2 {
-                let addr = pc as i32 + imm;
-                Some(addr as u32)
+                jump(pc.wrapping_add_signed(imm))?
             } else {
                 None
             }
         }
         Op::bne => {
             if rs1 != rs2 {
-                let addr = pc as i32 + imm;
-                Some(addr as u32)
+                jump(pc.wrapping_add_signed(imm))?
             } else {
                 None
             }
         }
         Op::blt => {
             if rs1 < rs2 {
-                let addr = pc as i32 + imm;
-                Some(addr as u32)
+                jump(pc.wrapping_add_signed(imm))?
             } else {
                 None
             }
         }
         Op::bge => {
             if rs1 >= rs2 {
-                let addr = pc as i32 + imm;
-                Some(addr as u32)
+                jump(pc.wrapping_add_signed(imm))?
             } else {
                 None
             }
         }
         Op::bltu => {
-            if (rs1 as u32) < (rs2 as u32) {
-                let addr = pc as i32 + imm;
-                Some(addr as u32)
+            if (rs1 as u64) < (rs2 as u64) {
+                jump(pc.wrapping_add_signed(imm))?
             } else {
                 None
             }
         }
         Op::bgeu => {
-            if (rs1 as u32) >= (rs2 as u32) {
-                let addr = pc as i32 + imm;
-                Some(addr as u32)
+            if (rs1 as u64) >= (rs2 as u64) {
+                jump(pc.wrapping_add_signed(imm))?
             } else {
                 None
             }
@@ -273,15 +545,15 @@ pub(crate) fn execute_btype(
 
 pub(crate) fn execute_jtype(
     op: &Op,
-    pc: u32,
-    rd: &mut i32,
-    imm: i32,
-) -> Result<Option<u32>, MachineError> {
+    pc: u64,
+    rd: &mut i64,
+    imm: i64,
+) -> Result<Option<u64>, MachineError> {
     Ok(match op {
         Op::jal => {
-            *rd = (pc + 4) as i32;
-            let addr = pc as i32 + imm;
-            Some(addr as u32)
+            let target = jump(pc.wrapping_add_signed(imm))?;
+            *rd = pc.wrapping_add(4) as i64;
+            target
         }
         _ => panic!("executing operation {} as a B-type.", op),
     })
@@ -289,19 +561,138 @@ pub(crate) fn execute_jtype(
 
 pub(crate) fn execute_utype(
     op: &Op,
-    pc: u32,
-    rd: &mut i32,
-    imm: i32,
-) -> Result<Option<u32>, MachineError> {
+    pc: u64,
+    rd: &mut i64,
+    imm: i64,
+) -> Result<Option<u64>, MachineError> {
+    // the 20-bit immediate fills bits 31:12 and is sign-extended from bit 31.
+    let upper = (imm << 12) as i32 as i64;
+
     Ok(match op {
         Op::lui => {
-            *rd = imm << 12;
+            *rd = upper;
             None
         }
         Op::auipc => {
-            *rd = (pc as i32) + (imm << 12);
+            *rd = (pc as i64).wrapping_add(upper);
             None
         }
         _ => panic!("executing operation {} as a U-type.", op),
     })
 }
+
+pub(crate) fn execute_atype(
+    op: &Op,
+    xlen: Xlen,
+    rd: &mut i64,
+    rs1: i64,
+    rs2: i64,
+    memory: &mut Bus,
+) -> Result<Option<u64>, MachineError> {
+    let addr = xlen.zext(rs1);
+    let doubleword = matches!(
+        op,
+        Op::lr_d
+            | Op::sc_d
+            | Op::amoswap_d
+            | Op::amoadd_d
+            | Op::amoxor_d
+            | Op::amoand_d
+            | Op::amoor_d
+            | Op::amomin_d
+            | Op::amomax_d
+            | Op::amominu_d
+            | Op::amomaxu_d
+    );
+    let width = if doubleword { 8 } else { 4 };
+    // word operations only see the low 32 bits of rs2, sign-extended.
+    let rs2 = if doubleword { rs2 } else { rs2 as i32 as i64 };
+    let load = |memory: &mut Bus| -> Result<i64, MachineError> {
+        Ok(if doubleword {
+            i64::from_le_bytes(memory.load::<8>(addr)?)
+        } else {
+            i32::from_le_bytes(memory.load::<4>(addr)?) as i64
+        })
+    };
+
+    match op {
+        Op::lr_w | Op::lr_d => {
+            if !addr.is_multiple_of(width as u64) {
+                return Err(MachineError::LoadAddressMisaligned(addr));
+            }
+            *rd = load(memory)?;
+            memory.reserve(addr, width)?;
+            return Ok(None);
+        }
+        Op::sc_w | Op::sc_d => {
+            if !addr.is_multiple_of(width as u64) {
+                return Err(MachineError::StoreAddressMisaligned(addr));
+            }
+            let stored = memory.store_conditional(addr, &rs2.to_le_bytes()[..width])?;
+            *rd = if stored { 0 } else { 1 };
+            return Ok(None);
+        }
+        _ => {}
+    }
+
+    if !addr.is_multiple_of(width as u64) {
+        return Err(MachineError::StoreAddressMisaligned(addr));
+    }
+
+    // AMOs raise store faults, even when the load is what fails.
+    memory.translate_range(addr, width, Access::Write)?;
+    let value = load(memory)?;
+    let result = match op {
+        Op::amoswap_w | Op::amoswap_d => rs2,
+        Op::amoadd_w | Op::amoadd_d => value.wrapping_add(rs2),
+        Op::amoxor_w | Op::amoxor_d => value ^ rs2,
+        Op::amoand_w | Op::amoand_d => value & rs2,
+        Op::amoor_w | Op::amoor_d => value | rs2,
+        Op::amomin_w | Op::amomin_d => value.min(rs2),
+        Op::amomax_w | Op::amomax_d => value.max(rs2),
+        Op::amominu_w | Op::amominu_d => (value as u64).min(rs2 as u64) as i64,
+        Op::amomaxu_w | Op::amomaxu_d => (value as u64).max(rs2 as u64) as i64,
+        _ => panic!("executing operation {} as a A-type.", op),
+    };
+    memory.store(addr, &result.to_le_bytes()[..width])?;
+    *rd = value;
+
+    Ok(None)
+}
+
+pub(crate) fn execute_csr(
+    op: &Op,
+    rd: &mut i64,
+    rs1_index: u5,
+    rs1: i64,
+    csr: u16,
+    privilege: Privilege,
+    csrs: &mut Csrs,
+) -> Result<Option<u64>, MachineError> {
+    // csrrs/csrrc with x0 (or a zero immediate) only read the CSR.
+    let writes = rs1_index.value() != 0 || matches!(op, Op::csrrw | Op::csrrwi);
+    csrs.check_access(csr, privilege, writes)?;
+    if matches!(csr, address::FFLAGS | address::FRM | address::FCSR) && csrs.fpu_off() {
+        return Err(MachineError::FloatingPointOff(*op));
+    }
+
+    let old = csrs.read(csr)?;
+    let source = match op {
+        Op::csrrwi | Op::csrrsi | Op::csrrci => rs1_index.as_u64(),
+        _ => rs1 as u64,
+    };
+
+    let new = match op {
+        Op::csrrw | Op::csrrwi => Some(source),
+        Op::csrrs | Op::csrrsi => writes.then_some(old | source),
+        Op::csrrc | Op::csrrci => writes.then_some(old & !source),
+        _ => panic!("executing operation {} as a CSR operation.", op),
+    };
+
+    if let Some(value) = new {
+        csrs.write(csr, value)?;
+    }
+    *rd = old as i64;
+
+    Ok(None)
+}
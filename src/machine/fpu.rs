@@ -0,0 +1,193 @@
+use bilge::prelude::{Integer, u5};
+
+use crate::{
+    instruction::{
+        definitions::{Extension, Op},
+        formats::InstructionFormat,
+    },
+    isa::Xlen,
+    machine::{
+        MachineError,
+        csr::Csrs,
+        mmu::Bus,
+        softfloat::{F32, F64, FloatFormat, RoundingMode},
+    },
+};
+
+/// Upper half of a single precision value held in a 64-bit f register.
+const NAN_BOX: u64 = 0xFFFF_FFFF_0000_0000;
+
+pub(crate) type FloatRegisters = [u64; 32];
+
+/* A single precision operand that is not properly NaN-boxed reads as the canonical NaN. */
+fn unbox_single(bits: u64) -> u64 {
+    if bits & NAN_BOX == NAN_BOX {
+        bits & 0xFFFF_FFFF
+    } else {
+        F32.canonical_nan()
+    }
+}
+
+fn box_single(bits: u64) -> u64 {
+    NAN_BOX | bits
+}
+
+/* Reads an f register as a value of the given format. */
+fn read(fmt: FloatFormat, bits: u64) -> u64 {
+    if fmt == F32 { unbox_single(bits) } else { bits }
+}
+
+/* Produces the f register contents holding a value of the given format. */
+fn write(fmt: FloatFormat, bits: u64) -> u64 {
+    if fmt == F32 { box_single(bits) } else { bits }
+}
+
+fn rounding_mode(rm: u8, csrs: &Csrs) -> Result<RoundingMode, MachineError> {
+    let bits = if rm == 0b111 { csrs.frm } else { rm };
+    RoundingMode::from_bits(bits).ok_or(MachineError::InvalidRoundingMode(bits))
+}
+
+pub(crate) fn execute_float(
+    op: &Op,
+    format: &InstructionFormat,
+    xlen: Xlen,
+    x: &mut [i64; 32],
+    f: &mut FloatRegisters,
+    csrs: &mut Csrs,
+    memory: &mut Bus,
+) -> Result<Option<u64>, MachineError> {
+    if csrs.fpu_off() {
+        return Err(MachineError::FloatingPointOff(*op));
+    }
+
+    let index = |field: Option<u5>| field.map_or(0, |reg| reg.as_usize());
+    let rd = index(format.rd());
+    let rs1 = index(format.rs1());
+    let rs2 = index(format.rs2());
+    let rs3 = index(format.rs3());
+    let mode = rounding_mode(format.funct3().map_or(0, |rm| rm.value()), csrs);
+
+    let fmt = if op.extension() == Extension::D {
+        F64
+    } else {
+        F32
+    };
+    let (a, b, c) = (read(fmt, f[rs1]), read(fmt, f[rs2]), read(fmt, f[rs3]));
+    let sign = fmt.sign_bit();
+
+    let result = match op {
+        Op::flw | Op::fld => {
+            let imm = format
+                .immediate_value()
+                .expect("I-type should have an immediate value");
+            let addr = xlen.zext(x[rs1].wrapping_add(imm as i64));
+            f[rd] = match op {
+                Op::flw => box_single(u32::from_le_bytes(memory.load::<4>(addr)?) as u64),
+                _ => u64::from_le_bytes(memory.load::<8>(addr)?),
+            };
+            None
+        }
+        Op::fsw | Op::fsd => {
+            let imm = format
+                .immediate_value()
+                .expect("S-type should have an immediate value");
+            let addr = xlen.zext(x[rs1].wrapping_add(imm as i64));
+            match op {
+                Op::fsw => memory.store(addr, &(f[rs2] as u32).to_le_bytes())?,
+                _ => memory.store(addr, &f[rs2].to_le_bytes())?,
+            }
+            None
+        }
+        Op::fmadd_s | Op::fmadd_d => Some(fmt.fused_mul_add(a, b, c, false, false, mode?)),
+        Op::fmsub_s | Op::fmsub_d => Some(fmt.fused_mul_add(a, b, c, false, true, mode?)),
+        Op::fnmsub_s | Op::fnmsub_d => Some(fmt.fused_mul_add(a, b, c, true, false, mode?)),
+        Op::fnmadd_s | Op::fnmadd_d => Some(fmt.fused_mul_add(a, b, c, true, true, mode?)),
+        Op::fadd_s | Op::fadd_d => Some(fmt.add(a, b, mode?)),
+        Op::fsub_s | Op::fsub_d => Some(fmt.sub(a, b, mode?)),
+        Op::fmul_s | Op::fmul_d => Some(fmt.mul(a, b, mode?)),
+        Op::fdiv_s | Op::fdiv_d => Some(fmt.div(a, b, mode?)),
+        Op::fsqrt_s | Op::fsqrt_d => Some(fmt.sqrt(a, mode?)),
+        Op::fsgnj_s | Op::fsgnj_d => Some(((a & !sign) | (b & sign), 0)),
+        Op::fsgnjn_s | Op::fsgnjn_d => Some(((a & !sign) | (!b & sign), 0)),
+        Op::fsgnjx_s | Op::fsgnjx_d => Some((a ^ (b & sign), 0)),
+        Op::fmin_s | Op::fmin_d => Some(fmt.min_max(a, b, false)),
+        Op::fmax_s | Op::fmax_d => Some(fmt.min_max(a, b, true)),
+        Op::fcvt_s_d => {
+            let (bits, flags) = F32.convert(F64, f[rs1], mode?);
+            f[rd] = box_single(bits);
+            csrs.fflags |= flags;
+            None
+        }
+        Op::fcvt_d_s => {
+            let (bits, flags) = F64.convert(F32, unbox_single(f[rs1]), mode?);
+            f[rd] = bits;
+            csrs.fflags |= flags;
+            None
+        }
+        Op::fcvt_w_s | Op::fcvt_wu_s | Op::fcvt_w_d | Op::fcvt_wu_d => {
+            let signed = matches!(op, Op::fcvt_w_s | Op::fcvt_w_d);
+            let (value, flags) = fmt.float_to_int(a, signed, 32, mode?);
+            // 32-bit results are sign-extended on RV64, even the unsigned ones.
+            x[rd] = value as i32 as i64;
+            csrs.fflags |= flags;
+            None
+        }
+        Op::fcvt_l_s | Op::fcvt_lu_s | Op::fcvt_l_d | Op::fcvt_lu_d => {
+            let signed = matches!(op, Op::fcvt_l_s | Op::fcvt_l_d);
+            let (value, flags) = fmt.float_to_int(a, signed, 64, mode?);
+            x[rd] = value as i64;
+            csrs.fflags |= flags;
+            None
+        }
+        Op::fcvt_s_w | Op::fcvt_d_w => Some(fmt.int_to_float(x[rs1] as i32 as i128, mode?)),
+        Op::fcvt_s_wu | Op::fcvt_d_wu => Some(fmt.int_to_float(x[rs1] as u32 as i128, mode?)),
+        Op::fcvt_s_l | Op::fcvt_d_l => Some(fmt.int_to_float(x[rs1] as i128, mode?)),
+        Op::fcvt_s_lu | Op::fcvt_d_lu => Some(fmt.int_to_float(x[rs1] as u64 as i128, mode?)),
+        Op::fmv_x_w => {
+            x[rd] = f[rs1] as u32 as i32 as i64;
+            None
+        }
+        Op::fmv_w_x => {
+            f[rd] = box_single(x[rs1] as u32 as u64);
+            None
+        }
+        Op::fmv_x_d => {
+            x[rd] = f[rs1] as i64;
+            None
+        }
+        Op::fmv_d_x => {
+            f[rd] = x[rs1] as u64;
+            None
+        }
+        Op::fclass_s | Op::fclass_d => {
+            x[rd] = fmt.class_mask(a) as i64;
+            None
+        }
+        Op::feq_s | Op::flt_s | Op::fle_s | Op::feq_d | Op::flt_d | Op::fle_d => {
+            let (result, flags) = match op {
+                Op::feq_s | Op::feq_d => fmt.eq(a, b),
+                Op::flt_s | Op::flt_d => fmt.lt(a, b),
+                _ => fmt.le(a, b),
+            };
+            x[rd] = result as i64;
+            csrs.fflags |= flags;
+            None
+        }
+        _ => panic!("executing operation {} as a floating point operation.", op),
+    };
+
+    if let Some((bits, flags)) = result {
+        f[rd] = write(fmt, bits);
+        csrs.fflags |= flags;
+    }
+    // everything else writes an f register or accrues flags in fcsr.
+    let reads_only = matches!(
+        op,
+        Op::fsw | Op::fsd | Op::fmv_x_w | Op::fmv_x_d | Op::fclass_s | Op::fclass_d
+    );
+    if !reads_only {
+        csrs.set_fpu_dirty();
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, io::Write};
+
+use crate::machine::{MachineError, memory::Memory};
+
+/* Syscall numbers of the proxy, the same as Linux on RISC-V. */
+const SYS_WRITE: u64 = 64;
+const SYS_EXIT: u64 = 93;
+const ENOSYS: i64 = 38;
+
+/// What the host did for a command, as seen by the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Response {
+    /// Nothing to do, or the command was handled.
+    Continue,
+    /// The program exited with a code.
+    Exit(u64),
+}
+
+/// The host-target interface of Spike: the program writes a command to `tohost`, and
+/// the host answers through `fromhost`. A command is 8 bytes: the device in bits 63:56,
+/// the command in bits 55:48 and a payload in bits 47:0.
+pub(crate) struct Htif {
+    tohost: u64,
+    fromhost: Option<u64>,
+    /// tohost as last polled. A command is handled once the hart moves on from writing
+    /// it, as RV32 programs store it as two words.
+    last: u64,
+}
+
+impl Htif {
+    pub fn new(tohost: u64, fromhost: Option<u64>) -> Self {
+        Htif {
+            tohost,
+            fromhost,
+            last: 0,
+        }
+    }
+
+    /* Programs that define a tohost symbol talk to the host through it. */
+    pub fn from_symbols(symbols: &HashMap<String, u64>) -> Option<Self> {
+        let tohost = *symbols.get("tohost")?;
+
+        Some(Htif::new(tohost, symbols.get("fromhost").copied()))
+    }
+
+    /* Forgets a command half written before the machine was reset. */
+    pub fn reset(&mut self) {
+        self.last = 0;
+    }
+
+    fn read_u64(memory: &Memory, addr: u64) -> Result<u64, MachineError> {
+        Ok(u64::from_le_bytes(memory.load::<8>(addr)?))
+    }
+
+    /* Called after every instruction: handles a command once it is complete. */
+    pub fn poll(&mut self, memory: &mut Memory) -> Result<Response, MachineError> {
+        let command = Self::read_u64(memory, self.tohost)?;
+        let settled = command == self.last;
+        self.last = command;
+        if command == 0 || !settled {
+            return Ok(Response::Continue);
+        }
+
+        memory.store(self.tohost, &0u64.to_le_bytes())?;
+        self.last = 0;
+
+        let device = command >> 56;
+        let cmd = (command >> 48) & 0xFF;
+        let payload = command & 0xFFFF_FFFF_FFFF;
+
+        match (device, cmd) {
+            // exit, with the code in the upper bits.
+            (0, 0) if payload & 1 == 1 => return Ok(Response::Exit(payload >> 1)),
+            (0, 0) => {
+                if let Some(code) = self.syscall(memory, payload)? {
+                    return Ok(Response::Exit(code));
+                }
+                self.respond(memory, 1)?;
+            }
+            // console output.
+            (1, 1) => {
+                let mut stdout = std::io::stdout();
+                let _ = stdout.write_all(&[payload as u8]);
+                let _ = stdout.flush();
+                self.respond(memory, 1 << 56 | 1 << 48)?;
+            }
+            _ => println!("htif: ignoring command {command:#X}"),
+        }
+
+        Ok(Response::Continue)
+    }
+
+    fn respond(&self, memory: &mut Memory, value: u64) -> Result<(), MachineError> {
+        if let Some(fromhost) = self.fromhost {
+            memory.store(fromhost, &value.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /* The syscall proxy: payload points to eight words holding the syscall number and
+     * its arguments, and the first word receives the return value. */
+    fn syscall(&self, memory: &mut Memory, args: u64) -> Result<Option<u64>, MachineError> {
+        let arg = |i: u64| Self::read_u64(memory, args + 8 * i);
+        let (number, a0, a1, a2) = (arg(0)?, arg(1)?, arg(2)?, arg(3)?);
+
+        let ret = match number {
+            SYS_EXIT => return Ok(Some(a0)),
+            SYS_WRITE => {
+                let bytes = memory.read(a1, a2 as usize)?;
+                let written = match a0 {
+                    1 => std::io::stdout().write_all(bytes),
+                    2 => std::io::stderr().write_all(bytes),
+                    _ => Err(std::io::ErrorKind::Unsupported.into()),
+                };
+                match written {
+                    Ok(()) => a2 as i64,
+                    Err(_) => -9, // EBADF
+                }
+            }
+            _ => {
+                println!("htif: unsupported syscall {number}");
+                -ENOSYS
+            }
+        };
+        memory.store(args, &ret.to_le_bytes())?;
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use crate::instruction::Instruction;
+
+pub(crate) const PAGE_SIZE: u64 = 4096;
+
+/// Decoded instructions, grouped by page so that a store only has to drop the
+/// entries of the page it touched.
+#[derive(Default)]
+pub(crate) struct InstructionCache {
+    pages: HashMap<u64, HashMap<u64, Instruction>>,
+}
+
+impl InstructionCache {
+    pub fn get(&self, pc: u64) -> Option<Instruction> {
+        self.pages
+            .get(&(pc / PAGE_SIZE))
+            .and_then(|page| page.get(&pc))
+            .copied()
+    }
+
+    pub fn insert(&mut self, pc: u64, instr: Instruction) {
+        self.pages
+            .entry(pc / PAGE_SIZE)
+            .or_default()
+            .insert(pc, instr);
+    }
+
+    pub fn invalidate_page(&mut self, page: u64) {
+        self.pages.remove(&page);
+    }
+
+    pub fn flush(&mut self) {
+        self.pages.clear();
+    }
+}
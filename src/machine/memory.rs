@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use crate::{
+    loader::{self, Segment},
+    machine::{MachineError, icache::PAGE_SIZE},
+};
+
+/// Access rights of a memory region, as described by the ELF `p_flags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl Permissions {
+    /// Permissions of memory not covered by any loaded segment (stack, heap).
+    pub const DEFAULT: Permissions = Permissions {
+        read: true,
+        write: true,
+        execute: false,
+    };
+
+    /// Permissions of images that do not record any, e.g. raw binaries.
+    pub const ALL: Permissions = Permissions {
+        read: true,
+        write: true,
+        execute: true,
+    };
+
+    pub fn from_elf_flags(flags: u32) -> Self {
+        Permissions {
+            read: flags & elf::abi::PF_R != 0,
+            write: flags & elf::abi::PF_W != 0,
+            execute: flags & elf::abi::PF_X != 0,
+        }
+    }
+
+    pub fn allows(&self, access: Access) -> bool {
+        match access {
+            Access::Read => self.read,
+            Access::Write => self.write,
+            Access::Execute => self.execute,
+        }
+    }
+}
+
+impl std::fmt::Display for Permissions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flag = |set: bool, c: char| if set { c } else { '-' };
+        write!(
+            f,
+            "{}{}{}",
+            flag(self.read, 'r'),
+            flag(self.write, 'w'),
+            flag(self.execute, 'x')
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+/// What a load or store to an address that is not a multiple of its size does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MisalignedAccess {
+    /// The access completes as if the hardware supported it.
+    #[default]
+    Emulate,
+    /// The access completes and a warning is printed in the trace.
+    Warn,
+    /// The access raises a load or store address misaligned exception.
+    Trap,
+}
+
+impl std::str::FromStr for MisalignedAccess {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "emulate" => Ok(MisalignedAccess::Emulate),
+            "warn" => Ok(MisalignedAccess::Warn),
+            "trap" => Ok(MisalignedAccess::Trap),
+            _ => Err(format!(
+                "unknown misaligned access policy {s}, expected emulate, warn or trap"
+            )),
+        }
+    }
+}
+
+/// Where the RAM of a machine starts and how large it is. Images must fit inside, and
+/// the stack starts at its end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryLayout {
+    pub base: u64,
+    pub size: u64,
+}
+
+impl MemoryLayout {
+    pub fn end(&self) -> u64 {
+        self.base + self.size
+    }
+}
+
+impl Default for MemoryLayout {
+    /* 4 MiB from address 0. */
+    fn default() -> Self {
+        MemoryLayout {
+            base: 0,
+            size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+/// A contiguous range of memory `[start, end)` sharing the same permissions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub start: u64,
+    pub end: u64,
+    pub permissions: Permissions,
+}
+
+impl MemoryRegion {
+    pub fn contains(&self, addr: u64) -> bool {
+        (self.start..self.end).contains(&addr)
+    }
+}
+
+pub(crate) struct Memory {
+    /// The address of the first byte of data.
+    base: u64,
+    data: Vec<u8>,
+    regions: Vec<MemoryRegion>,
+    /// Pages written since the last call to `take_written_pages`.
+    written_pages: HashSet<u64>,
+    /// Address and size reserved by the last `lr.w`/`lr.d`, cleared by any store that overlaps it.
+    reservation: Option<(u64, usize)>,
+}
+
+impl Memory {
+    pub fn new(layout: MemoryLayout, regions: Vec<MemoryRegion>) -> Self {
+        Memory {
+            base: layout.base,
+            data: vec![0; layout.size as usize],
+            regions,
+            written_pages: HashSet::new(),
+            reservation: None,
+        }
+    }
+
+    /* Zeroes every byte, as at power on, and forgets written pages and the reservation. */
+    pub fn clear(&mut self) {
+        self.data.fill(0);
+        self.written_pages.clear();
+        self.reservation = None;
+    }
+
+    /* Places a segment, whatever the permissions of its region. */
+    pub fn place(&mut self, segment: &Segment) -> Result<(), loader::Error> {
+        segment.load_into(&mut self.data, self.base)
+    }
+
+    pub fn regions(&self) -> &[MemoryRegion] {
+        &self.regions
+    }
+
+    fn permissions(&self, addr: u64) -> Permissions {
+        self.regions
+            .iter()
+            .find(|region| region.contains(addr))
+            .map(|region| region.permissions)
+            .unwrap_or(Permissions::DEFAULT)
+    }
+
+    /* Checks that every byte in [addr, addr + len) exists and allows the access, and
+     * returns the offset of addr in data. */
+    fn check(&self, addr: u64, len: usize, access: Access) -> Result<usize, MachineError> {
+        for i in 0..len as u64 {
+            let byte_addr = addr.wrapping_add(i);
+            if byte_addr.wrapping_sub(self.base) >= self.data.len() as u64 {
+                return Err(MachineError::MemoryError(byte_addr));
+            }
+
+            if !self.permissions(byte_addr).allows(access) {
+                return Err(match access {
+                    Access::Read => MachineError::LoadAccessFault(byte_addr),
+                    Access::Write => MachineError::StoreAccessFault(byte_addr),
+                    Access::Execute => MachineError::InstructionAccessFault(byte_addr),
+                });
+            }
+        }
+
+        Ok(addr.wrapping_sub(self.base) as usize)
+    }
+
+    pub fn load<const N: usize>(&self, addr: u64) -> Result<[u8; N], MachineError> {
+        let start = self.check(addr, N, Access::Read)?;
+
+        Ok(self.data[start..start + N]
+            .try_into()
+            .expect("slice length checked above"))
+    }
+
+    pub fn read(&self, addr: u64, len: usize) -> Result<&[u8], MachineError> {
+        let start = self.check(addr, len, Access::Read)?;
+
+        Ok(&self.data[start..start + len])
+    }
+
+    /* Writes bytes whatever the permissions, as a device would: unlike store, the
+     * written pages are not recorded, so cached instructions there stay until fence.i. */
+    pub fn write(&mut self, addr: u64, bytes: &[u8]) -> Result<(), MachineError> {
+        let start = addr.wrapping_sub(self.base);
+        match start.checked_add(bytes.len() as u64) {
+            Some(end) if end <= self.data.len() as u64 => {
+                self.data[start as usize..end as usize].copy_from_slice(bytes);
+                Ok(())
+            }
+            _ => Err(MachineError::MemoryError(addr)),
+        }
+    }
+
+    pub fn store(&mut self, addr: u64, bytes: &[u8]) -> Result<(), MachineError> {
+        let start = self.check(addr, bytes.len(), Access::Write)?;
+        self.data[start..start + bytes.len()].copy_from_slice(bytes);
+
+        let last = addr + bytes.len().saturating_sub(1) as u64;
+        self.written_pages.insert(addr / PAGE_SIZE);
+        self.written_pages.insert(last / PAGE_SIZE);
+
+        if self
+            .reservation
+            .is_some_and(|(reserved, size)| addr < reserved + size as u64 && reserved <= last)
+        {
+            self.reservation = None;
+        }
+
+        Ok(())
+    }
+
+    pub fn reserve(&mut self, addr: u64, size: usize) {
+        self.reservation = Some((addr, size));
+    }
+
+    pub fn clear_reservation(&mut self) {
+        self.reservation = None;
+    }
+
+    /* Stores only if addr is still reserved for a store of this size. The reservation is consumed either way. */
+    pub fn store_conditional(&mut self, addr: u64, bytes: &[u8]) -> Result<bool, MachineError> {
+        let reserved = self.reservation.take() == Some((addr, bytes.len()));
+        if reserved {
+            self.store(addr, bytes)?;
+        }
+
+        Ok(reserved)
+    }
+
+    pub fn fetch(&self, addr: u64) -> Result<[u8; 4], MachineError> {
+        let start = self.check(addr, 4, Access::Execute)?;
+
+        Ok(self.data[start..start + 4]
+            .try_into()
+            .expect("slice length checked above"))
+    }
+
+    pub fn take_written_pages(&mut self) -> HashSet<u64> {
+        std::mem::take(&mut self.written_pages)
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use crate::{
+    isa::Xlen,
+    machine::{
+        Access, MachineError,
+        memory::{Memory, MisalignedAccess},
+        pmp::Pmp,
+        trap::Privilege,
+    },
+};
+
+const PAGE_SHIFT: u64 = 12;
+
+/// Sv32 page table entry bits.
+mod pte {
+    pub const V: u64 = 1 << 0;
+    pub const R: u64 = 1 << 1;
+    pub const W: u64 = 1 << 2;
+    pub const X: u64 = 1 << 3;
+    pub const U: u64 = 1 << 4;
+    pub const G: u64 = 1 << 5;
+    pub const A: u64 = 1 << 6;
+    pub const D: u64 = 1 << 7;
+}
+
+/// The state an access is translated and checked against.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Context {
+    pub satp: u64,
+    /// Effective privilege, which for loads and stores may come from MPRV.
+    pub privilege: Privilege,
+    /// mstatus.SUM: S-mode may load and store to user pages.
+    pub sum: bool,
+    /// mstatus.MXR: loads may read executable pages.
+    pub mxr: bool,
+    pub xlen: Xlen,
+    pub pmp: Pmp,
+    pub misaligned: MisalignedAccess,
+}
+
+impl Context {
+    fn asid(&self) -> u16 {
+        ((self.satp >> 22) & 0x1FF) as u16
+    }
+
+    /* Only Sv32 is implemented, M-mode and Bare mode use physical addresses. */
+    fn translates(&self) -> bool {
+        self.privilege < Privilege::Machine && self.xlen == Xlen::Rv32 && self.satp >> 31 == 1
+    }
+
+    /* PMP check of a physical access, faulting with the virtual address. */
+    pub fn check_physical(
+        &self,
+        vaddr: u64,
+        paddr: u64,
+        len: usize,
+        access: Access,
+    ) -> Result<(), MachineError> {
+        if !self.pmp.allows(paddr, len, access, self.privilege) {
+            return Err(access_fault(access, vaddr));
+        }
+
+        Ok(())
+    }
+
+    /* Page table accesses are implicit S-mode accesses, checked by PMP as such. */
+    fn check_page_table(
+        &self,
+        vaddr: u64,
+        pte_addr: u64,
+        access: Access,
+        pte_access: Access,
+    ) -> Result<(), MachineError> {
+        if !self
+            .pmp
+            .allows(pte_addr, 4, pte_access, Privilege::Supervisor)
+        {
+            return Err(access_fault(access, vaddr));
+        }
+
+        Ok(())
+    }
+}
+
+/// A cached leaf PTE covering one 4 KiB page, superpages are cached page by page.
+#[derive(Debug, Clone, Copy)]
+struct TlbEntry {
+    asid: u16,
+    pte: u64,
+    pte_addr: u64,
+    ppn: u64,
+}
+
+impl TlbEntry {
+    fn matches(&self, asid: u16) -> bool {
+        self.pte & pte::G != 0 || self.asid == asid
+    }
+}
+
+fn page_fault(access: Access, addr: u64) -> MachineError {
+    match access {
+        Access::Read => MachineError::LoadPageFault(addr),
+        Access::Write => MachineError::StorePageFault(addr),
+        Access::Execute => MachineError::InstructionPageFault(addr),
+    }
+}
+
+fn access_fault(access: Access, addr: u64) -> MachineError {
+    match access {
+        Access::Read => MachineError::LoadAccessFault(addr),
+        Access::Write => MachineError::StoreAccessFault(addr),
+        Access::Execute => MachineError::InstructionAccessFault(addr),
+    }
+}
+
+/// Translates virtual addresses with Sv32 page table walks, cached in a TLB indexed by
+/// virtual page number.
+#[derive(Default)]
+pub(crate) struct Mmu {
+    tlb: HashMap<u64, TlbEntry>,
+}
+
+impl Mmu {
+    pub fn translate(
+        &mut self,
+        memory: &mut Memory,
+        context: Context,
+        vaddr: u64,
+        access: Access,
+    ) -> Result<u64, MachineError> {
+        if !context.translates() {
+            return Ok(vaddr);
+        }
+
+        let vpn = vaddr >> PAGE_SHIFT;
+        let mut entry = match self.tlb.get(&vpn) {
+            Some(entry) if entry.matches(context.asid()) => *entry,
+            _ => {
+                let entry = Self::walk(memory, context, vaddr, access)?;
+                self.tlb.insert(vpn, entry);
+                entry
+            }
+        };
+
+        if !Self::allows(entry.pte, context, access) {
+            return Err(page_fault(access, vaddr));
+        }
+
+        // the accessed and dirty bits are set by the walker, not by a page fault.
+        let needed = pte::A | if access == Access::Write { pte::D } else { 0 };
+        if entry.pte & needed != needed {
+            entry.pte |= needed;
+            context.check_page_table(vaddr, entry.pte_addr, access, Access::Write)?;
+            memory
+                .store(entry.pte_addr, &(entry.pte as u32).to_le_bytes())
+                .map_err(|_| access_fault(access, vaddr))?;
+            self.tlb.insert(vpn, entry);
+        }
+
+        Ok(entry.ppn << PAGE_SHIFT | (vaddr & ((1 << PAGE_SHIFT) - 1)))
+    }
+
+    /* Walks the two-level Sv32 page table rooted at satp.PPN. */
+    fn walk(
+        memory: &Memory,
+        context: Context,
+        vaddr: u64,
+        access: Access,
+    ) -> Result<TlbEntry, MachineError> {
+        let vpn = [(vaddr >> 12) & 0x3FF, (vaddr >> 22) & 0x3FF];
+        let mut table = (context.satp & 0x3F_FFFF) << PAGE_SHIFT;
+
+        for level in (0..2).rev() {
+            let pte_addr = table + vpn[level] * 4;
+            context.check_page_table(vaddr, pte_addr, access, Access::Read)?;
+            let bytes = memory
+                .load::<4>(pte_addr)
+                .map_err(|_| access_fault(access, vaddr))?;
+            let pte = u32::from_le_bytes(bytes) as u64;
+
+            if pte & pte::V == 0 || (pte & pte::R == 0 && pte & pte::W != 0) {
+                return Err(page_fault(access, vaddr));
+            }
+
+            let ppn = pte >> 10;
+            if pte & (pte::R | pte::X) == 0 {
+                // pointer to the next level.
+                table = ppn << PAGE_SHIFT;
+                continue;
+            }
+
+            // a megapage must be aligned to 4 MiB, its low PPN comes from the address.
+            let ppn = match level {
+                1 if ppn & 0x3FF != 0 => return Err(page_fault(access, vaddr)),
+                1 => ppn | vpn[0],
+                _ => ppn,
+            };
+
+            return Ok(TlbEntry {
+                asid: context.asid(),
+                pte,
+                pte_addr,
+                ppn,
+            });
+        }
+
+        Err(page_fault(access, vaddr))
+    }
+
+    /* Checks the leaf permissions against the access and the effective privilege. */
+    fn allows(pte: u64, context: Context, access: Access) -> bool {
+        let permitted = match access {
+            Access::Read => pte & pte::R != 0 || (context.mxr && pte & pte::X != 0),
+            Access::Write => pte & pte::W != 0,
+            Access::Execute => pte & pte::X != 0,
+        };
+        let user_page = pte & pte::U != 0;
+        let privileged = match context.privilege {
+            Privilege::User => user_page,
+            Privilege::Supervisor => !user_page || (context.sum && access != Access::Execute),
+            Privilege::Machine => true,
+        };
+
+        permitted && privileged
+    }
+
+    /* sfence.vma: drops the entries of one page and/or one address space, or everything. */
+    pub fn flush(&mut self, vaddr: Option<u64>, asid: Option<u16>) {
+        match (vaddr, asid) {
+            (Some(vaddr), asid) => {
+                let vpn = vaddr >> PAGE_SHIFT;
+                if self.tlb.get(&vpn).is_some_and(|entry| {
+                    asid.is_none_or(|asid| entry.pte & pte::G == 0 && entry.asid == asid)
+                }) {
+                    self.tlb.remove(&vpn);
+                }
+            }
+            (None, Some(asid)) => self
+                .tlb
+                .retain(|_, entry| entry.pte & pte::G != 0 || entry.asid != asid),
+            (None, None) => self.tlb.clear(),
+        }
+    }
+}
+
+/// Memory as seen by a load or store of the executing instruction: virtual addresses
+/// are translated before reaching physical memory.
+pub(crate) struct Bus<'a> {
+    memory: &'a mut Memory,
+    mmu: &'a mut Mmu,
+    context: Context,
+}
+
+impl<'a> Bus<'a> {
+    pub fn new(memory: &'a mut Memory, mmu: &'a mut Mmu, context: Context) -> Self {
+        Bus {
+            memory,
+            mmu,
+            context,
+        }
+    }
+
+    fn translate(&mut self, vaddr: u64, access: Access) -> Result<u64, MachineError> {
+        self.mmu.translate(self.memory, self.context, vaddr, access)
+    }
+
+    /* Translates every byte of [vaddr, vaddr + len), which may span two pages, and
+     * checks the physical bytes against PMP. */
+    pub fn translate_range(
+        &mut self,
+        vaddr: u64,
+        len: usize,
+        access: Access,
+    ) -> Result<Vec<u64>, MachineError> {
+        let paddrs = (0..len as u64)
+            .map(|i| self.translate(vaddr.wrapping_add(i), access))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if paddrs
+            .windows(2)
+            .all(|pair| pair[1] == pair[0].wrapping_add(1))
+        {
+            self.context.check_physical(vaddr, paddrs[0], len, access)?;
+        } else {
+            for &paddr in &paddrs {
+                self.context.check_physical(vaddr, paddr, 1, access)?;
+            }
+        }
+
+        Ok(paddrs)
+    }
+
+    /* Applies the misaligned access policy to a load or store of len bytes. */
+    fn check_alignment(&self, vaddr: u64, len: usize, access: Access) -> Result<(), MachineError> {
+        if vaddr.is_multiple_of(len as u64) {
+            return Ok(());
+        }
+
+        match (self.context.misaligned, access) {
+            (MisalignedAccess::Emulate, _) => {}
+            (MisalignedAccess::Warn, _) => {
+                println!("warning: misaligned {len} byte access at {vaddr:#X}")
+            }
+            (MisalignedAccess::Trap, Access::Write) => {
+                return Err(MachineError::StoreAddressMisaligned(vaddr));
+            }
+            (MisalignedAccess::Trap, _) => return Err(MachineError::LoadAddressMisaligned(vaddr)),
+        }
+
+        Ok(())
+    }
+
+    pub fn load<const N: usize>(&mut self, vaddr: u64) -> Result<[u8; N], MachineError> {
+        self.check_alignment(vaddr, N, Access::Read)?;
+        let paddrs = self.translate_range(vaddr, N, Access::Read)?;
+        if paddrs
+            .windows(2)
+            .all(|pair| pair[1] == pair[0].wrapping_add(1))
+        {
+            return self.memory.load::<N>(paddrs[0]);
+        }
+
+        let mut bytes = [0; N];
+        for (byte, paddr) in bytes.iter_mut().zip(paddrs) {
+            *byte = self.memory.load::<1>(paddr)?[0];
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn store(&mut self, vaddr: u64, bytes: &[u8]) -> Result<(), MachineError> {
+        self.check_alignment(vaddr, bytes.len(), Access::Write)?;
+        let paddrs = self.translate_range(vaddr, bytes.len(), Access::Write)?;
+        if paddrs
+            .windows(2)
+            .all(|pair| pair[1] == pair[0].wrapping_add(1))
+        {
+            return self.memory.store(paddrs[0], bytes);
+        }
+
+        for (byte, paddr) in bytes.iter().zip(paddrs) {
+            self.memory.store(paddr, &[*byte])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn reserve(&mut self, vaddr: u64, size: usize) -> Result<(), MachineError> {
+        let paddr = self.translate_range(vaddr, size, Access::Read)?[0];
+        self.memory.reserve(paddr, size);
+
+        Ok(())
+    }
+
+    pub fn store_conditional(&mut self, vaddr: u64, bytes: &[u8]) -> Result<bool, MachineError> {
+        let paddr = self.translate_range(vaddr, bytes.len(), Access::Write)?[0];
+        self.memory.store_conditional(paddr, bytes)
+    }
+}
@@ -1,90 +1,317 @@
-use std::collections::HashMap;
-
+mod csr;
 mod executor;
+mod fpu;
+mod htif;
+mod icache;
+mod memory;
+mod mmu;
+mod pmp;
+mod softfloat;
+mod stack;
+mod trap;
+
+use std::slice;
 
 use bilge::prelude::{Integer, u5};
 
-type Memory = Vec<u8>;
+pub use memory::{Access, MemoryLayout, MemoryRegion, MisalignedAccess, Permissions};
+pub use trap::Privilege;
+
+use csr::{Csrs, status};
+use fpu::FloatRegisters;
+use htif::Htif;
+use icache::InstructionCache;
+use memory::Memory;
+use mmu::{Bus, Context, Mmu};
 
 use crate::{
-    file_parser::{self, ElfFile},
-    instruction::{self, Instruction},
+    assembler::Program,
+    file_parser::{self, Requirements},
+    instruction::{
+        self, Instruction,
+        definitions::{Extension, Op},
+    },
+    isa::Isa,
+    loader::{self, Entry, Image, Loader, Segment},
 };
 
 #[derive(thiserror::Error, Debug)]
 pub enum MachineError {
     #[error("AddressError: Tried to access an invalid pc address. pc={0:#X}")]
-    AddressError(u32),
+    AddressError(u64),
 
     #[error("MemoryError: Tried to access an invalid memory address. addr={0:#X}")]
-    MemoryError(u32),
+    MemoryError(u64),
+
+    #[error("InstructionAccessFault: Tried to execute non-executable memory. addr={0:#X}")]
+    InstructionAccessFault(u64),
+
+    #[error("LoadAccessFault: Tried to read non-readable memory. addr={0:#X}")]
+    LoadAccessFault(u64),
+
+    #[error("StoreAccessFault: Tried to write non-writable memory. addr={0:#X}")]
+    StoreAccessFault(u64),
+
+    #[error("InstructionAddressMisaligned: Tried to jump to a misaligned address. addr={0:#X}")]
+    InstructionAddressMisaligned(u64),
+
+    #[error("LoadAddressMisaligned: Tried to load from a misaligned address. addr={0:#X}")]
+    LoadAddressMisaligned(u64),
+
+    #[error("StoreAddressMisaligned: Tried to store to a misaligned address. addr={0:#X}")]
+    StoreAddressMisaligned(u64),
+
+    #[error("InstructionPageFault: No executable mapping for the address. addr={0:#X}")]
+    InstructionPageFault(u64),
+
+    #[error("LoadPageFault: No readable mapping for the address. addr={0:#X}")]
+    LoadPageFault(u64),
+
+    #[error("StorePageFault: No writable mapping for the address. addr={0:#X}")]
+    StorePageFault(u64),
+
+    #[error("EnvironmentCall: ecall executed. pc={0:#X}")]
+    EnvironmentCall(u64),
+
+    #[error("Breakpoint: ebreak executed. pc={0:#X}")]
+    Breakpoint(u64),
+
+    #[error("IllegalInstruction: Could not decode the instruction at pc={0:#X}. {1}")]
+    IllegalInstruction(u64, instruction::Error),
+
+    #[error("UnsupportedCsr: Tried to access an unsupported CSR. csr={0:#X}")]
+    UnsupportedCsr(u16),
+
+    #[error("IllegalCsrAccess: CSR {0:#X} cannot be accessed this way from {1}-mode.")]
+    IllegalCsrAccess(u16, Privilege),
+
+    #[error("PrivilegedInstruction: {0} cannot be executed from {1}-mode.")]
+    PrivilegedInstruction(Op, Privilege),
+
+    #[error("InvalidRoundingMode: Floating point rounding mode {0:#b} is reserved.")]
+    InvalidRoundingMode(u8),
+
+    #[error("FloatingPointOff: {0} uses the floating point state, which mstatus.FS turns off.")]
+    FloatingPointOff(Op),
+
+    #[error(
+        "InvalidMemoryLayout: {1:#X} bytes of memory at {0:#X} pass the end of the address space."
+    )]
+    InvalidMemoryLayout(u64, u64),
+
+    #[error("ArgumentsTooLarge: The arguments and environment need {0} bytes of a {1} byte stack.")]
+    ArgumentsTooLarge(u64, u64),
 
     #[error("Error parsing the file: {0}")]
     ElfError(#[from] file_parser::Error),
+
+    #[error("Error loading the image: {0}")]
+    LoadError(loader::Error),
+}
+
+/* ELF errors are reported as they were before images could come from other formats. */
+impl From<loader::Error> for MachineError {
+    fn from(err: loader::Error) -> Self {
+        match err {
+            loader::Error::Elf(err) => MachineError::ElfError(err),
+            err => MachineError::LoadError(err),
+        }
+    }
 }
 
 pub struct Machine {
-    pc: u32,
-    registers: [i32; 32],
-    instructions: HashMap<u32, Instruction>,
-    memory: Vec<u8>,
+    pc: u64,
+    /// x registers; on RV32 every value is kept sign-extended from bit 31.
+    registers: [i64; 32],
+    fregisters: FloatRegisters,
+    csrs: Csrs,
+    privilege: Privilege,
+    icache: InstructionCache,
+    mmu: Mmu,
+    memory: Memory,
+    isa: Isa,
+    misaligned: MisalignedAccess,
+    sanitizer: bool,
+    /// The pc of every instruction the sanitizer reported.
+    overflows: Vec<u64>,
+    /// Present if the program talks to the host through tohost.
+    htif: Option<Htif>,
+    exit_code: Option<u64>,
+    /// What reset loads into memory again.
+    segments: Vec<Segment>,
+    /// What the loaded files ask of the ISA.
+    requirements: Vec<Requirements>,
+    entry: u64,
+    layout: MemoryLayout,
+    /// The alignment of sp at entry, at least the 16 bytes the psABI asks for.
+    stack_align: u64,
 }
 
 impl Machine {
-    const MEMORY_SIZE: usize = 4 * 1024 * 1024; // 4 MiB
-    const _STACK_SIZE: usize = 256 * 1024; // 256 kiB
-    const STACK_TOP: i32 = Self::MEMORY_SIZE as i32; // grows backwards
-    //
+    const STACK_SIZE: usize = 256 * 1024; // 256 kiB
     pub fn new(data: &[u8]) -> Result<Machine, MachineError> {
-        let file = ElfFile::from_buffer(data)?;
-        let memory = file.load_memory(Self::MEMORY_SIZE);
-        let text_section = file.find_section_by_name(".text")?;
-        let instructions = ElfFile::load_section(text_section, &memory)?;
-        let mut registers = [0i32; 32];
-
-        // set sp
-        registers[2] = Self::STACK_TOP & !0xF;
-
-        Ok(Machine {
-            pc: file.entry_point(),
-            registers,
-            instructions,
+        Self::from_image(&loader::Elf.load(data)?)
+    }
+
+    /* Loads an assembled program: text is readable and executable, data and bss readable
+     * and writable, and the machine is RV32 with every extension. */
+    pub fn from_program(program: &Program) -> Result<Machine, MachineError> {
+        Self::from_image(&program.into())
+    }
+
+    pub fn from_image(image: &Image) -> Result<Machine, MachineError> {
+        Self::from_images(slice::from_ref(image), &Entry::Image(0))
+    }
+
+    /* Places the segments of every image in memory, e.g. a bootloader, a kernel and a
+     * device tree, and starts at entry. Images that do not name an ISA run on RV32 with
+     * every extension. Memory is the default 4 MiB from address 0. */
+    pub fn from_images(images: &[Image], entry: &Entry) -> Result<Machine, MachineError> {
+        Self::from_images_in(images, entry, MemoryLayout::default())
+    }
+
+    /* Like from_images, with memory where layout says, e.g. at 0x80000000 where most
+     * RISC-V boards and test suites put it. */
+    pub fn from_images_in(
+        images: &[Image],
+        entry: &Entry,
+        layout: MemoryLayout,
+    ) -> Result<Machine, MachineError> {
+        if layout.base.checked_add(layout.size).is_none() {
+            return Err(MachineError::InvalidMemoryLayout(layout.base, layout.size));
+        }
+
+        let image = Image::combine(images, entry)?;
+        let regions = image
+            .segments
+            .iter()
+            .map(|segment| {
+                Ok(MemoryRegion {
+                    start: segment.addr,
+                    end: segment.end()?,
+                    permissions: segment.permissions,
+                })
+            })
+            .collect::<Result<_, loader::Error>>()?;
+        let mut memory = Memory::new(layout, regions);
+        for segment in &image.segments {
+            memory.place(segment)?;
+        }
+
+        let entry = image.entry_point();
+        let htif = Htif::from_symbols(&image.symbols);
+        let isa = image.isa.unwrap_or_default();
+        for requirements in &image.requirements {
+            requirements.check(&isa)?;
+        }
+        let mut machine = Self::with_memory(
+            isa,
             memory,
-        })
+            layout,
+            image.segments,
+            entry,
+            image.stack_align.unwrap_or(16),
+            htif,
+        );
+        machine.requirements = image.requirements;
+
+        Ok(machine)
     }
 
-    fn get_register(&self, index: u5) -> i32 {
-        self.registers
-            .get(index.as_usize())
-            .copied()
-            .expect("register index not found? check registers array size.")
+    fn with_memory(
+        isa: Isa,
+        memory: Memory,
+        layout: MemoryLayout,
+        segments: Vec<Segment>,
+        entry: u64,
+        stack_align: u64,
+        htif: Option<Htif>,
+    ) -> Machine {
+        let mut machine = Machine {
+            pc: entry,
+            registers: [0; 32],
+            fregisters: [0; 32],
+            csrs: Csrs::new(&isa),
+            privilege: Privilege::Machine,
+            icache: InstructionCache::default(),
+            mmu: Mmu::default(),
+            memory,
+            isa,
+            misaligned: MisalignedAccess::default(),
+            sanitizer: false,
+            overflows: Vec::new(),
+            htif,
+            exit_code: None,
+            segments,
+            requirements: Vec::new(),
+            entry,
+            layout,
+            // aligned to at least 16 bytes as the psABI requires.
+            stack_align: stack_align.max(16),
+        };
+        machine.reset_hart();
+
+        machine
     }
 
-    fn get_mut_register(&mut self, index: u5) -> &mut i32 {
+    /* Puts the machine back as it was once loaded, to run the program again: memory holds
+     * only the images, with .bss zeroed, and execution starts over at the entry point.
+     * The ISA and the options set since loading are kept; arguments must be set again. */
+    pub fn reset(&mut self) {
+        self.memory.clear();
+        for segment in &self.segments {
+            self.memory
+                .place(segment)
+                .expect("segments fit in memory, as they did when loaded");
+        }
+        self.reset_hart();
+    }
+
+    fn reset_hart(&mut self) {
+        self.pc = self.entry;
+        self.registers = [0; 32];
+        // the stack grows down from the end of memory.
+        let sp = self.layout.end() & !(self.stack_align - 1);
+        self.registers[2] = self.isa.xlen().sext(sp as i64);
+        self.fregisters = [0; 32];
+        self.csrs = Csrs::new(&self.isa);
+        // like a hart coming out of reset, programs start in M-mode.
+        self.privilege = Privilege::Machine;
+        self.icache.flush();
+        self.mmu = Mmu::default();
+        if let Some(htif) = &mut self.htif {
+            htif.reset();
+        }
+        self.exit_code = None;
+        self.overflows.clear();
+    }
+
+    fn get_register(&self, index: u5) -> i64 {
         self.registers
-            .get_mut(index.as_usize())
+            .get(index.as_usize())
+            .copied()
             .expect("register index not found? check registers array size.")
     }
 
     pub fn execute_until_loop(&mut self) -> Result<(), MachineError> {
         let mut nops = 0;
         loop {
-            let instr = self
-                .instructions
-                .get(&self.pc)
-                .copied()
-                .ok_or(MachineError::AddressError(self.pc))?;
+            // a failing fetch is reported (or trapped) when executing.
+            let is_nop = self.fetch().is_ok_and(|instr| instr.is_nop());
 
             let current_pc = self.pc;
 
             self.execute_next_instruction()?;
+            if self.exit_code.is_some() {
+                break;
+            }
             if self.pc == current_pc {
                 break;
             };
-            if (current_pc - nops * 4..current_pc).contains(&self.pc) {
+            if (current_pc.saturating_sub(nops * 4)..current_pc).contains(&self.pc) {
                 break;
             }
-            if instr.is_nop() {
+            if is_nop {
                 nops += 1;
             } else {
                 nops = 0;
@@ -93,15 +320,214 @@ impl Machine {
         Ok(())
     }
 
+    pub fn isa(&self) -> &Isa {
+        &self.isa
+    }
+
+    /* Changes the base width and the extensions the machine decodes.
+     * Instructions of disabled extensions are illegal. The ISA must still provide what
+     * the loaded files require, or the machine is left as it was. */
+    pub fn set_isa(&mut self, isa: Isa) -> Result<(), MachineError> {
+        for requirements in &self.requirements {
+            requirements.check(&isa)?;
+        }
+
+        self.csrs.set_isa(&isa);
+        self.isa = isa;
+        self.icache.flush();
+
+        Ok(())
+    }
+
+    /* Chooses how misaligned loads and stores behave. */
+    pub fn set_misaligned_access(&mut self, policy: MisalignedAccess) {
+        let unaligned = self.requirements.iter().any(|r| r.unaligned_access);
+        if policy == MisalignedAccess::Trap && unaligned {
+            println!("warning: the program was built to access misaligned data, which will trap.");
+        }
+        self.misaligned = policy;
+    }
+
+    /* When enabled, signed overflow in guest arithmetic is reported in the trace and
+     * recorded in overflows. The results still wrap as the ISA specifies. */
+    pub fn set_sanitizer(&mut self, enabled: bool) {
+        self.sanitizer = enabled;
+    }
+
+    /* Passes arguments and environment variables to the program: argc, argv, envp and the
+     * auxiliary vector are laid out on the stack as the RISC-V Linux psABI describes, and
+     * sp points at argc, for startup code calling main(argc, argv, envp). Call it before
+     * running, after any change of ISA, as pointers on the stack are XLEN wide. */
+    pub fn set_arguments(&mut self, args: &[&str], env: &[&str]) -> Result<(), MachineError> {
+        let stack = stack::initial_stack(
+            self.layout.end(),
+            Self::STACK_SIZE as u64,
+            self.stack_align,
+            &self.isa,
+            self.entry,
+            args,
+            env,
+        )?;
+        self.memory.store(stack.sp, &stack.bytes)?;
+        self.registers[2] = self.isa.xlen().sext(stack.sp as i64);
+
+        Ok(())
+    }
+
+    /* x registers, sign-extended from bit 31 on RV32. */
+    pub fn registers(&self) -> &[i64; 32] {
+        &self.registers
+    }
+
+    /* The code the program exited with through HTIF, once it has. */
+    pub fn exit_code(&self) -> Option<u64> {
+        self.exit_code
+    }
+
+    /* Where the sanitizer found signed overflow since the last reset, in order. */
+    pub fn overflows(&self) -> &[u64] {
+        &self.overflows
+    }
+
+    pub fn privilege(&self) -> Privilege {
+        self.privilege
+    }
+
+    /* Translation state for fetches, or for loads and stores which MPRV may run at MPP. */
+    fn translation_context(&self, access: Access) -> Context {
+        let mstatus = self.csrs.mstatus;
+        let mprv = self.privilege == Privilege::Machine && mstatus & status::MPRV != 0;
+        let privilege = match access {
+            Access::Read | Access::Write if mprv => self.csrs.mpp(),
+            _ => self.privilege,
+        };
+
+        Context {
+            satp: self.csrs.satp,
+            privilege,
+            sum: mstatus & status::SUM != 0,
+            mxr: mstatus & status::MXR != 0,
+            xlen: self.isa.xlen(),
+            pmp: self.csrs.pmp,
+            misaligned: self.misaligned,
+        }
+    }
+
+    pub fn memory_regions(&self) -> &[MemoryRegion] {
+        self.memory.regions()
+    }
+
+    /* Reads physical memory, e.g. to inspect results a program left behind. */
+    pub fn read_memory(&self, addr: u64, len: usize) -> Result<&[u8], MachineError> {
+        self.memory.read(addr, len)
+    }
+
+    /* Writes physical memory, e.g. to patch a program while it runs. Like a device, and
+     * unlike the hart's own stores, this leaves decoded instructions cached until the
+     * program executes fence.i. */
+    pub fn write_memory(&mut self, addr: u64, bytes: &[u8]) -> Result<(), MachineError> {
+        self.memory.write(addr, bytes)
+    }
+
+    /* Returns the instruction at pc, decoding it from memory if it is not cached. */
+    fn fetch(&mut self) -> Result<Instruction, MachineError> {
+        let context = self.translation_context(Access::Execute);
+        // the cache is indexed by physical address, so it survives remapping.
+        let paddr = self
+            .mmu
+            .translate(&mut self.memory, context, self.pc, Access::Execute)?;
+        context.check_physical(self.pc, paddr, 4, Access::Execute)?;
+        if let Some(instr) = self.icache.get(paddr) {
+            return Ok(instr);
+        }
+
+        let bytes = self.memory.fetch(paddr)?;
+        let instr = Instruction::decode(bytes, &self.isa)
+            .map_err(|err| MachineError::IllegalInstruction(self.pc, err))?;
+        self.icache.insert(paddr, instr);
+
+        Ok(instr)
+    }
+
+    /* Executes one instruction, or enters the trap handler for a pending interrupt or
+     * for the exception the instruction raised. */
     pub fn execute_next_instruction(&mut self) -> Result<(), MachineError> {
-        let instr = self
-            .instructions
-            .get(&self.pc)
-            .ok_or(MachineError::AddressError(self.pc))?;
+        if let Some(interrupt) = self.pending_interrupt().filter(|&trap| self.handles(trap)) {
+            self.take_trap(interrupt);
+            return Ok(());
+        }
+
+        if let Err(err) = self.execute_instruction() {
+            match err.trap(self.privilege) {
+                Some(trap) if self.handles(trap) => {
+                    println!("{err}");
+                    self.take_trap(trap);
+                }
+                _ => return Err(err),
+            }
+        }
+
+        self.poll_htif()
+    }
+
+    /* Lets the host see what the program wrote to tohost. */
+    fn poll_htif(&mut self) -> Result<(), MachineError> {
+        let Some(htif) = &mut self.htif else {
+            return Ok(());
+        };
+
+        if let htif::Response::Exit(code) = htif.poll(&mut self.memory)? {
+            println!("htif: exit with code {code}");
+            self.exit_code = Some(code);
+        }
+
+        Ok(())
+    }
+
+    fn execute_instruction(&mut self) -> Result<(), MachineError> {
+        let instr = self.fetch()?;
         let format = instr.format();
+        let xlen = self.isa.xlen();
+        let context = self.translation_context(Access::Read);
 
         use instruction::formats::InstructionFormat::*;
         let new_pc = match format {
+            _ if matches!(instr.op(), Op::mret | Op::sret | Op::wfi | Op::sfence_vma) => {
+                let op = &instr.op();
+
+                println!("executing {op}({format})");
+                self.execute_privileged(op, &format)?
+            }
+            _ if matches!(instr.op().extension(), Extension::F | Extension::D) => {
+                let op = &instr.op();
+
+                println!("executing {op}({format})");
+                fpu::execute_float(
+                    op,
+                    &format,
+                    xlen,
+                    &mut self.registers,
+                    &mut self.fregisters,
+                    &mut self.csrs,
+                    &mut Bus::new(&mut self.memory, &mut self.mmu, context),
+                )?
+            }
+            I(itype) if instr.op().extension() == Extension::Zicsr => {
+                let rs1_index = itype.rs1();
+                let rd_index = itype.rd();
+                let rs1 = self.get_register(itype.rs1());
+                let csr = itype.imm().value();
+                let op = &instr.op();
+                let rd = self
+                    .registers
+                    .get_mut(itype.rd().value() as usize)
+                    .expect("register index not found? check registers array size.");
+
+                println!(
+                    "executing {op}(rd={rd_index} [{rd:#X}], rs1={rs1_index} [{rs1:#X}], csr={csr:#X})"
+                );
+                executor::execute_csr(op, rd, rs1_index, rs1, csr, self.privilege, &mut self.csrs)?
+            }
             R(rtype) => {
                 let rs1_index = rtype.rs1();
                 let rs2_index = rtype.rs2();
@@ -109,12 +535,22 @@ impl Machine {
                 let rs1 = self.get_register(rtype.rs1());
                 let rs2 = self.get_register(rtype.rs2());
                 let op = &instr.op();
-                let rd = &mut self.get_mut_register(rtype.rd());
+                let rd = self
+                    .registers
+                    .get_mut(rtype.rd().value() as usize)
+                    .expect("register index not found? check registers array size.");
 
                 println!(
                     "executing {op}(rd={rd_index} [{rd:#X}], rs1={rs1_index} [{rs1:#X}], rs2={rs2_index} [{rs2:#X}])"
                 );
-                executor::execute_rtype(op, rd, rs1, rs2)?
+                if self.sanitizer && executor::signed_overflow(op, xlen, rs1, rs2) {
+                    println!(
+                        "sanitizer: signed overflow in {op} of {rs1} and {rs2} at pc={:#X}",
+                        self.pc
+                    );
+                    self.overflows.push(self.pc);
+                }
+                executor::execute_rtype(op, xlen, rd, rs1, rs2)?
             }
             I(itype) => {
                 let rs1_index = itype.rs1();
@@ -128,12 +564,28 @@ impl Machine {
                 let op = &instr.op();
                 let imm = format
                     .immediate_value()
-                    .expect("I-type should have an immediate value");
+                    .expect("I-type should have an immediate value")
+                    .into();
 
                 println!(
                     "executing {op}(rd={rd_index} [{rd:#X}], rs1={rs1_index} [{rs1:#X}], imm={imm})"
                 );
-                executor::execute_itype(op, self.pc, rd, rs1, imm, &self.memory)?
+                if self.sanitizer && executor::signed_overflow(op, xlen, rs1, imm) {
+                    println!(
+                        "sanitizer: signed overflow in {op} of {rs1} and {imm} at pc={:#X}",
+                        self.pc
+                    );
+                    self.overflows.push(self.pc);
+                }
+                executor::execute_itype(
+                    op,
+                    xlen,
+                    self.pc,
+                    rd,
+                    rs1,
+                    imm,
+                    &mut Bus::new(&mut self.memory, &mut self.mmu, context),
+                )?
             }
             S(stype) => {
                 let rs1_index = stype.rs1();
@@ -144,12 +596,20 @@ impl Machine {
 
                 let imm = format
                     .immediate_value()
-                    .expect("S-type should have an immediate value");
+                    .expect("S-type should have an immediate value")
+                    .into();
 
                 println!(
                     "executing {op}(rs1={rs1_index} [{rs1:#X}], rs2={rs2_index} [{rs2:#X}], imm={imm})"
                 );
-                executor::execute_stype(op, rs1, rs2, imm, &mut self.memory)?
+                executor::execute_stype(
+                    op,
+                    xlen,
+                    rs1,
+                    rs2,
+                    imm,
+                    &mut Bus::new(&mut self.memory, &mut self.mmu, context),
+                )?
             }
             U(utype) => {
                 let op = &instr.op();
@@ -160,7 +620,8 @@ impl Machine {
 
                 let imm = format
                     .immediate_value()
-                    .expect("U-type should have an immediate value");
+                    .expect("U-type should have an immediate value")
+                    .into();
 
                 println!("executing {op}(rd={rd:#X}, imm={imm})");
                 executor::execute_utype(op, self.pc, rd, imm)?
@@ -174,7 +635,8 @@ impl Machine {
 
                 let imm = format
                     .immediate_value()
-                    .expect("S-type should have an immediate value");
+                    .expect("S-type should have an immediate value")
+                    .into();
 
                 println!(
                     "executing {op}(rs1={rs1_index} [{rs1:#X}], rs2={rs2_index} [{rs2:#X}], imm={imm})"
@@ -191,21 +653,62 @@ impl Machine {
 
                 let imm = format
                     .immediate_value()
-                    .expect("J-type should have an immediate value");
+                    .expect("J-type should have an immediate value")
+                    .into();
 
                 println!("executing {op}(rd={rd_index} [{rd:#X}], imm={imm})");
                 executor::execute_jtype(op, self.pc, rd, imm)?
             }
+
+            A(atype) => {
+                let rs1_index = atype.rs1();
+                let rs2_index = atype.rs2();
+                let rd_index = atype.rd();
+                let aq = atype.aq();
+                let rl = atype.rl();
+                let rs1 = self.get_register(atype.rs1());
+                let rs2 = self.get_register(atype.rs2());
+                let op = &instr.op();
+                let rd = self
+                    .registers
+                    .get_mut(atype.rd().value() as usize)
+                    .expect("register index not found? check registers array size.");
+
+                println!(
+                    "executing {op}(rd={rd_index} [{rd:#X}], rs1={rs1_index} [{rs1:#X}], rs2={rs2_index} [{rs2:#X}], aq={aq}, rl={rl})"
+                );
+                executor::execute_atype(
+                    op,
+                    xlen,
+                    rd,
+                    rs1,
+                    rs2,
+                    &mut Bus::new(&mut self.memory, &mut self.mmu, context),
+                )?
+            }
+            R4(..) => panic!("executing operation {} as a R4-type.", instr.op()),
         };
 
-        // hardwire x0 to 0.
+        // keep RV32 results sign-extended and hardwire x0 to 0.
+        if let Some(rd) = format.rd() {
+            self.registers[rd.as_usize()] = xlen.sext(self.registers[rd.as_usize()]);
+        }
         self.registers[0] = 0;
 
+        // drop decoded instructions that may have been overwritten.
+        for page in self.memory.take_written_pages() {
+            self.icache.invalidate_page(page);
+        }
+        if instr.op() == Op::fence_i {
+            self.icache.flush();
+        }
+
         if let Some(pc) = new_pc {
+            let pc = xlen.zext(pc as i64);
             println!("jumping to {pc:#X}");
             self.pc = pc;
         } else {
-            self.pc += 4;
+            self.pc = xlen.zext(self.pc.wrapping_add(4) as i64);
         }
 
         Ok(())
@@ -0,0 +1,152 @@
+use crate::{
+    isa::Xlen,
+    machine::{Access, trap::Privilege},
+};
+
+pub(crate) const ENTRIES: usize = 16;
+
+/// Fields of a pmpNcfg byte.
+mod cfg {
+    pub const R: u8 = 1 << 0;
+    pub const W: u8 = 1 << 1;
+    pub const X: u8 = 1 << 2;
+    pub const A_SHIFT: u8 = 3;
+    pub const A: u8 = 0b11 << A_SHIFT;
+    pub const L: u8 = 1 << 7;
+
+    pub const WRITABLE: u8 = R | W | X | A | L;
+}
+
+/// How an entry's address register selects the region it covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Matching {
+    Off,
+    /// Top of range: from the previous entry's address up to this one.
+    Tor,
+    /// Naturally aligned four byte region.
+    Na4,
+    /// Naturally aligned power of two region, the size is encoded in the trailing ones.
+    Napot,
+}
+
+/// The physical memory protection entries, with a granularity of four bytes.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Pmp {
+    cfg: [u8; ENTRIES],
+    addr: [u64; ENTRIES],
+}
+
+impl Pmp {
+    fn matching(&self, entry: usize) -> Matching {
+        match (self.cfg[entry] & cfg::A) >> cfg::A_SHIFT {
+            0 => Matching::Off,
+            1 => Matching::Tor,
+            2 => Matching::Na4,
+            _ => Matching::Napot,
+        }
+    }
+
+    fn locked(&self, entry: usize) -> bool {
+        self.cfg[entry] & cfg::L != 0
+    }
+
+    /* The byte range [start, end) an entry covers, or None when it is off. */
+    fn range(&self, entry: usize) -> Option<(u64, u64)> {
+        let addr = self.addr[entry];
+        match self.matching(entry) {
+            Matching::Off => None,
+            Matching::Tor => {
+                let start = match entry {
+                    0 => 0,
+                    _ => self.addr[entry - 1] << 2,
+                };
+                Some((start, addr << 2))
+            }
+            Matching::Na4 => Some((addr << 2, (addr << 2) + 4)),
+            Matching::Napot => {
+                let size = 1 << (addr.trailing_ones() + 3);
+                let start = (addr << 2) & !(size - 1);
+                Some((start, start + size))
+            }
+        }
+    }
+
+    /* Checks an access of len bytes at a physical address. The lowest numbered entry
+     * matching any byte decides, and it must cover every byte. M-mode is only held to
+     * locked entries. Unmatched U and S-mode accesses fail, also when no entry is in
+     * use, so M-mode has to grant them memory before dropping privilege. */
+    pub fn allows(&self, addr: u64, len: usize, access: Access, privilege: Privilege) -> bool {
+        let end = addr.saturating_add(len as u64);
+        for entry in 0..ENTRIES {
+            let Some((start, stop)) = self.range(entry) else {
+                continue;
+            };
+            if start >= stop || addr >= stop || end <= start {
+                continue;
+            }
+            if addr < start || end > stop {
+                return false;
+            }
+            if privilege == Privilege::Machine && !self.locked(entry) {
+                return true;
+            }
+
+            let required = match access {
+                Access::Read => cfg::R,
+                Access::Write => cfg::W,
+                Access::Execute => cfg::X,
+            };
+            return self.cfg[entry] & required != 0;
+        }
+
+        privilege == Privilege::Machine
+    }
+
+    /* pmpcfg registers pack four entries on RV32 and eight on RV64, where only the even
+     * numbered registers exist. */
+    fn cfg_entries(index: usize, xlen: Xlen) -> std::ops::Range<usize> {
+        let first = index * 4;
+        first..first + xlen.bits() as usize / 8
+    }
+
+    pub fn read_cfg(&self, index: usize, xlen: Xlen) -> u64 {
+        Self::cfg_entries(index, xlen)
+            .rev()
+            .fold(0, |value, entry| value << 8 | self.cfg[entry] as u64)
+    }
+
+    /* Locked entries ignore writes, and the reserved R=0 W=1 combination clears W. */
+    pub fn write_cfg(&mut self, index: usize, xlen: Xlen, value: u64) {
+        for (i, entry) in Self::cfg_entries(index, xlen).enumerate() {
+            if self.locked(entry) {
+                continue;
+            }
+
+            let mut byte = (value >> (8 * i)) as u8 & cfg::WRITABLE;
+            if byte & (cfg::R | cfg::W) == cfg::W {
+                byte &= !cfg::W;
+            }
+            self.cfg[entry] = byte;
+        }
+    }
+
+    pub fn read_addr(&self, entry: usize) -> u64 {
+        self.addr[entry]
+    }
+
+    /* Locked entries ignore writes, as does the base of a locked TOR entry. pmpaddr holds
+     * bits 33:2 of the address on RV32 and bits 55:2 on RV64. */
+    pub fn write_addr(&mut self, entry: usize, xlen: Xlen, value: u64) {
+        let locked_top = entry + 1 < ENTRIES
+            && self.locked(entry + 1)
+            && self.matching(entry + 1) == Matching::Tor;
+        if self.locked(entry) || locked_top {
+            return;
+        }
+
+        self.addr[entry] = match xlen {
+            Xlen::Rv32 => value & 0xFFFF_FFFF,
+            Xlen::Rv64 => value & ((1 << 54) - 1),
+        };
+    }
+}
@@ -0,0 +1,579 @@
+use std::cmp::Ordering;
+
+/* IEEE 754 binary32/binary64 arithmetic done on integers, so every RISC-V
+ * rounding mode and exception flag is exact regardless of the host FPU. */
+
+/// fflags bits, as laid out in fcsr.
+pub mod flags {
+    pub const NX: u8 = 1 << 0; // inexact
+    pub const UF: u8 = 1 << 1; // underflow
+    pub const OF: u8 = 1 << 2; // overflow
+    pub const DZ: u8 = 1 << 3; // divide by zero
+    pub const NV: u8 = 1 << 4; // invalid operation
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    NearestEven,
+    TowardZero,
+    Down,
+    Up,
+    NearestMaxMagnitude,
+}
+
+impl RoundingMode {
+    /* Decodes a static rm field / frm value. 0b111 (dynamic) is resolved by the caller. */
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        Some(match bits {
+            0b000 => RoundingMode::NearestEven,
+            0b001 => RoundingMode::TowardZero,
+            0b010 => RoundingMode::Down,
+            0b011 => RoundingMode::Up,
+            0b100 => RoundingMode::NearestMaxMagnitude,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatFormat {
+    exp_bits: u32,
+    man_bits: u32,
+}
+
+pub const F32: FloatFormat = FloatFormat {
+    exp_bits: 8,
+    man_bits: 23,
+};
+
+pub const F64: FloatFormat = FloatFormat {
+    exp_bits: 11,
+    man_bits: 52,
+};
+
+/// A finite value `(-1)^sign * sig * 2^exp`. `sig == 0` is a signed zero.
+#[derive(Debug, Clone, Copy)]
+struct Part {
+    sign: bool,
+    exp: i32,
+    sig: u128,
+}
+
+enum Class {
+    Finite(Part),
+    Infinite(bool),
+    NaN,
+}
+
+/// Result bits plus the exception flags raised while computing them.
+pub type FloatResult = (u64, u8);
+
+impl FloatFormat {
+    fn bias(self) -> i32 {
+        (1 << (self.exp_bits - 1)) - 1
+    }
+
+    fn max_exp_field(self) -> u64 {
+        (1 << self.exp_bits) - 1
+    }
+
+    fn exp_field(self, bits: u64) -> u64 {
+        (bits >> self.man_bits) & self.max_exp_field()
+    }
+
+    fn man_field(self, bits: u64) -> u64 {
+        bits & ((1 << self.man_bits) - 1)
+    }
+
+    pub fn sign_bit(self) -> u64 {
+        1 << (self.exp_bits + self.man_bits)
+    }
+
+    pub fn canonical_nan(self) -> u64 {
+        (self.max_exp_field() << self.man_bits) | (1 << (self.man_bits - 1))
+    }
+
+    fn infinity(self, sign: bool) -> u64 {
+        (self.max_exp_field() << self.man_bits) | if sign { self.sign_bit() } else { 0 }
+    }
+
+    fn zero(self, sign: bool) -> u64 {
+        if sign { self.sign_bit() } else { 0 }
+    }
+
+    fn max_finite(self, sign: bool) -> u64 {
+        self.infinity(sign) - 1
+    }
+
+    pub fn is_nan(self, bits: u64) -> bool {
+        self.exp_field(bits) == self.max_exp_field() && self.man_field(bits) != 0
+    }
+
+    pub fn is_signaling_nan(self, bits: u64) -> bool {
+        self.is_nan(bits) && bits & (1 << (self.man_bits - 1)) == 0
+    }
+
+    fn is_negative(self, bits: u64) -> bool {
+        bits & self.sign_bit() != 0
+    }
+
+    fn classify(self, bits: u64) -> Class {
+        let sign = self.is_negative(bits);
+        let exp = self.exp_field(bits);
+        let man = self.man_field(bits) as u128;
+
+        if exp == self.max_exp_field() {
+            return if man == 0 {
+                Class::Infinite(sign)
+            } else {
+                Class::NaN
+            };
+        }
+
+        let min_exp = 1 - self.bias() - self.man_bits as i32;
+        Class::Finite(if exp == 0 {
+            Part {
+                sign,
+                exp: min_exp,
+                sig: man,
+            }
+        } else {
+            Part {
+                sign,
+                exp: min_exp + exp as i32 - 1,
+                sig: man | 1 << self.man_bits,
+            }
+        })
+    }
+
+    fn invalid_flag(self, operands: &[u64]) -> u8 {
+        if operands.iter().any(|&bits| self.is_signaling_nan(bits)) {
+            flags::NV
+        } else {
+            0
+        }
+    }
+
+    /* Rounds sig * 2^exp to this format. */
+    fn round_pack(self, sign: bool, exp: i32, sig: u128, mode: RoundingMode) -> FloatResult {
+        if sig == 0 {
+            return (self.zero(sign), 0);
+        }
+
+        let emin = 1 - self.bias();
+        let man = self.man_bits as i32;
+        let top = exp + 127 - sig.leading_zeros() as i32;
+        let lsb = top.max(emin) - man;
+
+        let (kept, inexact) = shift_right_round(sig, lsb - exp, sign, mode);
+
+        let biased = (top.max(emin) + self.bias() - 1) as i64;
+        let overflow = biased >= self.max_exp_field() as i64
+            || (biased << man) as u128 + kept >= (self.max_exp_field() as u128) << man;
+        if overflow {
+            let to_infinity = match mode {
+                RoundingMode::NearestEven | RoundingMode::NearestMaxMagnitude => true,
+                RoundingMode::TowardZero => false,
+                RoundingMode::Down => sign,
+                RoundingMode::Up => !sign,
+            };
+            let bits = if to_infinity {
+                self.infinity(sign)
+            } else {
+                self.max_finite(sign)
+            };
+            return (bits, flags::OF | flags::NX);
+        }
+
+        let bits = ((biased as u64) << man) + kept as u64;
+        let bits = bits | if sign { self.sign_bit() } else { 0 };
+
+        // tininess is detected after rounding, as if the exponent range were unbounded.
+        let tiny = top < emin - 1
+            || (top == emin - 1 && {
+                let (unbounded, _) = shift_right_round(sig, top - man - exp, sign, mode);
+                unbounded < 1 << (man + 1)
+            });
+
+        let mut raised = 0;
+        if inexact {
+            raised |= flags::NX;
+            if tiny {
+                raised |= flags::UF;
+            }
+        }
+
+        (bits, raised)
+    }
+
+    fn add_parts(self, a: Part, b: Part, mode: RoundingMode) -> FloatResult {
+        let exact_zero_sign = mode == RoundingMode::Down;
+
+        match (a.sig, b.sig) {
+            (0, 0) => {
+                let sign = if a.sign == b.sign {
+                    a.sign
+                } else {
+                    exact_zero_sign
+                };
+                return (self.zero(sign), 0);
+            }
+            (0, _) => return self.round_pack(b.sign, b.exp, b.sig, mode),
+            (_, 0) => return self.round_pack(a.sign, a.exp, a.sig, mode),
+            _ => {}
+        }
+
+        // align both operands so the larger one's leading bit sits at bit 125.
+        let top = |p: &Part| p.exp + 127 - p.sig.leading_zeros() as i32;
+        let target = top(&a).max(top(&b)) - 125;
+        let siga = align(a, target);
+        let sigb = align(b, target);
+
+        let (sign, sig) = if a.sign == b.sign {
+            (a.sign, siga + sigb)
+        } else {
+            match siga.cmp(&sigb) {
+                Ordering::Greater => (a.sign, siga - sigb),
+                Ordering::Less => (b.sign, sigb - siga),
+                Ordering::Equal => return (self.zero(exact_zero_sign), 0),
+            }
+        };
+
+        self.round_pack(sign, target, sig, mode)
+    }
+
+    pub fn add(self, a: u64, b: u64, mode: RoundingMode) -> FloatResult {
+        match (self.classify(a), self.classify(b)) {
+            (Class::NaN, _) | (_, Class::NaN) => (self.canonical_nan(), self.invalid_flag(&[a, b])),
+            (Class::Infinite(sa), Class::Infinite(sb)) if sa != sb => {
+                (self.canonical_nan(), flags::NV)
+            }
+            (Class::Infinite(sign), _) | (_, Class::Infinite(sign)) => (self.infinity(sign), 0),
+            (Class::Finite(pa), Class::Finite(pb)) => self.add_parts(pa, pb, mode),
+        }
+    }
+
+    pub fn sub(self, a: u64, b: u64, mode: RoundingMode) -> FloatResult {
+        if self.is_nan(b) {
+            return self.add(a, b, mode);
+        }
+        self.add(a, b ^ self.sign_bit(), mode)
+    }
+
+    pub fn mul(self, a: u64, b: u64, mode: RoundingMode) -> FloatResult {
+        match (self.classify(a), self.classify(b)) {
+            (Class::NaN, _) | (_, Class::NaN) => (self.canonical_nan(), self.invalid_flag(&[a, b])),
+            (Class::Infinite(_), Class::Finite(p)) | (Class::Finite(p), Class::Infinite(_))
+                if p.sig == 0 =>
+            {
+                (self.canonical_nan(), flags::NV)
+            }
+            (Class::Infinite(_), _) | (_, Class::Infinite(_)) => {
+                (self.infinity(self.is_negative(a ^ b)), 0)
+            }
+            (Class::Finite(pa), Class::Finite(pb)) => {
+                self.round_pack(pa.sign != pb.sign, pa.exp + pb.exp, pa.sig * pb.sig, mode)
+            }
+        }
+    }
+
+    pub fn div(self, a: u64, b: u64, mode: RoundingMode) -> FloatResult {
+        let sign = self.is_negative(a ^ b);
+        match (self.classify(a), self.classify(b)) {
+            (Class::NaN, _) | (_, Class::NaN) => (self.canonical_nan(), self.invalid_flag(&[a, b])),
+            (Class::Infinite(_), Class::Infinite(_)) => (self.canonical_nan(), flags::NV),
+            (Class::Infinite(_), _) => (self.infinity(sign), 0),
+            (_, Class::Infinite(_)) => (self.zero(sign), 0),
+            (Class::Finite(pa), Class::Finite(pb)) => match (pa.sig, pb.sig) {
+                (0, 0) => (self.canonical_nan(), flags::NV),
+                (_, 0) => (self.infinity(sign), flags::DZ),
+                (0, _) => (self.zero(sign), 0),
+                _ => {
+                    let shift = pa.sig.leading_zeros() as i32 - 1;
+                    let dividend = pa.sig << shift;
+                    let mut quotient = dividend / pb.sig;
+                    if !dividend.is_multiple_of(pb.sig) {
+                        quotient |= 1;
+                    }
+                    self.round_pack(sign, pa.exp - pb.exp - shift, quotient, mode)
+                }
+            },
+        }
+    }
+
+    pub fn sqrt(self, a: u64, mode: RoundingMode) -> FloatResult {
+        match self.classify(a) {
+            Class::NaN => (self.canonical_nan(), self.invalid_flag(&[a])),
+            Class::Infinite(false) => (a, 0),
+            Class::Finite(p) if p.sig == 0 => (a, 0),
+            Class::Infinite(true) | Class::Finite(Part { sign: true, .. }) => {
+                (self.canonical_nan(), flags::NV)
+            }
+            Class::Finite(p) => {
+                let (mut exp, mut sig) = (p.exp, p.sig);
+                if exp % 2 != 0 {
+                    exp -= 1;
+                    sig <<= 1;
+                }
+                let shift = (sig.leading_zeros() as i32 - 1) & !1;
+                let radicand = sig << shift;
+                let mut root = radicand.isqrt();
+                if root * root != radicand {
+                    root |= 1;
+                }
+                self.round_pack(false, (exp - shift) / 2, root, mode)
+            }
+        }
+    }
+
+    /* (a * b) + c with a single rounding. The negate flags select fmsub/fnmsub/fnmadd. */
+    pub fn fused_mul_add(
+        self,
+        a: u64,
+        b: u64,
+        c: u64,
+        negate_product: bool,
+        negate_addend: bool,
+        mode: RoundingMode,
+    ) -> FloatResult {
+        let (ca, cb, cc) = (self.classify(a), self.classify(b), self.classify(c));
+        let product_sign = self.is_negative(a ^ b) != negate_product;
+        let addend_sign = self.is_negative(c) != negate_addend;
+
+        let zero_times_infinity = matches!(
+            (&ca, &cb),
+            (Class::Infinite(_), Class::Finite(Part { sig: 0, .. }))
+                | (Class::Finite(Part { sig: 0, .. }), Class::Infinite(_))
+        );
+        if zero_times_infinity {
+            return (self.canonical_nan(), flags::NV);
+        }
+        if matches!(ca, Class::NaN) || matches!(cb, Class::NaN) || matches!(cc, Class::NaN) {
+            return (self.canonical_nan(), self.invalid_flag(&[a, b, c]));
+        }
+
+        let product_infinite = matches!(ca, Class::Infinite(_)) || matches!(cb, Class::Infinite(_));
+        match (product_infinite, cc) {
+            (true, Class::Infinite(_)) if product_sign != addend_sign => {
+                (self.canonical_nan(), flags::NV)
+            }
+            (true, _) => (self.infinity(product_sign), 0),
+            (false, Class::Infinite(_)) => (self.infinity(addend_sign), 0),
+            (false, Class::Finite(pc)) => {
+                let (Class::Finite(pa), Class::Finite(pb)) = (ca, cb) else {
+                    unreachable!("infinite and NaN operands handled above")
+                };
+                let product = Part {
+                    sign: product_sign,
+                    exp: pa.exp + pb.exp,
+                    sig: pa.sig * pb.sig,
+                };
+                let addend = Part {
+                    sign: addend_sign,
+                    ..pc
+                };
+                self.add_parts(product, addend, mode)
+            }
+            (false, Class::NaN) => unreachable!("NaN operands handled above"),
+        }
+    }
+
+    /* Compares two values. None means unordered (at least one NaN). */
+    fn compare(self, a: u64, b: u64) -> Option<Ordering> {
+        if self.is_nan(a) || self.is_nan(b) {
+            return None;
+        }
+
+        // magnitudes order like their encodings; both zeros map to 0.
+        let key = |bits: u64| {
+            let magnitude = (bits & !self.sign_bit()) as i128;
+            if self.is_negative(bits) {
+                -magnitude
+            } else {
+                magnitude
+            }
+        };
+
+        Some(key(a).cmp(&key(b)))
+    }
+
+    /* feq: quiet comparison, only signaling NaNs raise NV. */
+    pub fn eq(self, a: u64, b: u64) -> (bool, u8) {
+        let result = self.compare(a, b) == Some(Ordering::Equal);
+        (result, self.invalid_flag(&[a, b]))
+    }
+
+    /* flt: signaling comparison, any NaN raises NV. */
+    pub fn lt(self, a: u64, b: u64) -> (bool, u8) {
+        match self.compare(a, b) {
+            Some(ordering) => (ordering == Ordering::Less, 0),
+            None => (false, flags::NV),
+        }
+    }
+
+    /* fle: signaling comparison, any NaN raises NV. */
+    pub fn le(self, a: u64, b: u64) -> (bool, u8) {
+        match self.compare(a, b) {
+            Some(ordering) => (ordering != Ordering::Greater, 0),
+            None => (false, flags::NV),
+        }
+    }
+
+    /* fmin/fmax: a NaN operand is ignored unless both are NaN; -0 is less than +0. */
+    pub fn min_max(self, a: u64, b: u64, max: bool) -> FloatResult {
+        let raised = self.invalid_flag(&[a, b]);
+        let bits = match (self.is_nan(a), self.is_nan(b)) {
+            (true, true) => self.canonical_nan(),
+            (true, false) => b,
+            (false, true) => a,
+            (false, false) => {
+                let a_first = match self.compare(a, b) {
+                    Some(Ordering::Equal) => self.is_negative(a) != max,
+                    Some(ordering) => (ordering == Ordering::Less) != max,
+                    None => unreachable!("NaN operands handled above"),
+                };
+                if a_first { a } else { b }
+            }
+        };
+
+        (bits, raised)
+    }
+
+    /* fclass: one-hot mask describing the kind of value. */
+    pub fn class_mask(self, bits: u64) -> u32 {
+        let negative = self.is_negative(bits);
+        let bit = match self.classify(bits) {
+            Class::Infinite(true) => 0,
+            Class::Finite(p) if p.sig == 0 => {
+                if negative {
+                    3
+                } else {
+                    4
+                }
+            }
+            Class::Finite(_) if self.exp_field(bits) == 0 => {
+                if negative {
+                    2
+                } else {
+                    5
+                }
+            }
+            Class::Finite(_) => {
+                if negative {
+                    1
+                } else {
+                    6
+                }
+            }
+            Class::Infinite(false) => 7,
+            Class::NaN if self.is_signaling_nan(bits) => 8,
+            Class::NaN => 9,
+        };
+
+        1 << bit
+    }
+
+    /* Converts to a `width`-bit integer. Out of range values and NaNs saturate and raise NV. */
+    pub fn float_to_int(
+        self,
+        bits: u64,
+        signed: bool,
+        width: u32,
+        mode: RoundingMode,
+    ) -> (u64, u8) {
+        let (min, max): (i128, i128) = if signed {
+            (-(1 << (width - 1)), (1 << (width - 1)) - 1)
+        } else {
+            (0, (1 << width) - 1)
+        };
+        let saturate = |negative: bool| {
+            let value = if negative { min } else { max };
+            (value as u64, flags::NV)
+        };
+
+        match self.classify(bits) {
+            Class::NaN => saturate(false),
+            Class::Infinite(sign) => saturate(sign),
+            Class::Finite(p) => {
+                let (magnitude, inexact) = if p.exp >= 0 {
+                    if p.sig != 0 && p.exp + 128 - p.sig.leading_zeros() as i32 > 64 {
+                        return saturate(p.sign);
+                    }
+                    (p.sig << p.exp, false)
+                } else {
+                    shift_right_round(p.sig, -p.exp, p.sign, mode)
+                };
+
+                let value = if p.sign {
+                    -(magnitude as i128)
+                } else {
+                    magnitude as i128
+                };
+                if value < min || value > max {
+                    return saturate(p.sign);
+                }
+
+                (value as u64, if inexact { flags::NX } else { 0 })
+            }
+        }
+    }
+
+    /* Converts a signed or unsigned integer (already extended to i128) to this format. */
+    pub fn int_to_float(self, value: i128, mode: RoundingMode) -> FloatResult {
+        self.round_pack(value < 0, 0, value.unsigned_abs(), mode)
+    }
+
+    /* Converts a value of format `from` to this format. */
+    pub fn convert(self, from: FloatFormat, bits: u64, mode: RoundingMode) -> FloatResult {
+        match from.classify(bits) {
+            Class::NaN => (self.canonical_nan(), from.invalid_flag(&[bits])),
+            Class::Infinite(sign) => (self.infinity(sign), 0),
+            Class::Finite(p) => self.round_pack(p.sign, p.exp, p.sig, mode),
+        }
+    }
+}
+
+/* Moves a part to exponent `target`, folding any bits shifted out into a sticky LSB. */
+fn align(part: Part, target: i32) -> u128 {
+    if part.exp >= target {
+        return part.sig << (part.exp - target);
+    }
+
+    let shift = (target - part.exp) as u32;
+    if shift >= 128 {
+        return (part.sig != 0) as u128;
+    }
+    let lost = part.sig & ((1 << shift) - 1);
+
+    (part.sig >> shift) | (lost != 0) as u128
+}
+
+/* Drops the lowest `shift` bits of sig, rounding the kept part according to mode.
+ * Returns the rounded value and whether any of the dropped bits were set. */
+fn shift_right_round(sig: u128, shift: i32, sign: bool, mode: RoundingMode) -> (u128, bool) {
+    if shift <= 0 {
+        return (sig << -shift, false);
+    }
+
+    let shift = shift as u32;
+    let (kept, remainder, inexact) = match shift {
+        129.. => (0, Ordering::Less, sig != 0),
+        128 => (0, sig.cmp(&(1 << 127)), sig != 0),
+        _ => {
+            let lost = sig & ((1 << shift) - 1);
+            (sig >> shift, lost.cmp(&(1 << (shift - 1))), lost != 0)
+        }
+    };
+
+    let round_up = match mode {
+        RoundingMode::NearestEven => {
+            remainder == Ordering::Greater || (remainder == Ordering::Equal && kept & 1 == 1)
+        }
+        RoundingMode::NearestMaxMagnitude => remainder != Ordering::Less,
+        RoundingMode::TowardZero => false,
+        RoundingMode::Down => inexact && sign,
+        RoundingMode::Up => inexact && !sign,
+    };
+
+    (kept + round_up as u128, inexact)
+}
@@ -0,0 +1,113 @@
+use crate::{isa::Isa, machine::MachineError};
+
+/* Auxiliary vector types, as in Linux's <elf.h>. */
+const AT_NULL: u64 = 0;
+const AT_PAGESZ: u64 = 6;
+const AT_ENTRY: u64 = 9;
+const AT_UID: u64 = 11;
+const AT_EUID: u64 = 12;
+const AT_GID: u64 = 13;
+const AT_EGID: u64 = 14;
+const AT_HWCAP: u64 = 16;
+const AT_SECURE: u64 = 23;
+const AT_RANDOM: u64 = 25;
+const AT_EXECFN: u64 = 31;
+
+const PAGE_SIZE: u64 = 4096;
+
+/// The 16 bytes AT_RANDOM points to, fixed so that runs are reproducible.
+const RANDOM: [u8; 16] = *b"periscvcope-seed";
+
+/// What a program finds on its stack at entry, from sp up to the top of the stack.
+pub(crate) struct InitialStack {
+    pub sp: u64,
+    pub bytes: Vec<u8>,
+}
+
+/* Lays out the initial stack of the RISC-V Linux psABI below top: argc at sp, then the
+ * NULL terminated argv and envp arrays and the auxiliary vector, with the strings they
+ * point to at the top. sp is aligned to align, and pointers are XLEN wide. */
+pub(crate) fn initial_stack(
+    top: u64,
+    size: u64,
+    align: u64,
+    isa: &Isa,
+    entry: u64,
+    args: &[&str],
+    env: &[&str],
+) -> Result<InitialStack, MachineError> {
+    let word = (isa.xlen().bits() / 8) as u64;
+
+    let mut strings = Vec::new();
+    let arg_offsets = push_strings(&mut strings, args);
+    let env_offsets = push_strings(&mut strings, env);
+    let random_offset = strings.len() as u64;
+    strings.extend_from_slice(&RANDOM);
+
+    let auxv_len = 11;
+    let words = 1 + (args.len() + 1) + (env.len() + 1) + 2 * auxv_len;
+    let needed = strings.len() as u64 + word + words as u64 * word + align;
+    if needed > size {
+        return Err(MachineError::ArgumentsTooLarge(needed, size));
+    }
+
+    let strings_start = (top - strings.len() as u64) & !(word - 1);
+    let sp = (strings_start - words as u64 * word) & !(align - 1);
+    let string = |offset: u64| strings_start + offset;
+
+    let mut table = Vec::with_capacity(words);
+    table.push(args.len() as u64);
+    table.extend(arg_offsets.iter().map(|&offset| string(offset)));
+    table.push(0);
+    table.extend(env_offsets.iter().map(|&offset| string(offset)));
+    table.push(0);
+    let auxv = [
+        (AT_PAGESZ, PAGE_SIZE),
+        (AT_ENTRY, entry),
+        (AT_HWCAP, hwcap(isa)),
+        (AT_UID, 0),
+        (AT_EUID, 0),
+        (AT_GID, 0),
+        (AT_EGID, 0),
+        (AT_SECURE, 0),
+        (AT_RANDOM, string(random_offset)),
+        // the file executed, which by convention is also argv[0].
+        (
+            AT_EXECFN,
+            arg_offsets.first().map_or(0, |&offset| string(offset)),
+        ),
+        (AT_NULL, 0),
+    ];
+    debug_assert_eq!(auxv.len(), auxv_len);
+    table.extend(auxv.iter().flat_map(|&(kind, value)| [kind, value]));
+
+    let mut bytes = vec![0; (top - sp) as usize];
+    for (i, value) in table.iter().enumerate() {
+        let start = i * word as usize;
+        bytes[start..start + word as usize].copy_from_slice(&value.to_le_bytes()[..word as usize]);
+    }
+    let start = (strings_start - sp) as usize;
+    bytes[start..start + strings.len()].copy_from_slice(&strings);
+
+    Ok(InitialStack { sp, bytes })
+}
+
+/* Appends each string with its NUL terminator and returns where each starts. */
+fn push_strings(strings: &mut Vec<u8>, list: &[&str]) -> Vec<u64> {
+    list.iter()
+        .map(|s| {
+            let offset = strings.len() as u64;
+            strings.extend_from_slice(s.as_bytes());
+            strings.push(0);
+            offset
+        })
+        .collect()
+}
+
+/* Linux reports the single letter extensions in AT_HWCAP, one bit per letter as in misa. */
+fn hwcap(isa: &Isa) -> u64 {
+    isa.extensions()
+        .map(|extension| extension.to_string())
+        .filter(|name| name.len() == 1)
+        .fold(0, |bits, name| bits | 1 << (name.as_bytes()[0] - b'a'))
+}
@@ -0,0 +1,315 @@
+use std::fmt;
+
+use bilge::prelude::{Integer, u5};
+
+use crate::{
+    instruction::{definitions::Op, formats::InstructionFormat},
+    machine::{
+        Machine, MachineError,
+        csr::{interrupt, status},
+    },
+};
+
+/// Privilege level the hart executes at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Privilege {
+    User = 0,
+    Supervisor = 1,
+    Machine = 3,
+}
+
+impl Privilege {
+    pub(crate) fn from_bits(bits: u64) -> Self {
+        match bits & 0b11 {
+            0 => Privilege::User,
+            1 => Privilege::Supervisor,
+            _ => Privilege::Machine,
+        }
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Privilege::User => "U",
+            Privilege::Supervisor => "S",
+            Privilege::Machine => "M",
+        })
+    }
+}
+
+/// Synchronous exceptions, numbered by their mcause code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Exception {
+    InstructionAddressMisaligned = 0,
+    InstructionAccessFault = 1,
+    IllegalInstruction = 2,
+    Breakpoint = 3,
+    LoadAddressMisaligned = 4,
+    LoadAccessFault = 5,
+    StoreAddressMisaligned = 6,
+    StoreAccessFault = 7,
+    UserEnvironmentCall = 8,
+    SupervisorEnvironmentCall = 9,
+    MachineEnvironmentCall = 11,
+    InstructionPageFault = 12,
+    LoadPageFault = 13,
+    StorePageFault = 15,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Trap {
+    /// An exception and the value written to xtval.
+    Exception(Exception, u64),
+    /// An interrupt number, see `csr::interrupt`.
+    Interrupt(u64),
+}
+
+impl Trap {
+    fn code(self) -> u64 {
+        match self {
+            Trap::Exception(exception, _) => exception as u64,
+            Trap::Interrupt(code) => code,
+        }
+    }
+}
+
+impl MachineError {
+    /* The exception this error raises in the guest, if it is one the guest can handle. */
+    pub(crate) fn trap(&self, privilege: Privilege) -> Option<Trap> {
+        use Exception::*;
+
+        let (exception, tval) = match *self {
+            MachineError::InstructionAddressMisaligned(addr) => {
+                (InstructionAddressMisaligned, addr)
+            }
+            MachineError::InstructionAccessFault(addr) => (InstructionAccessFault, addr),
+            MachineError::LoadAccessFault(addr) => (LoadAccessFault, addr),
+            MachineError::StoreAccessFault(addr) => (StoreAccessFault, addr),
+            MachineError::LoadAddressMisaligned(addr) => (LoadAddressMisaligned, addr),
+            MachineError::StoreAddressMisaligned(addr) => (StoreAddressMisaligned, addr),
+            MachineError::InstructionPageFault(addr) => (InstructionPageFault, addr),
+            MachineError::LoadPageFault(addr) => (LoadPageFault, addr),
+            MachineError::StorePageFault(addr) => (StorePageFault, addr),
+            MachineError::IllegalInstruction(..)
+            | MachineError::IllegalCsrAccess(..)
+            | MachineError::PrivilegedInstruction(..)
+            | MachineError::UnsupportedCsr(..)
+            | MachineError::InvalidRoundingMode(..)
+            | MachineError::FloatingPointOff(..) => (IllegalInstruction, 0),
+            MachineError::Breakpoint(pc) => (Breakpoint, pc),
+            MachineError::EnvironmentCall(_) => {
+                let exception = match privilege {
+                    Privilege::User => UserEnvironmentCall,
+                    Privilege::Supervisor => SupervisorEnvironmentCall,
+                    Privilege::Machine => MachineEnvironmentCall,
+                };
+                (exception, 0)
+            }
+            _ => return None,
+        };
+
+        Some(Trap::Exception(exception, tval))
+    }
+}
+
+impl Machine {
+    /* Traps taken from M-mode, or not delegated, are handled in M-mode. */
+    fn trap_target(&self, trap: Trap) -> Privilege {
+        let delegation = match trap {
+            Trap::Exception(..) => self.csrs.medeleg,
+            Trap::Interrupt(_) => self.csrs.mideleg,
+        };
+
+        if self.privilege < Privilege::Machine && delegation & (1 << trap.code()) != 0 {
+            Privilege::Supervisor
+        } else {
+            Privilege::Machine
+        }
+    }
+
+    /* Whether the guest set up a trap vector for the trap. Traps without one are
+     * reported as errors instead of jumping to address 0. */
+    pub(crate) fn handles(&self, trap: Trap) -> bool {
+        let tvec = match self.trap_target(trap) {
+            Privilege::Supervisor => self.csrs.stvec,
+            _ => self.csrs.mtvec,
+        };
+
+        tvec & !0b11 != 0
+    }
+
+    /* Enters the trap handler, saving the interrupted pc and privilege. */
+    pub(crate) fn take_trap(&mut self, trap: Trap) {
+        let target = self.trap_target(trap);
+        let (interrupt, tval) = match trap {
+            Trap::Exception(_, tval) => (0, self.isa.xlen().zext(tval as i64)),
+            Trap::Interrupt(_) => (1 << (self.isa.xlen().bits() - 1), 0),
+        };
+        let cause = interrupt | trap.code();
+        let csrs = &mut self.csrs;
+
+        let tvec = match target {
+            Privilege::Supervisor => {
+                csrs.sepc = self.pc;
+                csrs.scause = cause;
+                csrs.stval = tval;
+                let spie = if csrs.mstatus & status::SIE != 0 {
+                    status::SPIE
+                } else {
+                    0
+                };
+                let spp = if self.privilege == Privilege::Supervisor {
+                    status::SPP
+                } else {
+                    0
+                };
+                csrs.mstatus =
+                    (csrs.mstatus & !(status::SIE | status::SPIE | status::SPP)) | spie | spp;
+                csrs.stvec
+            }
+            _ => {
+                csrs.mepc = self.pc;
+                csrs.mcause = cause;
+                csrs.mtval = tval;
+                let mpie = if csrs.mstatus & status::MIE != 0 {
+                    status::MPIE
+                } else {
+                    0
+                };
+                csrs.mstatus = (csrs.mstatus & !(status::MIE | status::MPIE)) | mpie;
+                csrs.set_mpp(self.privilege);
+                csrs.mtvec
+            }
+        };
+
+        // vectored mode sends interrupts to base + 4 * cause.
+        let base = tvec & !0b11;
+        self.pc = match trap {
+            Trap::Interrupt(code) if tvec & 0b11 == 1 => base + 4 * code,
+            _ => base,
+        };
+        self.privilege = target;
+        self.memory.clear_reservation();
+        println!("trap {cause:#X} to {target}-mode handler at {:#X}", self.pc);
+    }
+
+    /* The highest priority interrupt that is pending, enabled and not masked by the
+     * current privilege level. */
+    pub(crate) fn pending_interrupt(&self) -> Option<Trap> {
+        let pending = self.csrs.mip & self.csrs.mie;
+        if pending == 0 {
+            return None;
+        }
+
+        let mstatus = self.csrs.mstatus;
+        let machine_enabled = self.privilege < Privilege::Machine || mstatus & status::MIE != 0;
+        let supervisor_enabled = self.privilege < Privilege::Supervisor
+            || (self.privilege == Privilege::Supervisor && mstatus & status::SIE != 0);
+
+        interrupt::PRIORITY
+            .into_iter()
+            .filter(|&code| pending & (1 << code) != 0)
+            .find(|&code| {
+                if self.csrs.mideleg & (1 << code) != 0 {
+                    supervisor_enabled
+                } else {
+                    machine_enabled
+                }
+            })
+            .map(Trap::Interrupt)
+    }
+
+    /* mret: returns to the privilege level saved in MPP. */
+    pub(crate) fn return_from_machine(&mut self) -> Result<u64, MachineError> {
+        if self.privilege < Privilege::Machine {
+            return Err(MachineError::PrivilegedInstruction(
+                Op::mret,
+                self.privilege,
+            ));
+        }
+
+        let csrs = &mut self.csrs;
+        let previous = csrs.mpp();
+        let mie = if csrs.mstatus & status::MPIE != 0 {
+            status::MIE
+        } else {
+            0
+        };
+        csrs.mstatus = (csrs.mstatus & !status::MIE) | mie | status::MPIE;
+        if previous != Privilege::Machine {
+            csrs.mstatus &= !status::MPRV;
+        }
+        csrs.set_mpp(Privilege::User);
+
+        self.privilege = previous;
+        self.memory.clear_reservation();
+        Ok(csrs.mepc)
+    }
+
+    /* sret: returns to the privilege level saved in SPP. */
+    pub(crate) fn return_from_supervisor(&mut self) -> Result<u64, MachineError> {
+        let trapped =
+            self.privilege == Privilege::Supervisor && self.csrs.mstatus & status::TSR != 0;
+        if self.privilege < Privilege::Supervisor || trapped {
+            return Err(MachineError::PrivilegedInstruction(
+                Op::sret,
+                self.privilege,
+            ));
+        }
+
+        let csrs = &mut self.csrs;
+        let previous = if csrs.mstatus & status::SPP != 0 {
+            Privilege::Supervisor
+        } else {
+            Privilege::User
+        };
+        let sie = if csrs.mstatus & status::SPIE != 0 {
+            status::SIE
+        } else {
+            0
+        };
+        csrs.mstatus =
+            (csrs.mstatus & !(status::SIE | status::SPP | status::MPRV)) | sie | status::SPIE;
+
+        self.privilege = previous;
+        self.memory.clear_reservation();
+        Ok(csrs.sepc)
+    }
+
+    /* mret, sret, wfi and sfence.vma. */
+    pub(crate) fn execute_privileged(
+        &mut self,
+        op: &Op,
+        format: &InstructionFormat,
+    ) -> Result<Option<u64>, MachineError> {
+        let mstatus = self.csrs.mstatus;
+        let denied = Err(MachineError::PrivilegedInstruction(*op, self.privilege));
+
+        match op {
+            Op::mret => self.return_from_machine().map(Some),
+            Op::sret => self.return_from_supervisor().map(Some),
+            // no interrupt source is ever waited on, so wfi completes at once.
+            Op::wfi if self.privilege < Privilege::Machine && mstatus & status::TW != 0 => denied,
+            Op::wfi => Ok(None),
+            Op::sfence_vma => {
+                let trapped = self.privilege == Privilege::Supervisor && mstatus & status::TVM != 0;
+                if self.privilege < Privilege::Supervisor || trapped {
+                    return denied;
+                }
+
+                // x0 selects every address or every address space.
+                let register = |field: Option<u5>| {
+                    field
+                        .filter(|reg| reg.value() != 0)
+                        .map(|reg| self.registers[reg.as_usize()])
+                };
+                let vaddr = register(format.rs1()).map(|value| self.isa.xlen().zext(value));
+                let asid = register(format.rs2()).map(|value| (value & 0x1FF) as u16);
+                self.mmu.flush(vaddr, asid);
+                Ok(None)
+            }
+            _ => panic!("executing operation {} as a privileged operation.", op),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Passes arguments and environment variables to programs, and checks the initial stack
+//! against the RISC-V Linux psABI: argc at sp, then argv, envp and the auxiliary vector.
+
+use periscvcope::{
+    assembler::{self, Program},
+    isa::Isa,
+    machine::{Machine, MachineError},
+};
+
+const MAX_STEPS: usize = 10_000;
+
+/* A crt0 reading argc, argv and envp from the stack, and a main returning 100 * argc
+ * plus the length of every argument after argv[0] plus 10 per environment variable. */
+const PROGRAM: &str = "
+        .globl _start
+_start:
+        lw      a0, 0(sp)
+        addi    a1, sp, 4
+        slli    t0, a0, 2
+        add     a2, a1, t0
+        addi    a2, a2, 4
+        call    main
+        slli    a0, a0, 1
+        ori     a0, a0, 1
+        la      t0, tohost
+        sw      a0, 0(t0)
+1:      j       1b
+
+main:
+        li      t0, 100
+        mul     s0, a0, t0
+        addi    a1, a1, 4
+2:      lw      t1, 0(a1)
+        beqz    t1, 4f
+3:      lbu     t2, 0(t1)
+        beqz    t2, 5f
+        addi    s0, s0, 1
+        addi    t1, t1, 1
+        j       3b
+5:      addi    a1, a1, 4
+        j       2b
+4:      lw      t1, 0(a2)
+        beqz    t1, 6f
+        addi    s0, s0, 10
+        addi    a2, a2, 4
+        j       4b
+6:      mv      a0, s0
+        ret
+
+        .data
+        .globl  tohost
+tohost: .word   0, 0
+";
+
+fn program() -> Program {
+    assembler::assemble(PROGRAM).unwrap()
+}
+
+/* The XLEN wide word at addr. */
+fn word(machine: &Machine, addr: u64) -> u64 {
+    let size = (machine.isa().xlen().bits() / 8) as usize;
+    let mut bytes = [0; 8];
+    bytes[..size].copy_from_slice(machine.read_memory(addr, size).unwrap());
+
+    u64::from_le_bytes(bytes)
+}
+
+/* The NUL terminated string at addr. */
+fn string(machine: &Machine, addr: u64) -> String {
+    let mut bytes = Vec::new();
+    for addr in addr.. {
+        match machine.read_memory(addr, 1).unwrap()[0] {
+            0 => break,
+            byte => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8(bytes).unwrap()
+}
+
+/* The strings of a NULL terminated array of pointers, and the address after its NULL. */
+fn strings(machine: &Machine, mut addr: u64) -> (Vec<String>, u64) {
+    let size = (machine.isa().xlen().bits() / 8) as u64;
+    let mut list = Vec::new();
+    loop {
+        let pointer = word(machine, addr);
+        addr += size;
+        if pointer == 0 {
+            return (list, addr);
+        }
+        list.push(string(machine, pointer));
+    }
+}
+
+/* Checks the initial stack and returns the auxiliary vector, without AT_NULL. */
+fn check_stack(machine: &Machine, args: &[&str], env: &[&str]) -> Vec<(u64, u64)> {
+    let size = (machine.isa().xlen().bits() / 8) as u64;
+    let sp = machine.registers()[2] as u64;
+    assert_eq!(sp % 16, 0, "sp is aligned");
+    assert_eq!(word(machine, sp), args.len() as u64, "argc");
+
+    let (argv, envp) = strings(machine, sp + size);
+    assert_eq!(argv, args);
+    let (environment, mut addr) = strings(machine, envp);
+    assert_eq!(environment, env);
+
+    let mut auxv = Vec::new();
+    loop {
+        let (kind, value) = (word(machine, addr), word(machine, addr + size));
+        addr += 2 * size;
+        if kind == 0 {
+            return auxv;
+        }
+        auxv.push((kind, value));
+    }
+}
+
+fn value(auxv: &[(u64, u64)], kind: u64) -> Option<u64> {
+    auxv.iter()
+        .find(|&&(k, _)| k == kind)
+        .map(|&(_, value)| value)
+}
+
+#[test]
+fn main_gets_arguments() {
+    let mut machine = Machine::from_program(&program()).unwrap();
+    machine
+        .set_arguments(&["prog", "hello", "ab"], &["A=1", "B=2"])
+        .unwrap();
+
+    for _ in 0..MAX_STEPS {
+        machine.execute_next_instruction().unwrap();
+        if machine.exit_code().is_some() {
+            break;
+        }
+    }
+    assert_eq!(machine.exit_code(), Some(327));
+}
+
+#[test]
+fn initial_stack() {
+    let program = program();
+    let mut machine = Machine::from_program(&program).unwrap();
+    let args = ["/bin/prog", "-v", ""];
+    let env = ["HOME=/", "TERM=dumb"];
+    machine.set_arguments(&args, &env).unwrap();
+
+    let auxv = check_stack(&machine, &args, &env);
+    assert_eq!(value(&auxv, 6), Some(4096), "AT_PAGESZ");
+    assert_eq!(value(&auxv, 9), Some(program.entry), "AT_ENTRY");
+    let execfn = value(&auxv, 31).expect("AT_EXECFN");
+    assert_eq!(string(&machine, execfn), "/bin/prog");
+    let random = value(&auxv, 25).expect("AT_RANDOM");
+    assert!(machine.read_memory(random, 16).is_ok());
+    // I, M, A, F and D.
+    assert_eq!(value(&auxv, 16), Some(0x1129), "AT_HWCAP");
+
+    // without arguments, there is still argc and an empty argv and envp.
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.set_arguments(&[], &[]).unwrap();
+    let auxv = check_stack(&machine, &[], &[]);
+    assert_eq!(value(&auxv, 31), Some(0));
+}
+
+#[test]
+fn rv64_stack() {
+    let mut machine = Machine::from_program(&program()).unwrap();
+    machine.set_isa("rv64im".parse::<Isa>().unwrap()).unwrap();
+    machine
+        .set_arguments(&["prog", "arg"], &["PATH=/bin"])
+        .unwrap();
+
+    let auxv = check_stack(&machine, &["prog", "arg"], &["PATH=/bin"]);
+    assert_eq!(value(&auxv, 6), Some(4096));
+    assert_eq!(value(&auxv, 16), Some(1 << 8 | 1 << 12));
+}
+
+#[test]
+fn arguments_must_fit_on_the_stack() {
+    let mut machine = Machine::from_program(&program()).unwrap();
+    let sp = machine.registers()[2];
+    let huge = "x".repeat(256 * 1024);
+
+    assert!(matches!(
+        machine.set_arguments(&["prog", &huge], &[]),
+        Err(MachineError::ArgumentsTooLarge(_, 0x40000))
+    ));
+    assert_eq!(machine.registers()[2], sp);
+}
@@ -0,0 +1,152 @@
+//! Runs the M extension on RV32 and RV64, including the cases the spec gives fixed
+//! results for instead of a trap: division by zero and the overflow of MIN / -1.
+
+use periscvcope::{
+    assembler,
+    isa::Isa,
+    machine::{Machine, MachineError},
+};
+
+/* Computes `op a0, a1, a2` on a machine with isa. */
+fn compute(isa: &str, op: &str, a: i64, b: i64) -> Result<i64, MachineError> {
+    let source = format!("li a1, {a}\nli a2, {b}\n{op} a0, a1, a2\n1: j 1b");
+    let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+    machine.set_isa(isa.parse::<Isa>().unwrap()).unwrap();
+    machine.execute_until_loop()?;
+
+    Ok(machine.registers()[10])
+}
+
+/* Checks (a, b, a op b) on RV32. */
+fn check(op: &str, cases: &[(i64, i64, i64)]) {
+    for &(a, b, expected) in cases {
+        assert_eq!(
+            compute("rv32im", op, a, b).unwrap(),
+            expected,
+            "{op} {a}, {b}"
+        );
+    }
+}
+
+const MIN: i64 = i32::MIN as i64;
+const MAX: i64 = i32::MAX as i64;
+
+#[test]
+fn multiplication() {
+    check(
+        "mul",
+        &[
+            (6, 7, 42),
+            (-6, 7, -42),
+            (MIN, -1, MIN),
+            (0x10000, 0x10000, 0),
+        ],
+    );
+    check(
+        "mulh",
+        &[
+            (0x10000, 0x10000, 1),
+            (-1, -1, 0),
+            (MIN, MIN, 0x4000_0000),
+            (-1, 1, -1),
+        ],
+    );
+    check("mulhu", &[(-1, -1, -2), (-1, 2, 1), (MIN, 2, 1)]);
+    // rs1 is signed and rs2 unsigned.
+    check(
+        "mulhsu",
+        &[(-1, -1, -1), (1, -1, 0), (-1, 2, -1), (MAX, 4, 1)],
+    );
+}
+
+#[test]
+fn division() {
+    check(
+        "div",
+        &[(42, 5, 8), (-42, 5, -8), (42, -5, -8), (-42, -5, 8)],
+    );
+    check("divu", &[(42, 5, 8), (-1, 2, MAX), (MIN, 1, MIN)]);
+    // remainders take the sign of the dividend.
+    check(
+        "rem",
+        &[(42, 5, 2), (-42, 5, -2), (42, -5, 2), (-42, -5, -2)],
+    );
+    check("remu", &[(42, 5, 2), (-1, 10, 5)]);
+}
+
+#[test]
+fn division_by_zero() {
+    // the quotient has all bits set and the remainder is the dividend.
+    check("div", &[(42, 0, -1), (-42, 0, -1), (0, 0, -1)]);
+    check("divu", &[(42, 0, -1), (MIN, 0, -1)]);
+    check("rem", &[(42, 0, 42), (-42, 0, -42), (MIN, 0, MIN)]);
+    check("remu", &[(42, 0, 42), (-1, 0, -1)]);
+}
+
+#[test]
+fn signed_overflow() {
+    // MIN / -1 does not fit, the quotient is MIN and the remainder 0.
+    check("div", &[(MIN, -1, MIN)]);
+    check("rem", &[(MIN, -1, 0)]);
+    // as unsigned numbers, they are nothing special.
+    check("divu", &[(MIN, -1, 0)]);
+    check("remu", &[(MIN, -1, MIN)]);
+}
+
+#[test]
+fn rv64() {
+    // the assembler only takes RV32 shift amounts, so 1 << 63 takes three shifts.
+    let source = "
+        li      a1, 1
+        slli    a1, a1, 31
+        slli    a1, a1, 31
+        slli    a1, a1, 1
+        li      a2, -1
+        div     a3, a1, a2
+        rem     a4, a1, a2
+        div     a5, a1, zero
+        rem     a6, a1, zero
+        mulh    a7, a1, a1
+        mulhu   s2, a2, a2
+        li      t0, 0x10000
+        mul     s3, t0, t0
+1:      j       1b
+    ";
+    let mut machine = Machine::from_program(&assembler::assemble(source).unwrap()).unwrap();
+    machine.set_isa("rv64im".parse::<Isa>().unwrap()).unwrap();
+    machine.execute_until_loop().unwrap();
+
+    let registers = machine.registers();
+    assert_eq!(registers[13..=17], [i64::MIN, 0, -1, i64::MIN, 1 << 62]);
+    assert_eq!(registers[18], -2);
+    assert_eq!(registers[19], 1 << 32);
+}
+
+#[test]
+fn m_must_be_enabled() {
+    for op in ["mul", "mulh", "div", "remu"] {
+        assert!(
+            matches!(
+                compute("rv32i", op, 1, 1),
+                Err(MachineError::IllegalInstruction(..))
+            ),
+            "{op}"
+        );
+    }
+}
+
+#[test]
+fn zmmul_does_not_divide() {
+    for op in ["mul", "mulh", "mulhsu", "mulhu"] {
+        assert!(compute("rv32i_zmmul", op, 6, 7).is_ok(), "{op}");
+    }
+    for op in ["div", "divu", "rem", "remu"] {
+        assert!(
+            matches!(
+                compute("rv32i_zmmul", op, 6, 7),
+                Err(MachineError::IllegalInstruction(..))
+            ),
+            "{op}"
+        );
+    }
+}
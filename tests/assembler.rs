@@ -0,0 +1,273 @@
+//! Assembles programs with `periscvcope::assembler` and runs them, and compares the
+//! output for tests/fixtures/assembler/program.s with the image llvm-mc built from it.
+
+use std::fs;
+
+use periscvcope::{
+    assembler::{self, Assembler, Error, Program},
+    file_parser::ElfFile,
+    machine::{Machine, MemoryRegion, Permissions},
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/assembler");
+const MAX_STEPS: usize = 10_000;
+
+/* Runs a program until it exits through tohost or for MAX_STEPS instructions. */
+fn run(program: &Program) -> Machine {
+    let mut machine = Machine::from_program(program).unwrap();
+
+    for _ in 0..MAX_STEPS {
+        machine.execute_next_instruction().unwrap();
+        if machine.exit_code().is_some() {
+            break;
+        }
+    }
+
+    machine
+}
+
+#[test]
+fn matches_llvm_mc() {
+    let source = fs::read_to_string(format!("{FIXTURES}/program.s")).unwrap();
+    let reference = fs::read(format!("{FIXTURES}/program.elf")).unwrap();
+    let file = ElfFile::from_buffer(&reference).unwrap();
+    let memory = file.load_memory(4 * 1024 * 1024).unwrap();
+
+    let program = assembler::assemble(&source).unwrap();
+    for (name, section) in [(".text", &program.text), (".data", &program.data)] {
+        let header = file.find_section_by_name(name).unwrap();
+        assert_eq!(section.base, header.sh_addr, "address of {name}");
+        assert_eq!(section.bytes.len() as u64, header.sh_size, "size of {name}");
+
+        let expected = &memory[section.base as usize..section.end() as usize];
+        for (i, (byte, expected)) in section.bytes.iter().zip(expected).enumerate() {
+            assert_eq!(
+                byte,
+                expected,
+                "byte at {:#x} differs from llvm-mc",
+                section.base + i as u64
+            );
+        }
+    }
+    assert_eq!(program.entry, file.entry_point());
+    for (name, addr) in program.symbols() {
+        assert_eq!(file.symbol(name).unwrap(), Some(addr), "address of {name}");
+    }
+
+    // 11 characters, the counter and fib(10).
+    assert_eq!(run(&program).exit_code(), Some(11 + 42 + 55));
+}
+
+#[test]
+fn registers_and_memory() {
+    let program = assembler::assemble(
+        "
+        .data
+        value:  .word 0x11223344
+        buffer: .zero 8
+
+        .text
+        _start:
+            li   a0, 0x80000000
+            li   a1, -1
+            la   t0, value
+            lw   a2, 0(t0)
+            lb   a3, 0(t0)
+            sh   a1, 4(t0)
+            lui  t1, %hi(buffer+4)
+            sw   a2, %lo(buffer+4)(t1)
+        1:  j 1b
+    ",
+    )
+    .unwrap();
+    let machine = run(&program);
+
+    let registers = machine.registers();
+    assert_eq!(registers[10], i32::MIN as i64);
+    assert_eq!(registers[11], -1);
+    assert_eq!(registers[12], 0x11223344);
+    assert_eq!(registers[13], 0x44);
+    assert_eq!(
+        machine.read_memory(0x2000, 12).unwrap(),
+        [
+            0x44, 0x33, 0x22, 0x11, 0xFF, 0xFF, 0, 0, 0x44, 0x33, 0x22, 0x11
+        ]
+    );
+}
+
+#[test]
+fn exits_through_tohost() {
+    let program = assembler::assemble(
+        "
+        _start:
+            li   a0, 3
+            slli a0, a0, 1
+            ori  a0, a0, 1
+            la   t0, tohost
+            sw   a0, 0(t0)
+        1:  j 1b
+
+        .data
+        tohost:   .dword 0
+        fromhost: .dword 0
+    ",
+    )
+    .unwrap();
+
+    assert_eq!(run(&program).exit_code(), Some(3));
+}
+
+#[test]
+fn counter_names() {
+    // the counter pseudo-instructions read their CSR with csrrs, leaving it unchanged.
+    for (text, csr) in [
+        ("rdcycle a0", 0xC00),
+        ("rdtime a0", 0xC01),
+        ("rdinstret a0", 0xC02),
+        ("rdcycleh a0", 0xC80),
+        ("rdtimeh a0", 0xC81),
+        ("rdinstreth a0", 0xC82),
+        ("csrr a0, mcycle", 0xB00),
+        ("csrr a0, minstreth", 0xB82),
+        ("csrr a0, mcounteren", 0x306),
+        ("csrr a0, scounteren", 0x106),
+    ] {
+        let program = assembler::assemble(text).unwrap();
+        let word = u32::from_le_bytes(program.text.bytes[..4].try_into().unwrap());
+        assert_eq!(word, csr << 20 | 0x2573, "{text}");
+    }
+}
+
+#[test]
+fn layout() {
+    let mut assembler = Assembler::default();
+    assembler.set_text_base(0x4000);
+    assembler.set_data_base(0x8000);
+    let program = assembler
+        .assemble("nop\nstart: .byte 1\n.p2align 3\nend: ret\n.data\nx: .half 2")
+        .unwrap();
+
+    assert_eq!(program.text.base, 0x4000);
+    assert_eq!(program.symbol("start"), Some(0x4004));
+    assert_eq!(program.symbol("end"), Some(0x4008));
+    assert_eq!(program.symbol("x"), Some(0x8000));
+    // _start is not defined, so the program starts at the text base.
+    assert_eq!(program.entry, 0x4000);
+
+    let (base, image) = program.image();
+    assert_eq!(base, 0x4000);
+    assert_eq!(image.len(), 0x4002);
+    assert_eq!(image[4..8], [1, 0, 0, 0]);
+    assert_eq!(image[0x4000..], [2, 0]);
+}
+
+#[test]
+fn bss() {
+    let program = assembler::assemble(
+        "
+        nop
+        .data
+        a: .byte 1
+        .bss
+        .p2align 4
+        b: .zero 6
+        c: .space 2
+        .section .sbss
+        d:
+    ",
+    )
+    .unwrap();
+
+    assert_eq!(program.data.bytes, [1]);
+    assert_eq!(program.bss, 0x2010..0x2018);
+    assert_eq!(program.symbol("b"), Some(0x2010));
+    assert_eq!(program.symbol("c"), Some(0x2016));
+    assert_eq!(program.symbol("d"), Some(0x2018));
+    assert_eq!(program.image().1.len(), 0x1001);
+
+    assert!(matches!(
+        assembler::assemble(".bss\n.word 1"),
+        Err(Error::InitializedBss(2, directive)) if directive == ".word"
+    ));
+    assert!(matches!(
+        assembler::assemble(".bss\nnop"),
+        Err(Error::InitializedBss(2, _))
+    ));
+}
+
+#[test]
+fn elf_loads_back() {
+    let source = fs::read_to_string(format!("{FIXTURES}/program.s")).unwrap();
+    let program = assembler::assemble(&(source + ".bss\nscratch: .zero 32\n")).unwrap();
+    let elf = program.to_elf();
+    let file = ElfFile::from_buffer(&elf).unwrap();
+
+    assert_eq!(file.entry_point(), program.entry);
+    let permissions = |read, write, execute| Permissions {
+        read,
+        write,
+        execute,
+    };
+    assert_eq!(
+        file.memory_regions().unwrap(),
+        [
+            MemoryRegion {
+                start: program.text.base,
+                end: program.text.end(),
+                permissions: permissions(true, false, true),
+            },
+            MemoryRegion {
+                start: program.data.base,
+                end: program.bss.end,
+                permissions: permissions(true, true, false),
+            },
+        ]
+    );
+    for (name, section) in [(".text", &program.text), (".data", &program.data)] {
+        let header = file.find_section_by_name(name).unwrap();
+        assert_eq!(header.sh_addr, section.base, "address of {name}");
+        assert_eq!(header.sh_size, section.bytes.len() as u64, "size of {name}");
+    }
+    let bss = file.find_section_by_name(".bss").unwrap();
+    assert_eq!(bss.sh_addr..bss.sh_addr + bss.sh_size, program.bss);
+
+    let memory = file.load_memory(4 * 1024 * 1024).unwrap();
+    let (base, image) = program.image();
+    assert_eq!(memory[base as usize..][..image.len()], image);
+    for (name, addr) in program.symbols() {
+        assert_eq!(file.symbol(name).unwrap(), Some(addr), "address of {name}");
+    }
+
+    let mut machine = Machine::new(&elf).unwrap();
+    for _ in 0..MAX_STEPS {
+        machine.execute_next_instruction().unwrap();
+        if machine.exit_code().is_some() {
+            break;
+        }
+    }
+    assert_eq!(machine.exit_code(), Some(11 + 42 + 55));
+}
+
+#[test]
+fn errors() {
+    let error = |source: &str| assembler::assemble(source).unwrap_err();
+
+    assert!(matches!(error("nop\nfoo a0"), Error::UnknownInstruction(2, name) if name == "foo"));
+    assert!(matches!(error("addi a0, a9, 1"), Error::InvalidRegister(1, reg) if reg == "a9"));
+    assert!(matches!(error("add a0, a1"), Error::InvalidOperands(1, _)));
+    assert!(matches!(error("j nowhere"), Error::UndefinedSymbol(1, name) if name == "nowhere"));
+    assert!(matches!(error("x: nop\nx: nop"), Error::DuplicateSymbol(2, name) if name == "x"));
+    assert!(matches!(error(".globl main\nnop"), Error::UndefinedSymbol(1, name) if name == "main"));
+    assert!(matches!(error(".quux 1"), Error::UnknownDirective(1, _)));
+    assert!(matches!(error(".byte 256"), Error::ValueOutOfRange(1, _)));
+    assert!(matches!(
+        error("li a0, 0x100000000"),
+        Error::ValueOutOfRange(1, _)
+    ));
+    assert!(matches!(
+        error("fadd.s f0, f1, f2"),
+        Error::UnsupportedInstruction(1, _)
+    ));
+    assert!(matches!(error("addi a0, a0, 2048"), Error::Encoding(1, _)));
+    assert!(matches!(error("beq a0, a1, 3"), Error::Encoding(1, _)));
+}
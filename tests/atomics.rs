@@ -0,0 +1,161 @@
+//! Runs LR/SC and AMO sequences of the A extension and checks the registers and memory
+//! they leave. The assembler takes no A instructions, so they are encoded as `.word`s.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    assembler,
+    instruction::{Instruction, definitions::Op},
+    isa::{Isa, Xlen},
+    machine::{Machine, MachineError},
+};
+
+const T0: u8 = 5;
+const T1: u8 = 6;
+const S0: u8 = 8;
+const S1: u8 = 9;
+const A0: u8 = 10;
+
+/* An A instruction as a .word: op rd, rs2, (rs1). */
+fn atomic(op: Op, rd: u8, rs2: u8, rs1: u8) -> String {
+    let raw =
+        Instruction::encode(op, u5::new(rd), u5::new(rs1), u5::new(rs2), 0, Xlen::Rv32).unwrap();
+
+    format!(".word {raw:#010x}")
+}
+
+fn lr(rd: u8, rs1: u8) -> String {
+    atomic(Op::lr_w, rd, 0, rs1)
+}
+
+fn sc(rd: u8, rs2: u8, rs1: u8) -> String {
+    atomic(Op::sc_w, rd, rs2, rs1)
+}
+
+/* Runs text, followed by a loop to stop at, with data in .data. */
+fn run(text: &str, data: &str) -> Machine {
+    let source = format!("{text}\n1: j 1b\n.data\n{data}");
+    let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+    machine.execute_until_loop().unwrap();
+
+    machine
+}
+
+fn word(machine: &Machine, addr: u64) -> i32 {
+    i32::from_le_bytes(machine.read_memory(addr, 4).unwrap().try_into().unwrap())
+}
+
+#[test]
+fn store_conditional() {
+    let text = [
+        "la s0, value".to_string(),
+        "li t1, 20".into(),
+        // a reserved word is stored once, as SC consumes the reservation.
+        lr(T0, S0),
+        sc(A0, T1, S0),
+        sc(11, T1, S0),
+        // a store overlapping the reservation, even by one byte, invalidates it.
+        lr(T0, S0),
+        "sw zero, 0(s0)".into(),
+        sc(12, T1, S0),
+        lr(T0, S0),
+        "sb zero, 3(s0)".into(),
+        sc(13, T1, S0),
+        // a store elsewhere does not.
+        lr(T0, S0),
+        "sw zero, 8(s0)".into(),
+        sc(14, T1, S0),
+        // nor is another address reserved.
+        lr(T0, S0),
+        "addi s1, s0, 4".into(),
+        sc(15, T1, S1),
+        // an AMO is a store too.
+        lr(T0, S0),
+        atomic(Op::amoadd_w, 0, T1, S0),
+        sc(16, T1, S0),
+    ]
+    .join("\n");
+    let machine = run(&text, "value: .word 10, 0, 0");
+
+    let registers = machine.registers();
+    assert_eq!(registers[A0 as usize..=16], [0, 1, 1, 1, 0, 1, 1]);
+    assert_eq!(registers[T0 as usize], 20);
+    let value = registers[S0 as usize] as u64;
+    assert_eq!(word(&machine, value), 40);
+}
+
+#[test]
+fn memory_operations() {
+    // each op combines -5 in memory with 3, and returns the old value.
+    let cases = [
+        (Op::amoswap_w, 3),
+        (Op::amoadd_w, -2),
+        (Op::amoxor_w, -8),
+        (Op::amoand_w, 3),
+        (Op::amoor_w, -5),
+        (Op::amomin_w, -5),
+        (Op::amomax_w, 3),
+        (Op::amominu_w, 3),
+        (Op::amomaxu_w, -5),
+    ];
+    let mut text = vec!["la s0, words".to_string(), "li t1, 3".into()];
+    for (op, _) in cases {
+        text.push(atomic(op, A0, T1, S0));
+        text.push("addi s0, s0, 4".into());
+    }
+    let data = format!("words: .word {}", vec!["-5"; cases.len()].join(", "));
+    let machine = run(&text.join("\n"), &data);
+
+    assert_eq!(machine.registers()[A0 as usize], -5);
+    let words = machine.registers()[S0 as usize] as u64 - 4 * cases.len() as u64;
+    for (i, (op, expected)) in cases.into_iter().enumerate() {
+        assert_eq!(word(&machine, words + 4 * i as u64), expected, "{op}");
+    }
+}
+
+#[test]
+fn misaligned_addresses_trap() {
+    for (instruction, store) in [
+        (lr(T0, S0), false),
+        (sc(T0, T1, S0), true),
+        (atomic(Op::amoadd_w, T0, T1, S0), true),
+    ] {
+        let source =
+            format!("la s0, value\naddi s0, s0, 2\n{instruction}\n.data\nvalue: .word 0, 0");
+        let program = assembler::assemble(&source).unwrap();
+        let mut machine = Machine::from_program(&program).unwrap();
+        let err = (0..10)
+            .find_map(|_| machine.execute_next_instruction().err())
+            .unwrap();
+
+        // unlike plain loads and stores, atomics are never emulated.
+        let value = program.symbol("value").unwrap();
+        match err {
+            MachineError::LoadAddressMisaligned(addr) if !store => assert_eq!(addr, value + 2),
+            MachineError::StoreAddressMisaligned(addr) if store => assert_eq!(addr, value + 2),
+            err => panic!("{instruction}: {err}"),
+        }
+    }
+}
+
+#[test]
+fn subsets_must_be_enabled() {
+    for (isa, instruction, legal) in [
+        ("rv32ia", lr(T0, S0), true),
+        ("rv32ia", atomic(Op::amoadd_w, T0, T1, S0), true),
+        ("rv32i_zaamo", atomic(Op::amoadd_w, T0, T1, S0), true),
+        ("rv32i_zaamo", lr(T0, S0), false),
+        ("rv32i_zaamo", sc(T0, T1, S0), false),
+        ("rv32i_zalrsc", lr(T0, S0), true),
+        ("rv32i_zalrsc", atomic(Op::amoswap_w, T0, T1, S0), false),
+        ("rv32i", lr(T0, S0), false),
+    ] {
+        let source = format!("la s0, value\n{instruction}\n1: j 1b\n.data\nvalue: .word 0");
+        let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+        machine.set_isa(isa.parse::<Isa>().unwrap()).unwrap();
+        match machine.execute_until_loop() {
+            Ok(_) if legal => {}
+            Err(MachineError::IllegalInstruction(..)) if !legal => {}
+            result => panic!("{isa} {instruction}: {:?}", result.map(|_| ())),
+        }
+    }
+}
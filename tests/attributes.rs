@@ -0,0 +1,200 @@
+//! Builds ELF files with a .riscv.attributes section through the `.attribute` directive,
+//! and checks that what they require is checked against the ISA the machine decodes,
+//! both when loading them and when the ISA changes afterwards.
+
+use periscvcope::{
+    assembler::{self, Assembler},
+    file_parser::{self, ElfFile, RiscvAttributes},
+    instruction::definitions::Extension,
+    isa::{Isa, Xlen},
+    loader::{Elf, Entry, Loader},
+    machine::{Machine, MachineError},
+};
+
+/* An ELF file of a program that multiplies, built for arch. */
+fn elf(arch: &str) -> Vec<u8> {
+    let source = format!(
+        "
+        .attribute arch, \"{arch}\"
+        .attribute stack_align, 16
+        .attribute unaligned_access, 1
+        li      a0, 6
+        li      a1, 7
+        mul     a0, a0, a1
+1:      j       1b
+        "
+    );
+
+    assembler::assemble(&source).unwrap().to_elf()
+}
+
+fn isa(s: &str) -> Isa {
+    s.parse().unwrap()
+}
+
+#[test]
+fn attributes_are_written_and_read() {
+    let data = elf("rv32i2p1_m2p0_zicsr2p0");
+    let attributes = ElfFile::from_buffer(&data)
+        .unwrap()
+        .riscv_attributes()
+        .unwrap();
+    assert_eq!(
+        attributes,
+        RiscvAttributes {
+            stack_align: Some(16),
+            arch: Some("rv32i2p1_m2p0_zicsr2p0".to_string()),
+            unaligned_access: Some(true),
+        }
+    );
+
+    let image = Elf.load(&data).unwrap();
+    assert_eq!(image.isa, Some(isa("rv32im_zicsr")));
+    let [requirements] = image.requirements.as_slice() else {
+        panic!("{:?}", image.requirements);
+    };
+    assert_eq!(requirements.xlen, Xlen::Rv32);
+    assert_eq!(requirements.arch, Some(isa("rv32im_zicsr")));
+    assert!(requirements.unaligned_access);
+
+    let mut machine = Machine::new(&data).unwrap();
+    assert_eq!(machine.isa(), &isa("rv32im_zicsr"));
+    machine.execute_until_loop().unwrap();
+    assert_eq!(machine.registers()[10], 42);
+
+    // without attributes, nothing is written.
+    let data = assembler::assemble("nop").unwrap().to_elf();
+    let file = ElfFile::from_buffer(&data).unwrap();
+    assert_eq!(file.riscv_attributes().unwrap(), RiscvAttributes::default());
+    assert!(file.find_section_by_name(".riscv.attributes").is_err());
+}
+
+#[test]
+fn set_isa_checks_requirements() {
+    let mut machine = Machine::new(&elf("rv32im")).unwrap();
+
+    for (isa, missing) in [
+        (isa("rv32i"), "the m extension (Tag_RISCV_arch)"),
+        // Zmmul has no division, so it does not stand in for M.
+        (isa("rv32i_zmmul"), "the m extension (Tag_RISCV_arch)"),
+        (isa("rv32imafd"), ""),
+    ] {
+        match machine.set_isa(isa.clone()) {
+            Ok(()) if missing.is_empty() => assert_eq!(machine.isa(), &isa),
+            Err(MachineError::ElfError(file_parser::Error::MissingFeature(feature))) => {
+                assert_eq!(feature, missing);
+                // the machine keeps the ISA it had.
+                assert!(machine.isa().supports(Extension::M));
+            }
+            result => panic!("{isa}: {result:?}"),
+        }
+    }
+
+    assert!(matches!(
+        machine.set_isa(isa("rv64im")),
+        Err(MachineError::ElfError(file_parser::Error::XlenMismatch(
+            Xlen::Rv32,
+            Xlen::Rv64
+        )))
+    ));
+    assert_eq!(machine.isa().xlen(), Xlen::Rv32);
+
+    // assembled programs are not loaded from a file, and require nothing.
+    let program = assembler::assemble("nop").unwrap();
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.set_isa(isa("rv64i")).unwrap();
+}
+
+#[test]
+fn every_image_is_checked() {
+    // the machine decodes the ISA of the first image that names one, which lacks M.
+    let mut assembler = Assembler::default();
+    assembler.set_text_base(0x8000);
+    let boot = assembler
+        .assemble(".attribute arch, \"rv32i\"\nli t0, 0x10000\njr t0")
+        .unwrap()
+        .to_elf();
+    assembler.set_text_base(0x10000);
+    assembler.set_data_base(0x18000);
+    let kernel = assembler
+        .assemble(".attribute arch, \"rv32im\"\nmul a0, a0, a0")
+        .unwrap()
+        .to_elf();
+    let images = [Elf.load(&boot).unwrap(), Elf.load(&kernel).unwrap()];
+
+    assert!(matches!(
+        Machine::from_images(&images, &Entry::Image(0)),
+        Err(MachineError::ElfError(file_parser::Error::MissingFeature(
+            _
+        )))
+    ));
+}
+
+#[test]
+fn compressed_is_left_out_of_the_arch() {
+    // the arch names C, but EF_RISCV_RVC is clear: there are no compressed instructions.
+    let data = elf("rv32i2p1_m2p0_c2p0");
+    let image = Elf.load(&data).unwrap();
+    let requirements = &image.requirements[0];
+    assert!(requirements.compressed);
+    assert!(!requirements.flags.rvc);
+    // the rest of the arch is kept, and decides what the machine decodes.
+    assert_eq!(requirements.arch, Some(isa("rv32im")));
+    let extensions: Vec<_> = image.isa.unwrap().extensions().collect();
+    assert_eq!(extensions, [Extension::I, Extension::M, Extension::Zmmul]);
+
+    let mut machine = Machine::new(&data).unwrap();
+    assert_eq!(machine.isa(), &isa("rv32im"));
+    machine.execute_until_loop().unwrap();
+    assert_eq!(machine.registers()[10], 42);
+
+    // and nothing the file does not name is enabled.
+    let machine = Machine::new(&elf("rv32imac_zicsr")).unwrap();
+    assert_eq!(machine.isa(), &isa("rv32ima_zicsr"));
+    assert!(!machine.isa().supports(Extension::F));
+
+    // other unsupported extensions still fail.
+    assert!(matches!(
+        Machine::new(&elf("rv32imv")),
+        Err(MachineError::ElfError(file_parser::Error::UnsupportedIsa(
+            _
+        )))
+    ));
+}
+
+#[test]
+fn stack_align_is_a_power_of_two() {
+    for align in [0, 24] {
+        let section = RiscvAttributes {
+            stack_align: Some(align),
+            ..RiscvAttributes::default()
+        }
+        .to_bytes();
+        assert!(
+            matches!(
+                RiscvAttributes::parse(&section),
+                Err(file_parser::Error::MalformedAttributes(_))
+            ),
+            "{align}"
+        );
+
+        let source = format!(".attribute stack_align, {align}\nnop");
+        assert!(
+            matches!(
+                assembler::assemble(&source),
+                Err(assembler::Error::ValueOutOfRange(1, _))
+            ),
+            "{align}"
+        );
+    }
+
+    let section = RiscvAttributes {
+        stack_align: Some(32),
+        ..RiscvAttributes::default()
+    }
+    .to_bytes();
+    assert_eq!(
+        RiscvAttributes::parse(&section).unwrap().stack_align,
+        Some(32)
+    );
+}
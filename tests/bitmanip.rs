@@ -0,0 +1,143 @@
+//! Runs the Zba, Zbb and Zbs instructions on chosen operands, on RV32 through the
+//! assembler and on RV64 as `.word`s, and checks they are only decoded when enabled.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    assembler,
+    instruction::{Instruction, definitions::Op, formats::Format},
+    isa::{Isa, Xlen},
+    machine::{Machine, MachineError},
+};
+
+/* Runs text on a machine with isa, followed by a loop to stop at. */
+fn run(isa: &str, text: &str) -> Result<Machine, MachineError> {
+    let source = format!("{text}\n1: j 1b");
+    let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+    machine.set_isa(isa.parse::<Isa>().unwrap()).unwrap();
+    machine.execute_until_loop()?;
+
+    Ok(machine)
+}
+
+/* Checks `op a0, a1, a2` on RV32 for each (a1, a2, a0), or `op a0, a1` for unary ops. */
+fn check(op: &str, cases: &[(i64, i64, i64)]) {
+    let unary = [
+        "clz", "ctz", "cpop", "sext.b", "sext.h", "zext.h", "orc.b", "rev8",
+    ];
+    for &(a, b, expected) in cases {
+        let instruction = match op {
+            op if unary.contains(&op) => format!("{op} a0, a1"),
+            op if op.ends_with('i') => format!("{op} a0, a1, {b}"),
+            op => format!("{op} a0, a1, a2"),
+        };
+        let text = format!("li a1, {a}\nli a2, {b}\n{instruction}");
+        let machine = run("rv32i_zba_zbb_zbs", &text).unwrap();
+        assert_eq!(
+            machine.registers()[10],
+            expected,
+            "{instruction} with {a:#x}, {b:#x}"
+        );
+    }
+}
+
+#[test]
+fn address_generation() {
+    check("sh1add", &[(5, 7, 17), (-1, 0, -2)]);
+    check("sh2add", &[(5, 7, 27)]);
+    check("sh3add", &[(5, 7, 47), (0x2000_0000, 1, 1)]);
+}
+
+#[test]
+fn basic_bit_manipulation() {
+    check("andn", &[(0b1100, 0b1010, 0b0100)]);
+    check("orn", &[(0b1100, 0b1010, -3)]);
+    check("xnor", &[(0b1100, 0b1010, -7)]);
+    check("min", &[(-1, 1, -1)]);
+    check("max", &[(-1, 1, 1)]);
+    check("minu", &[(-1, 1, 1)]);
+    check("maxu", &[(-1, 1, -1)]);
+
+    // results are sign-extended from bit 31.
+    check("rol", &[(0x8000_0001, 1, 3), (0x8000_0001, 33, 3)]);
+    check("ror", &[(0x8000_0001, 1, -0x4000_0000), (1, 0, 1)]);
+    check("rori", &[(0x8000_0001, 1, -0x4000_0000), (0x10, 4, 1)]);
+
+    check("clz", &[(0, 0, 32), (1, 0, 31), (-1, 0, 0)]);
+    check("ctz", &[(0, 0, 32), (0x100, 0, 8), (-0x8000_0000, 0, 31)]);
+    check("cpop", &[(0, 0, 0), (0xF0, 0, 4), (-1, 0, 32)]);
+    check("sext.b", &[(0x80, 0, -0x80), (0x17F, 0, 0x7F)]);
+    check("sext.h", &[(0x8000, 0, -0x8000), (0x1_7FFF, 0, 0x7FFF)]);
+    check("zext.h", &[(-1, 0, 0xFFFF)]);
+    check("orc.b", &[(0x0012_0300, 0, 0x00FF_FF00), (0, 0, 0)]);
+    check(
+        "rev8",
+        &[(0x1234_5678, 0, 0x7856_3412), (0x80, 0, -0x8000_0000)],
+    );
+}
+
+#[test]
+fn single_bit_instructions() {
+    check("bset", &[(0, 31, -0x8000_0000), (0, 33, 2)]);
+    check("bclr", &[(-1, 0, -2), (-1, 31, 0x7FFF_FFFF)]);
+    check("binv", &[(5, 2, 1), (5, 1, 7)]);
+    check("bext", &[(-0x8000_0000, 31, 1), (5, 1, 0)]);
+    check("bseti", &[(0, 31, -0x8000_0000)]);
+    check("bclri", &[(-1, 0, -2)]);
+    check("binvi", &[(5, 2, 1)]);
+    check("bexti", &[(4, 2, 1)]);
+}
+
+#[test]
+fn rv64_instructions() {
+    let word = |op: Op, imm: i32| {
+        // immediates and unary ops have no rs2.
+        let rs2 = match (op.format(), op.encoding(Xlen::Rv64).unwrap().rs2) {
+            (Format::R, None) => u5::new(12),
+            _ => u5::new(0),
+        };
+        let raw = Instruction::encode(op, u5::new(10), u5::new(11), rs2, imm, Xlen::Rv64);
+        format!(".word {:#010x}", raw.unwrap())
+    };
+    for (op, imm, a, b, expected) in [
+        (Op::add_uw, 0, -1i64, 1i64, 0x1_0000_0000),
+        (Op::sh1add_uw, 0, -1, 0, 0x1_FFFF_FFFE),
+        (Op::sh3add_uw, 0, 1, 1, 9),
+        (Op::slli_uw, 4, -1, 0, 0xF_FFFF_FFF0),
+        (Op::clz, 0, 1, 0, 63),
+        (Op::clzw, 0, 1, 0, 31),
+        (Op::ctzw, 0, 0, 0, 32),
+        (Op::cpop, 0, -1, 0, 64),
+        (Op::cpopw, 0, -1, 0, 32),
+        (Op::rev8, 0, 0x1234_5678, 0, 0x7856_3412_0000_0000),
+        (Op::rolw, 0, 0x8000_0001, 1, 3),
+        (Op::rorw, 0, 1, 1, -0x8000_0000),
+        (Op::roriw, 1, 1, 0, -0x8000_0000),
+        (Op::bset, 0, 0, 63, i64::MIN),
+    ] {
+        let text = format!("li a1, {a}\nli a2, {b}\n{}", word(op, imm));
+        let machine = run("rv64i_zba_zbb_zbs", &text).unwrap();
+        assert_eq!(
+            machine.registers()[10],
+            expected,
+            "{op} with {a:#x}, {b:#x}"
+        );
+    }
+}
+
+#[test]
+fn extensions_must_be_enabled() {
+    let machine = run("rv32i_zba", "li a1, 1\nsh1add a0, a1, a1").unwrap();
+    assert_eq!(machine.registers()[10], 3);
+
+    for (isa, text) in [
+        ("rv32i_zba", "clz a0, a1"),
+        ("rv32i_zba", "bset a0, a1, a2"),
+        ("rv32i_zbb_zbs", "sh1add a0, a1, a2"),
+        ("rv32im", "andn a0, a1, a2"),
+    ] {
+        assert!(
+            matches!(run(isa, text), Err(MachineError::IllegalInstruction(..))),
+            "{text} on {isa}"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Decodes the encodings in the llvm-mc listings of tests/fixtures/decoder and compares
+//! each instruction with the one the assembler printed for it: the op, every register
+//! operand, the immediate, the rounding mode and the ordering bits.
+
+use std::{collections::HashSet, fs};
+
+use periscvcope::{
+    instruction::{Instruction, definitions::Op, formats::InstructionFormat},
+    isa::Xlen,
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/decoder");
+
+/* Ops whose immediate operand is a shift amount, stored under funct6 or funct7. */
+const SHIFTS: &[Op] = &[
+    Op::slli,
+    Op::srli,
+    Op::srai,
+    Op::slliw,
+    Op::srliw,
+    Op::sraiw,
+    Op::slli_uw,
+    Op::rori,
+    Op::roriw,
+    Op::bclri,
+    Op::bexti,
+    Op::binvi,
+    Op::bseti,
+];
+
+/* pause is `fence w, 0`, which llvm-mc 14 neither assembles nor prints. */
+const PAUSE: u32 = 0x0100_000F;
+
+/// An instruction as the assembler printed it.
+#[derive(Debug)]
+struct Listed {
+    mnemonic: String,
+    registers: Vec<u8>,
+    /// The register of a memory operand, e.g. `-4(x2)`, which is always rs1.
+    base: Option<u8>,
+    immediates: Vec<i64>,
+    rounding_mode: Option<u8>,
+    aq: bool,
+    rl: bool,
+}
+
+fn rounding_mode(name: &str) -> Option<u8> {
+    ["rne", "rtz", "rdn", "rup", "rmm", "", "", "dyn"]
+        .iter()
+        .position(|&mode| mode == name)
+        .map(|mode| mode as u8)
+}
+
+/* The predecessor or successor set of a fence, e.g. iorw. */
+fn fence_set(name: &str) -> i64 {
+    name.chars()
+        .map(|access| match access {
+            'i' => 8,
+            'o' => 4,
+            'r' => 2,
+            'w' => 1,
+            _ => panic!("unknown fence set {name}"),
+        })
+        .sum()
+}
+
+fn register(name: &str) -> Option<u8> {
+    name.strip_prefix(['x', 'f'])?.parse().ok()
+}
+
+/* Parses a listing line such as
+ * `\tsw\tx10, 2047(x11)  # encoding: [0xa3,0xaf,0xa5,0x7e]`. */
+fn parse_line(line: &str) -> Option<(Listed, [u8; 4])> {
+    let (text, encoding) = line.split_once("# encoding:")?;
+
+    let bytes: Vec<u8> = encoding
+        .trim()
+        .trim_matches(['[', ']'])
+        .split(',')
+        .map(|byte| u8::from_str_radix(byte.trim_start_matches("0x"), 16).unwrap())
+        .collect();
+    let bytes = bytes.try_into().expect("instructions should be 4 bytes");
+
+    let mut text = text.split_whitespace();
+    let mut mnemonic = text.next()?.to_string();
+    let operands: Vec<&str> = text.flat_map(|operands| operands.split(',')).collect();
+
+    let (mut aq, mut rl) = (false, false);
+    for (suffix, acquire, release) in [
+        (".aqrl", true, true),
+        (".aq", true, false),
+        (".rl", false, true),
+    ] {
+        if let Some(stripped) = mnemonic.strip_suffix(suffix) {
+            (aq, rl) = (acquire, release);
+            mnemonic = stripped.to_string();
+            break;
+        }
+    }
+
+    let mut listed = Listed {
+        mnemonic,
+        registers: Vec::new(),
+        base: None,
+        immediates: Vec::new(),
+        rounding_mode: None,
+        aq,
+        rl,
+    };
+    for operand in operands.into_iter().filter(|operand| !operand.is_empty()) {
+        if let Some((offset, base)) = operand.strip_suffix(')').and_then(|o| o.split_once('(')) {
+            listed.base = register(base);
+            if !offset.is_empty() {
+                listed.immediates.push(offset.parse().unwrap());
+            }
+        } else if let Some(reg) = register(operand) {
+            listed.registers.push(reg);
+        } else if let Ok(imm) = operand.parse() {
+            listed.immediates.push(imm);
+        } else if listed.mnemonic == "fence" {
+            listed.immediates.push(fence_set(operand));
+        } else if let Some(mode) = rounding_mode(operand) {
+            listed.rounding_mode = Some(mode);
+        } else {
+            panic!("unknown operand {operand:?} in {line:?}");
+        }
+    }
+
+    Some((listed, bytes))
+}
+
+/* Returns what differs between the decoded instruction and the listed one. */
+fn compare(instr: &Instruction, listed: &Listed) -> Vec<String> {
+    let op = instr.op();
+    let format = instr.format();
+    let mut errors = Vec::new();
+    let mut check = |field: &str, decoded: Option<i64>, expected: i64| {
+        if decoded != Some(expected) {
+            errors.push(format!("{field} is {decoded:?}, expected {expected}"));
+        }
+    };
+    let mut unexpected = None;
+
+    if op.to_string() != listed.mnemonic {
+        return vec![format!("decoded as {op}")];
+    }
+
+    // registers are listed as rd, rs1, rs2, rs3, skipping those not in the text.
+    let field = |reg: Option<bilge::prelude::u5>| reg.map(|reg| reg.value() as i64);
+    let mut slots = vec![];
+    if format.rd().is_some() && op != Op::sfence_vma {
+        slots.push(("rd", field(format.rd())));
+    }
+    if listed.base.is_none() {
+        slots.push(("rs1", field(format.rs1())));
+    }
+    slots.push(("rs2", field(format.rs2())));
+    slots.push(("rs3", field(format.rs3())));
+    for (&reg, (name, decoded)) in listed.registers.iter().zip(slots) {
+        check(name, decoded, reg as i64);
+    }
+    if let Some(base) = listed.base {
+        check("rs1", field(format.rs1()), base as i64);
+    }
+
+    let imm = format.immediate_value().map(|imm| imm as i64);
+    match (op, listed.immediates.as_slice()) {
+        (Op::csrrwi | Op::csrrsi | Op::csrrci, &[csr, uimm]) => {
+            check("csr", imm.map(|imm| imm & 0xFFF), csr);
+            check("uimm", field(format.rs1()), uimm);
+        }
+        (Op::csrrw | Op::csrrs | Op::csrrc, &[csr]) => {
+            check("csr", imm.map(|imm| imm & 0xFFF), csr)
+        }
+        (Op::fence, &[pred, succ]) => {
+            check("pred/succ", imm.map(|imm| imm & 0xFF), pred << 4 | succ)
+        }
+        (_, &[shamt]) if SHIFTS.contains(&op) => check("shamt", imm.map(|imm| imm & 0x3F), shamt),
+        (_, &[expected]) => check("imm", imm, expected),
+        (_, &[]) => {}
+        (_, immediates) => unexpected = Some(immediates),
+    }
+
+    if let Some(mode) = listed.rounding_mode {
+        check(
+            "rm",
+            format.funct3().map(|rm| rm.value() as i64),
+            mode as i64,
+        );
+    }
+
+    if let InstructionFormat::A(atype) = format {
+        check("aq", Some(atype.aq().value() as i64), listed.aq as i64);
+        check("rl", Some(atype.rl().value() as i64), listed.rl as i64);
+    }
+
+    if let Some(immediates) = unexpected {
+        errors.push(format!("unexpected immediates {immediates:?}"));
+    }
+
+    errors
+}
+
+/* Decodes every line of a listing, returning the ops seen and the mismatches. */
+fn check_listing(name: &str, xlen: Xlen) -> (HashSet<String>, Vec<String>) {
+    let listing = fs::read_to_string(format!("{FIXTURES}/{name}"))
+        .expect("decoder listing should be readable");
+    let mut seen = HashSet::new();
+    let mut failures = Vec::new();
+
+    for (number, line) in listing.lines().enumerate() {
+        let Some((listed, bytes)) = parse_line(line) else {
+            continue;
+        };
+        let text = line.split('#').next().unwrap().trim().replace('\t', " ");
+        seen.insert(listed.mnemonic.clone());
+
+        match Instruction::from_bytes(bytes, xlen) {
+            Ok(instr) => failures.extend(
+                compare(&instr, &listed)
+                    .into_iter()
+                    .map(|error| format!("{name}:{}: {text}: {error}", number + 1)),
+            ),
+            Err(err) => failures.push(format!("{name}:{}: {text}: {err}", number + 1)),
+        }
+    }
+
+    (seen, failures)
+}
+
+#[test]
+fn rv32_encodings() {
+    let (_, failures) = check_listing("rv32.lst", Xlen::Rv32);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn rv64_encodings() {
+    let (_, failures) = check_listing("rv64.lst", Xlen::Rv64);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn pause() {
+    for xlen in [Xlen::Rv32, Xlen::Rv64] {
+        let instr = Instruction::from_bytes(PAUSE.to_le_bytes(), xlen).unwrap();
+        assert_eq!(instr.op(), Op::pause, "on {xlen}");
+    }
+}
+
+#[test]
+fn listings_cover_every_op() {
+    let (rv32, _) = check_listing("rv32.lst", Xlen::Rv32);
+    let (rv64, _) = check_listing("rv64.lst", Xlen::Rv64);
+
+    let missing: Vec<_> = Op::ALL
+        .iter()
+        .filter(|&&op| op != Op::pause)
+        .map(|op| op.to_string())
+        .filter(|op| !rv32.contains(op) && !rv64.contains(op))
+        .collect();
+
+    assert!(missing.is_empty(), "no encodings of {}", missing.join(", "));
+}
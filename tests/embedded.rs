@@ -0,0 +1,104 @@
+//! Runs programs on RV32E, where only x0-x15 exist: instructions naming x16-x31 in any
+//! register field are illegal, while the f registers are all still there.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    assembler,
+    instruction::{self, Instruction, definitions::Op},
+    isa::{Isa, Xlen},
+    machine::{Machine, MachineError},
+};
+
+/* Runs text on isa up to the loop after it. */
+fn run(isa: &str, text: &str) -> Result<Machine, MachineError> {
+    let source = format!("{text}\n1: j 1b");
+    let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+    machine.set_isa(isa.parse::<Isa>().unwrap()).unwrap();
+    machine.execute_until_loop()?;
+
+    Ok(machine)
+}
+
+#[test]
+fn lower_registers_run() {
+    let machine = run(
+        "rv32em_zicsr",
+        "
+        li      a0, 6
+        li      a5, 7
+        mul     a0, a0, a5
+        sw      a0, -4(sp)
+        lw      t2, -4(sp)
+        csrr    s1, misa
+        ",
+    )
+    .unwrap();
+
+    let registers = machine.registers();
+    assert_eq!(registers[10], 42);
+    assert_eq!(registers[7], 42);
+    // misa reports E instead of I.
+    let misa = registers[9] as u32;
+    assert_eq!(misa & (1 << 4 | 1 << 8), 1 << 4);
+    assert_ne!(misa & 1 << 12, 0);
+}
+
+#[test]
+fn upper_registers_are_illegal() {
+    for (text, op, register) in [
+        ("li a6, 1", Op::addi, 16),
+        ("add a0, a6, a1", Op::add, 16),
+        ("add a0, a1, t6", Op::add, 31),
+        ("addi s2, zero, 1", Op::addi, 18),
+        ("lw a0, 0(s11)", Op::lw, 27),
+        ("sw t3, -4(sp)", Op::sw, 28),
+        ("beq a0, a7, 1f", Op::beq, 17),
+        ("lui t4, 1", Op::lui, 29),
+        ("jal s3, 1f", Op::jal, 19),
+        ("csrw mscratch, s4", Op::csrrw, 20),
+        ("mul x16, x1, x2", Op::mul, 16),
+    ] {
+        let result = run("rv32em_zicsr", &format!("{text}\n1:"));
+        match result {
+            Err(MachineError::IllegalInstruction(
+                pc,
+                instruction::Error::RegisterUnavailable(decoded, index),
+            )) => {
+                assert_eq!(pc, 0x1000, "{text}");
+                assert_eq!((decoded, index), (op, register), "{text}");
+            }
+            result => panic!("{text}: {:?}", result.map(|_| ())),
+        }
+
+        // the same instruction is fine with 32 registers.
+        run("rv32im_zicsr", &format!("{text}\n1:")).unwrap();
+    }
+}
+
+#[test]
+fn float_registers_are_not_restricted() {
+    // fmv.w.x f31, a0; fadd.s f16, f31, f31; fmv.x.w a1, f16
+    let word = |op, rd: u8, rs1: u8, rs2: u8| {
+        let raw = Instruction::encode(op, u5::new(rd), u5::new(rs1), u5::new(rs2), 0, Xlen::Rv32);
+        format!(".word {:#010x}", raw.unwrap())
+    };
+    let text = [
+        "li a0, 0x3F800000".to_string(),
+        word(Op::fmv_w_x, 31, 10, 0),
+        word(Op::fadd_s, 16, 31, 31),
+        word(Op::fmv_x_w, 11, 16, 0),
+    ]
+    .join("\n");
+    let machine = run("rv32ef", &text).unwrap();
+    assert_eq!(machine.registers()[11], 2.0f32.to_bits() as i64);
+
+    // but the x register of a move still is.
+    let text = format!("{}\n1:", word(Op::fmv_x_w, 16, 1, 0));
+    assert!(matches!(
+        run("rv32ef", &text),
+        Err(MachineError::IllegalInstruction(
+            _,
+            instruction::Error::RegisterUnavailable(Op::fmv_x_w, 16)
+        ))
+    ));
+}
@@ -0,0 +1,159 @@
+//! Round trips between `Instruction::encode` and `Instruction::from_bytes`: every word
+//! that decodes is built again from its operands, and every word that encodes decodes
+//! to the op and operands it was built from.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    instruction::{Error, Instruction, definitions::Op},
+    isa::Xlen,
+};
+use proptest::{prelude::*, sample::select};
+
+fn xlen() -> impl Strategy<Value = Xlen> {
+    prop_oneof![Just(Xlen::Rv32), Just(Xlen::Rv64)]
+}
+
+/* Registers, with x0 as often as the others together since most formats leave some
+ * register fields unused. */
+fn register() -> impl Strategy<Value = u5> {
+    prop_oneof![Just(0u8), 0..32u8].prop_map(u5::new)
+}
+
+fn immediate() -> impl Strategy<Value = i32> {
+    prop_oneof![
+        Just(0),
+        0..0x100,
+        -0x1000..0x1000,
+        -0x10_0000..0x10_0000,
+        any::<i32>(),
+    ]
+}
+
+/* The bits an op fixes in its encoding on xlen. */
+fn fixed_bits(op: Op, xlen: Xlen) -> Option<u32> {
+    let encoding = op.encoding(xlen)?;
+    let mask = |fixed: bool, mask: u32| if fixed { mask } else { 0 };
+
+    Some(
+        0x7F | mask(encoding.funct3.is_some(), 0x0000_7000)
+            | mask(encoding.funct7.is_some(), 0xFE00_0000)
+            | mask(encoding.funct6.is_some(), 0xFC00_0000)
+            | mask(encoding.funct5.is_some(), 0xF800_0000)
+            | mask(encoding.funct2.is_some(), 0x0600_0000)
+            | mask(encoding.rs2.is_some(), 0x01F0_0000)
+            | mask(encoding.imm.is_some(), 0xFFF0_0000),
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 2048,
+        max_global_rejects: 1 << 16,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn decoded_words_encode_back(op in select(Op::ALL), xlen in xlen(), bits in any::<u32>()) {
+        let mask = fixed_bits(op, xlen);
+        prop_assume!(mask.is_some());
+
+        // random operands under the fields of op.
+        let base = Instruction::encode(op, u5::new(0), u5::new(0), u5::new(0), 0, xlen).unwrap();
+        let word = base | bits & !mask.unwrap();
+        let instr = Instruction::from_bytes(word.to_le_bytes(), xlen).unwrap();
+
+        let (rd, rs1, rs2, imm) = instr.operands();
+        prop_assert_eq!(
+            Instruction::encode(instr.op(), rd, rs1, rs2, imm, xlen).unwrap(),
+            word,
+            "{} {:?}",
+            instr.op(),
+            instr.operands()
+        );
+    }
+
+    #[test]
+    fn encoded_words_decode_back(
+        op in select(Op::ALL),
+        xlen in xlen(),
+        rd in register(),
+        rs1 in register(),
+        rs2 in register(),
+        imm in immediate(),
+    ) {
+        let encoded = Instruction::encode(op, rd, rs1, rs2, imm, xlen);
+        prop_assume!(encoded.is_ok());
+
+        let instr = Instruction::from_bytes(encoded.unwrap().to_le_bytes(), xlen).unwrap();
+        prop_assert_eq!(instr.op(), op);
+        prop_assert_eq!(instr.operands(), (rd, rs1, rs2, imm));
+    }
+}
+
+fn x(index: u8) -> u5 {
+    u5::new(index)
+}
+
+#[test]
+fn known_encodings() {
+    let encode = |op, rd, rs1, rs2, imm| {
+        Instruction::encode(op, x(rd), x(rs1), x(rs2), imm, Xlen::Rv64).unwrap()
+    };
+
+    assert_eq!(encode(Op::addi, 10, 11, 0, -2048), 0x8005_8513);
+    assert_eq!(encode(Op::sw, 0, 11, 10, 2047), 0x7EA5_AFA3);
+    assert_eq!(encode(Op::beq, 0, 10, 11, -4096), 0x80B5_0063);
+    assert_eq!(encode(Op::jal, 0, 0, 0, 1_048_574), 0x7FFF_F06F);
+    assert_eq!(encode(Op::lui, 10, 0, 0, 0xFFFFF), 0xFFFF_F537);
+    assert_eq!(encode(Op::csrrw, 10, 11, 0, 0x300), 0x3005_9573);
+    assert_eq!(encode(Op::rori, 10, 11, 0, 63), 0x63F5_D513);
+    // fmadd.d f1, f2, f3, f4, rne
+    assert_eq!(encode(Op::fmadd_d, 1, 2, 3, 4 << 3), 0x2231_00C3);
+    // lr.w.aqrl x10, (x11)
+    assert_eq!(encode(Op::lr_w, 10, 11, 0, 0b11), 0x1605_A52F);
+}
+
+#[test]
+fn rejects_invalid_operands() {
+    let encode =
+        |op, rd, rs1, rs2, imm, xlen| Instruction::encode(op, x(rd), x(rs1), x(rs2), imm, xlen);
+
+    assert!(matches!(
+        encode(Op::addi, 1, 2, 0, 2048, Xlen::Rv32),
+        Err(Error::ImmediateOutOfRange(Op::addi, 2048))
+    ));
+    assert!(matches!(
+        encode(Op::beq, 0, 1, 2, 4096, Xlen::Rv32),
+        Err(Error::ImmediateOutOfRange(Op::beq, 4096))
+    ));
+    assert!(matches!(
+        encode(Op::beq, 0, 1, 2, -3, Xlen::Rv32),
+        Err(Error::MisalignedImmediate(Op::beq, -3))
+    ));
+    assert!(matches!(
+        encode(Op::jal, 1, 0, 0, 7, Xlen::Rv32),
+        Err(Error::MisalignedImmediate(Op::jal, 7))
+    ));
+    assert!(matches!(
+        encode(Op::slli, 1, 2, 0, 32, Xlen::Rv32),
+        Err(Error::ImmediateOutOfRange(Op::slli, 32))
+    ));
+    assert!(encode(Op::slli, 1, 2, 0, 32, Xlen::Rv64).is_ok());
+    assert!(matches!(
+        encode(Op::ld, 1, 2, 0, 0, Xlen::Rv32),
+        Err(Error::UnavailableOnXlen(Op::ld, Xlen::Rv32))
+    ));
+    assert!(matches!(
+        encode(Op::lr_w, 1, 2, 3, 0, Xlen::Rv32),
+        Err(Error::UnexpectedOperand(Op::lr_w, "rs2"))
+    ));
+    assert!(matches!(
+        encode(Op::sw, 1, 2, 3, 0, Xlen::Rv32),
+        Err(Error::UnexpectedOperand(Op::sw, "rd"))
+    ));
+    // fence w, 0 is pause.
+    assert!(matches!(
+        encode(Op::fence, 0, 0, 0, 0x010, Xlen::Rv32),
+        Err(Error::EncodedAsOther(Op::fence, Op::pause))
+    ));
+}
@@ -0,0 +1,103 @@
+# Assembled both by llvm-mc and by periscvcope::assembler, which must agree on every
+# byte. Sums the lengths of the strings in the table and a few arithmetic results,
+# then exits through tohost with the total.
+
+        .text
+        .globl _start
+_start:
+        li      sp, 0x3ffff0
+        la      s0, table
+        li      s1, 0                   # total
+1:      lw      a0, 0(s0)
+        beqz    a0, 2f
+        call    strlen
+        add     s1, s1, a0
+        addi    s0, s0, 4
+        j       1b
+
+2:      lui     t0, %hi(counter)
+        lw      t1, %lo(counter)(t0)
+        addi    t1, t1, 1
+        sw      t1, %lo(counter)(t0)
+        add     s1, s1, t1
+
+        li      a0, 10
+        call    fib                     # 55
+        add     s1, s1, a0
+
+        li      t0, 0x12345678
+        li      t1, -2048
+        li      t2, 0x7ff
+        li      t3, 0xfffff000
+        mul     t4, t1, t2
+        divu    t5, t3, t2
+        rem     t6, t0, t2
+        not     t4, t4
+        neg     t5, t5
+        seqz    a1, x0
+        snez    a2, t6
+        sltz    a3, t1
+        sgtz    a4, t2
+        andn    a5, t0, t1
+        sh1add  a6, t1, t2
+        bseti   a7, x0, 31
+        csrr    a1, mhartid
+        csrw    mscratch, s1
+        csrsi   mscratch, 0
+        fence
+        fence.i
+        bgt     a4, zero, 3f
+        ebreak
+3:      ble     a3, a4, 4f
+        ebreak
+4:      bgtu    a4, a3, 5f
+        tail    exit
+5:      ebreak
+
+strlen:
+        mv      t0, a0
+1:      lbu     t1, 0(t0)
+        beqz    t1, 2f
+        addi    t0, t0, 1
+        j       1b
+2:      sub     a0, t0, a0
+        ret
+
+fib:
+        li      t0, 0
+        li      t1, 1
+1:      blez    a0, 2f
+        add     t2, t0, t1
+        mv      t0, t1
+        mv      t1, t2
+        addi    a0, a0, -1
+        j       1b
+2:      mv      a0, t0
+        jr      ra
+
+exit:
+        csrr    a0, mscratch
+        slli    a0, a0, 1
+        ori     a0, a0, 1
+        la      t0, tohost
+        sw      a0, 0(t0)
+        sw      zero, 4(t0)
+1:      j       1b
+
+        .data
+        .p2align 3
+tohost: .dword  0
+fromhost:
+        .dword  0
+counter:
+        .word   41
+table:  .word   hello, world, empty, 0
+hello:  .asciz  "hello"
+world:  .ascii  "wor", "ld\n"
+        .byte   0
+empty:  .string ""
+        .balign 4
+bytes:  .byte   0x7f, -128, 'a', '\n'
+halves: .half   0xffff, -1
+        .zero   3
+end:
@@ -0,0 +1,185 @@
+#!/usr/bin/env python3
+"""Rebuilds the test images in tests/fixtures from their sources.
+
+Each source is assembled with llvm-mc and linked into an ELF32 executable: .text at
+0x80000000 and writable, as upstream's env/p/link.ld places it, .data on the page after
+it, and a symbol table so that tohost and the signature symbols can be found. Only the
+relocations these tests need are applied.
+
+assembler/program.s is linked the same way, but with a read-only .text at 0x1000 as the
+built-in assembler places it, as its reference. It is also converted to Intel HEX,
+S-records and a raw binary for the loaders.
+The decoder sources are only assembled, into listings with the encoding of every line.
+
+usage: tests/fixtures/build.py
+"""
+import os, struct, subprocess, tempfile
+
+FIXTURES = os.path.dirname(os.path.abspath(__file__))
+SUITES = ["self-checking", "signature"]
+SUITE_BASE = 0x80000000
+PF_X, PF_W, PF_R = 1, 2, 4
+
+
+def link(src, out, inc, text_addr, text_flags):
+    with tempfile.NamedTemporaryFile(suffix=".o") as obj:
+        subprocess.check_call(["llvm-mc", "-triple=riscv32", "-mattr=+m,+zba,+zbb,+zbs", "-I", inc,
+                               "-filetype=obj", src, "-o", obj.name])
+        b = obj.read()
+        shoff, = struct.unpack_from("<I", b, 0x20)
+        shentsize, shnum, shstrndx = struct.unpack_from("<HHH", b, 0x2e)
+        secs = [struct.unpack_from("<IIIIIIIIII", b, shoff + i * shentsize) for i in range(shnum)]
+        def cstr(off):
+            return b[off:b.index(b"\0", off)].decode()
+        names = [cstr(secs[shstrndx][4] + s[0]) for s in secs]
+        idx = {n: i for i, n in enumerate(names)}
+
+        text = bytearray(b[secs[idx[".text"]][4]:][:secs[idx[".text"]][5]])
+        data_addr = text_addr + ((len(text) + 0xFFF) & ~0xFFF)
+        data = bytearray()
+        if ".data" in idx:
+            data = bytearray(b[secs[idx[".data"]][4]:][:secs[idx[".data"]][5]])
+        attrs = b""
+        if ".riscv.attributes" in idx:
+            s = secs[idx[".riscv.attributes"]]
+            attrs = b[s[4]:s[4] + s[5]]
+        bases = {idx[".text"]: text_addr}
+        if ".data" in idx:
+            bases[idx[".data"]] = data_addr
+        contents = {idx[".text"]: text}
+        if ".data" in idx:
+            contents[idx[".data"]] = data
+
+        symtab = secs[idx[".symtab"]]
+        strtab = secs[symtab[6]]
+        syms = []
+        for i in range(symtab[5] // 16):
+            name, value, size, info, other, shndx = struct.unpack_from("<IIIBBH", b, symtab[4] + i * 16)
+            syms.append((cstr(strtab[4] + name) if name else "", value, size, info, other, shndx))
+        def sym_addr(i):
+            n, v, _, _, _, shndx = syms[i]
+            if shndx == 0xFFF1:
+                return v
+            if shndx not in bases:
+                raise SystemExit("undefined symbol " + n)
+            return bases[shndx] + v
+
+        def put(buf, off, val):
+            struct.pack_into("<I", buf, off, val & 0xFFFFFFFF)
+        def get(buf, off):
+            return struct.unpack_from("<I", buf, off)[0]
+        def hi20(v):
+            return ((v + 0x800) >> 12) & 0xFFFFF
+
+        pcrel_hi = {}
+        relocs = []
+        for i, n in enumerate(names):
+            if not n.startswith(".rela"):
+                continue
+            target = secs[i][7]
+            if target not in bases:
+                continue
+            s = secs[i]
+            for j in range(s[5] // 12):
+                off, info, addend = struct.unpack_from("<IIi", b, s[4] + j * 12)
+                relocs.append((target, off, info & 0xFF, info >> 8, addend))
+        for target, off, typ, sym, addend in relocs:
+            if typ == 23:
+                p = bases[target] + off
+                pcrel_hi[p] = sym_addr(sym) + addend - p
+        for target, off, typ, sym, addend in relocs:
+            buf, p = contents[target], bases[target] + off
+            S = sym_addr(sym) + addend
+            insn = get(buf, off)
+            if typ == 1:
+                put(buf, off, S)
+            elif typ in (23, 26):
+                v = S - p if typ == 23 else S
+                put(buf, off, (insn & 0xFFF) | hi20(v) << 12)
+            elif typ in (24, 25, 27, 28):
+                v = pcrel_hi[S] if typ in (24, 25) else S
+                lo = v & 0xFFF
+                if typ in (24, 27):
+                    put(buf, off, (insn & 0xFFFFF) | lo << 20)
+                else:
+                    put(buf, off, (insn & 0x1FFF07F) | (lo & 0x1F) << 7 | (lo >> 5) << 25)
+            elif typ == 18:
+                v = S - p
+                put(buf, off, (insn & 0xFFF) | hi20(v) << 12)
+                put(buf, off + 4, (get(buf, off + 4) & 0xFFFFF) | (v & 0xFFF) << 20)
+            elif typ in (16, 17):
+                v = S - p
+                if typ == 16:
+                    enc = ((v >> 12) & 1) << 31 | ((v >> 5) & 0x3F) << 25 | ((v >> 1) & 0xF) << 8 | ((v >> 11) & 1) << 7
+                    put(buf, off, (insn & 0x1FFF07F) | enc)
+                else:
+                    enc = ((v >> 20) & 1) << 31 | ((v >> 1) & 0x3FF) << 21 | ((v >> 11) & 1) << 20 | ((v >> 12) & 0xFF) << 12
+                    put(buf, off, (insn & 0xFFF) | enc)
+            elif typ == 51:
+                pass
+            else:
+                raise SystemExit("unsupported relocation %d" % typ)
+
+        locals_end = 1
+        out_strtab = bytearray(b"\0")
+        out_syms = bytearray(b"\0" * 16)
+        for i, (n, v, size, info, other, shndx) in enumerate(syms):
+            if not n or shndx not in bases and shndx != 0xFFF1:
+                continue
+            out_shndx = {idx[".text"]: 1, idx.get(".data", -1): 2}.get(shndx, 0xFFF1)
+            out_syms += struct.pack("<IIIBBH", len(out_strtab), sym_addr(i), size, info, other, out_shndx)
+            out_strtab += n.encode() + b"\0"
+            if info >> 4 == 0:
+                locals_end = len(out_syms) // 16
+
+        shstr = b"\0.text\0.data\0.shstrtab\0.riscv.attributes\0.symtab\0.strtab\0"
+        off_text, off_data = 0x1000, 0x1000 + (data_addr - text_addr)
+        img = bytearray(b"\x7fELF\x01\x01\x01" + b"\0" * 9)
+        tail = off_data + len(data)
+        sh_off = (tail + 0xF) & ~0xF
+        img += struct.pack("<HHIIIIIHHHHHH", 2, 243, 1, text_addr, 52, sh_off, 0, 52, 32, 2, 40, 7 if attrs else 6, 3)
+        img += struct.pack("<IIIIIIII", 1, off_text, text_addr, text_addr, len(text), len(text), text_flags, 0x1000)
+        img += struct.pack("<IIIIIIII", 1, off_data, data_addr, data_addr, len(data), len(data), 6, 0x1000)
+        img += b"\0" * (off_text - len(img)) + text
+        img += b"\0" * (off_data - len(img)) + data
+        img += b"\0" * (sh_off - len(img))
+        blobs_off = sh_off + (7 if attrs else 6) * 40
+        o_shstr = blobs_off
+        o_attrs = o_shstr + len(shstr)
+        o_syms = (o_attrs + len(attrs) + 3) & ~3
+        o_str = o_syms + len(out_syms)
+        sh = b"\0" * 40
+        sh += struct.pack("<IIIIIIIIII", 1, 1, 6, text_addr, off_text, len(text), 0, 0, 4, 0)
+        sh += struct.pack("<IIIIIIIIII", 7, 1, 3, data_addr, off_data, len(data), 0, 0, 4, 0)
+        sh += struct.pack("<IIIIIIIIII", 13, 3, 0, 0, o_shstr, len(shstr), 0, 0, 1, 0)
+        if attrs:
+            sh += struct.pack("<IIIIIIIIII", 23, 0x70000003, 0, 0, o_attrs, len(attrs), 0, 0, 1, 0)
+        strtab_index = 6 if attrs else 5
+        sh += struct.pack("<IIIIIIIIII", 41, 2, 0, 0, o_syms, len(out_syms), strtab_index, locals_end, 4, 16)
+        sh += struct.pack("<IIIIIIIIII", 49, 3, 0, 0, o_str, len(out_strtab), 0, 0, 1, 0)
+        img += sh + shstr + attrs
+        img += b"\0" * (o_syms - len(img)) + out_syms + out_strtab
+        with open(out, "wb") as f:
+            f.write(img)
+
+
+for suite in SUITES:
+    root = os.path.join(FIXTURES, suite)
+    for src in sorted(os.listdir(os.path.join(root, "src"))):
+        name = os.path.splitext(src)[0]
+        link(os.path.join(root, "src", src), os.path.join(root, name + ".elf"),
+             os.path.join(root, "env"), SUITE_BASE, PF_R | PF_W | PF_X)
+
+program = os.path.join(FIXTURES, "assembler", "program")
+link(program + ".s", program + ".elf", FIXTURES, 0x1000, PF_R | PF_X)
+subprocess.check_call(["llvm-objcopy", "-O", "ihex", program + ".elf", program + ".hex"])
+subprocess.check_call(["llvm-objcopy", "-O", "binary", program + ".elf", program + ".bin"])
+# the S0 header record holds the input path, so keep it relative.
+subprocess.check_call(["objcopy", "-I", "elf32-little", "-O", "srec", "--srec-forceS3",
+                       "program.elf", "program.srec"], cwd=os.path.dirname(program))
+
+for xlen in (32, 64):
+    src = os.path.join(FIXTURES, "decoder", "rv%d.s" % xlen)
+    subprocess.check_call(["llvm-mc", "-triple=riscv%d" % xlen, "-mattr=+m,+a,+f,+d,+zba,+zbb,+zbs",
+                           "-show-encoding", "-M", "no-aliases", "-M", "numeric", src,
+                           "-o", os.path.splitext(src)[0] + ".lst"])
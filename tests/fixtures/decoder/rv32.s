@@ -0,0 +1,546 @@
+# Decoder test cases for RV32, with the edge cases of every immediate format.
+# build.py assembles this into rv32.lst, which the decoder tests read.
+
+# base integer
+add x1, x2, x3
+add x31, x0, x15
+add x0, x31, x31
+sub x1, x2, x3
+sub x31, x0, x15
+sub x0, x31, x31
+xor x1, x2, x3
+xor x31, x0, x15
+xor x0, x31, x31
+or x1, x2, x3
+or x31, x0, x15
+or x0, x31, x31
+and x1, x2, x3
+and x31, x0, x15
+and x0, x31, x31
+sll x1, x2, x3
+sll x31, x0, x15
+sll x0, x31, x31
+srl x1, x2, x3
+srl x31, x0, x15
+srl x0, x31, x31
+sra x1, x2, x3
+sra x31, x0, x15
+sra x0, x31, x31
+slt x1, x2, x3
+slt x31, x0, x15
+slt x0, x31, x31
+sltu x1, x2, x3
+sltu x31, x0, x15
+sltu x0, x31, x31
+addi x0, x1, 0
+addi x1, x31, -1
+addi x31, x10, 2047
+addi x10, x0, -2048
+addi x0, x1, 1
+xori x0, x1, 0
+xori x1, x31, -1
+xori x31, x10, 2047
+xori x10, x0, -2048
+xori x0, x1, 1
+ori x0, x1, 0
+ori x1, x31, -1
+ori x31, x10, 2047
+ori x10, x0, -2048
+ori x0, x1, 1
+andi x0, x1, 0
+andi x1, x31, -1
+andi x31, x10, 2047
+andi x10, x0, -2048
+andi x0, x1, 1
+slti x0, x1, 0
+slti x1, x31, -1
+slti x31, x10, 2047
+slti x10, x0, -2048
+slti x0, x1, 1
+sltiu x0, x1, 0
+sltiu x1, x31, -1
+sltiu x31, x10, 2047
+sltiu x10, x0, -2048
+sltiu x0, x1, 1
+slli x0, x1, 0
+slli x1, x31, 1
+slli x31, x10, 31
+slli x10, x0, 17
+srli x0, x1, 0
+srli x1, x31, 1
+srli x31, x10, 31
+srli x10, x0, 17
+srai x0, x1, 0
+srai x1, x31, 1
+srai x31, x10, 31
+srai x10, x0, 17
+lb x0, 0(x1)
+lb x1, -1(x31)
+lb x31, 2047(x10)
+lb x10, -2048(x0)
+lb x0, 1(x1)
+lh x0, 0(x1)
+lh x1, -1(x31)
+lh x31, 2047(x10)
+lh x10, -2048(x0)
+lh x0, 1(x1)
+lw x0, 0(x1)
+lw x1, -1(x31)
+lw x31, 2047(x10)
+lw x10, -2048(x0)
+lw x0, 1(x1)
+lbu x0, 0(x1)
+lbu x1, -1(x31)
+lbu x31, 2047(x10)
+lbu x10, -2048(x0)
+lbu x0, 1(x1)
+lhu x0, 0(x1)
+lhu x1, -1(x31)
+lhu x31, 2047(x10)
+lhu x10, -2048(x0)
+lhu x0, 1(x1)
+sb x0, 0(x1)
+sb x1, -1(x31)
+sb x31, 2047(x10)
+sb x10, -2048(x0)
+sb x0, 1(x1)
+sh x0, 0(x1)
+sh x1, -1(x31)
+sh x31, 2047(x10)
+sh x10, -2048(x0)
+sh x0, 1(x1)
+sw x0, 0(x1)
+sw x1, -1(x31)
+sw x31, 2047(x10)
+sw x10, -2048(x0)
+sw x0, 1(x1)
+beq x0, x1, 0
+beq x1, x31, -2
+beq x31, x10, 2
+beq x10, x0, 4094
+beq x0, x1, -4096
+beq x1, x31, 2048
+beq x31, x10, -2050
+bne x0, x1, 0
+bne x1, x31, -2
+bne x31, x10, 2
+bne x10, x0, 4094
+bne x0, x1, -4096
+bne x1, x31, 2048
+bne x31, x10, -2050
+blt x0, x1, 0
+blt x1, x31, -2
+blt x31, x10, 2
+blt x10, x0, 4094
+blt x0, x1, -4096
+blt x1, x31, 2048
+blt x31, x10, -2050
+bge x0, x1, 0
+bge x1, x31, -2
+bge x31, x10, 2
+bge x10, x0, 4094
+bge x0, x1, -4096
+bge x1, x31, 2048
+bge x31, x10, -2050
+bltu x0, x1, 0
+bltu x1, x31, -2
+bltu x31, x10, 2
+bltu x10, x0, 4094
+bltu x0, x1, -4096
+bltu x1, x31, 2048
+bltu x31, x10, -2050
+bgeu x0, x1, 0
+bgeu x1, x31, -2
+bgeu x31, x10, 2
+bgeu x10, x0, 4094
+bgeu x0, x1, -4096
+bgeu x1, x31, 2048
+bgeu x31, x10, -2050
+lui x0, 0
+lui x1, 1
+lui x31, 1048575
+lui x10, 524288
+lui x0, 524287
+auipc x0, 0
+auipc x1, 1
+auipc x31, 1048575
+auipc x10, 524288
+auipc x0, 524287
+jal x0, 0
+jal x1, -2
+jal x31, 2
+jal x10, 1048574
+jal x0, -1048576
+jal x1, 2048
+jal x31, -4098
+jalr x0, 0(x1)
+jalr x1, -1(x31)
+jalr x31, 2047(x10)
+jalr x10, -2048(x0)
+jalr x0, 1(x1)
+fence iorw, iorw
+fence r, w
+fence io, o
+fence.tso
+ecall
+ebreak
+sret
+wfi
+mret
+sfence.vma x0, x0
+sfence.vma x10, x11
+sfence.vma x31, x0
+# M
+mul x1, x2, x3
+mul x31, x0, x15
+mul x0, x31, x31
+mulh x1, x2, x3
+mulh x31, x0, x15
+mulh x0, x31, x31
+mulhsu x1, x2, x3
+mulhsu x31, x0, x15
+mulhsu x0, x31, x31
+mulhu x1, x2, x3
+mulhu x31, x0, x15
+mulhu x0, x31, x31
+div x1, x2, x3
+div x31, x0, x15
+div x0, x31, x31
+divu x1, x2, x3
+divu x31, x0, x15
+divu x0, x31, x31
+rem x1, x2, x3
+rem x31, x0, x15
+rem x0, x31, x31
+remu x1, x2, x3
+remu x31, x0, x15
+remu x0, x31, x31
+# A
+lr.w x0, (x1)
+sc.w x0, x31, (x1)
+amoswap.w x0, x31, (x1)
+amoadd.w x0, x31, (x1)
+amoxor.w x0, x31, (x1)
+amoand.w x0, x31, (x1)
+amoor.w x0, x31, (x1)
+amomin.w x0, x31, (x1)
+amomax.w x0, x31, (x1)
+amominu.w x0, x31, (x1)
+amomaxu.w x0, x31, (x1)
+lr.w.aq x1, (x31)
+sc.w.aq x1, x10, (x31)
+amoswap.w.aq x1, x10, (x31)
+amoadd.w.aq x1, x10, (x31)
+amoxor.w.aq x1, x10, (x31)
+amoand.w.aq x1, x10, (x31)
+amoor.w.aq x1, x10, (x31)
+amomin.w.aq x1, x10, (x31)
+amomax.w.aq x1, x10, (x31)
+amominu.w.aq x1, x10, (x31)
+amomaxu.w.aq x1, x10, (x31)
+lr.w.rl x31, (x10)
+sc.w.rl x31, x0, (x10)
+amoswap.w.rl x31, x0, (x10)
+amoadd.w.rl x31, x0, (x10)
+amoxor.w.rl x31, x0, (x10)
+amoand.w.rl x31, x0, (x10)
+amoor.w.rl x31, x0, (x10)
+amomin.w.rl x31, x0, (x10)
+amomax.w.rl x31, x0, (x10)
+amominu.w.rl x31, x0, (x10)
+amomaxu.w.rl x31, x0, (x10)
+lr.w.aqrl x10, (x0)
+sc.w.aqrl x10, x1, (x0)
+amoswap.w.aqrl x10, x1, (x0)
+amoadd.w.aqrl x10, x1, (x0)
+amoxor.w.aqrl x10, x1, (x0)
+amoand.w.aqrl x10, x1, (x0)
+amoor.w.aqrl x10, x1, (x0)
+amomin.w.aqrl x10, x1, (x0)
+amomax.w.aqrl x10, x1, (x0)
+amominu.w.aqrl x10, x1, (x0)
+amomaxu.w.aqrl x10, x1, (x0)
+# S
+flw f0, 0(x1)
+fsw f0, 0(x1)
+flw f1, -1(x31)
+fsw f1, -1(x31)
+flw f31, 2047(x10)
+fsw f31, 2047(x10)
+flw f10, -2048(x0)
+fsw f10, -2048(x0)
+flw f0, 1(x1)
+fsw f0, 1(x1)
+fmadd.s f0, f8, f31, f16
+fmadd.s f1, f9, f30, f17, rne
+fmadd.s f2, f10, f29, f18, rtz
+fmsub.s f0, f8, f31, f16
+fmsub.s f1, f9, f30, f17, rne
+fmsub.s f2, f10, f29, f18, rtz
+fnmsub.s f0, f8, f31, f16
+fnmsub.s f1, f9, f30, f17, rne
+fnmsub.s f2, f10, f29, f18, rtz
+fnmadd.s f0, f8, f31, f16
+fnmadd.s f1, f9, f30, f17, rne
+fnmadd.s f2, f10, f29, f18, rtz
+fadd.s f0, f31, f7
+fadd.s f1, f30, f8, rne
+fadd.s f2, f29, f9, rtz
+fadd.s f3, f28, f10, rdn
+fadd.s f4, f27, f11, rup
+fadd.s f5, f26, f12, rmm
+fadd.s f6, f25, f13, dyn
+fsub.s f0, f31, f7
+fsub.s f1, f30, f8, rne
+fsub.s f2, f29, f9, rtz
+fsub.s f3, f28, f10, rdn
+fsub.s f4, f27, f11, rup
+fsub.s f5, f26, f12, rmm
+fsub.s f6, f25, f13, dyn
+fmul.s f0, f31, f7
+fmul.s f1, f30, f8, rne
+fmul.s f2, f29, f9, rtz
+fmul.s f3, f28, f10, rdn
+fmul.s f4, f27, f11, rup
+fmul.s f5, f26, f12, rmm
+fmul.s f6, f25, f13, dyn
+fdiv.s f0, f31, f7
+fdiv.s f1, f30, f8, rne
+fdiv.s f2, f29, f9, rtz
+fdiv.s f3, f28, f10, rdn
+fdiv.s f4, f27, f11, rup
+fdiv.s f5, f26, f12, rmm
+fdiv.s f6, f25, f13, dyn
+fsqrt.s f0, f31
+fsqrt.s f1, f30, rne
+fsqrt.s f2, f29, rtz
+fsgnj.s f0, f31, f7
+fsgnj.s f31, f1, f0
+fsgnjn.s f0, f31, f7
+fsgnjn.s f31, f1, f0
+fsgnjx.s f0, f31, f7
+fsgnjx.s f31, f1, f0
+fmin.s f0, f31, f7
+fmin.s f31, f1, f0
+fmax.s f0, f31, f7
+fmax.s f31, f1, f0
+fcvt.w.s x1, f31, rtz
+fcvt.w.s x31, f0
+fcvt.s.w f1, x31, rmm
+fcvt.s.w f31, x0
+fcvt.wu.s x1, f31, rtz
+fcvt.wu.s x31, f0
+fcvt.s.wu f1, x31, rmm
+fcvt.s.wu f31, x0
+feq.s x1, f2, f3
+feq.s x31, f31, f0
+flt.s x1, f2, f3
+flt.s x31, f31, f0
+fle.s x1, f2, f3
+fle.s x31, f31, f0
+fclass.s x10, f11
+fmv.x.w x10, f11
+fmv.w.x f10, x11
+# D
+fld f0, 0(x1)
+fsd f0, 0(x1)
+fld f1, -1(x31)
+fsd f1, -1(x31)
+fld f31, 2047(x10)
+fsd f31, 2047(x10)
+fld f10, -2048(x0)
+fsd f10, -2048(x0)
+fld f0, 1(x1)
+fsd f0, 1(x1)
+fmadd.d f0, f8, f31, f16
+fmadd.d f1, f9, f30, f17, rne
+fmadd.d f2, f10, f29, f18, rtz
+fmsub.d f0, f8, f31, f16
+fmsub.d f1, f9, f30, f17, rne
+fmsub.d f2, f10, f29, f18, rtz
+fnmsub.d f0, f8, f31, f16
+fnmsub.d f1, f9, f30, f17, rne
+fnmsub.d f2, f10, f29, f18, rtz
+fnmadd.d f0, f8, f31, f16
+fnmadd.d f1, f9, f30, f17, rne
+fnmadd.d f2, f10, f29, f18, rtz
+fadd.d f0, f31, f7
+fadd.d f1, f30, f8, rne
+fadd.d f2, f29, f9, rtz
+fadd.d f3, f28, f10, rdn
+fadd.d f4, f27, f11, rup
+fadd.d f5, f26, f12, rmm
+fadd.d f6, f25, f13, dyn
+fsub.d f0, f31, f7
+fsub.d f1, f30, f8, rne
+fsub.d f2, f29, f9, rtz
+fsub.d f3, f28, f10, rdn
+fsub.d f4, f27, f11, rup
+fsub.d f5, f26, f12, rmm
+fsub.d f6, f25, f13, dyn
+fmul.d f0, f31, f7
+fmul.d f1, f30, f8, rne
+fmul.d f2, f29, f9, rtz
+fmul.d f3, f28, f10, rdn
+fmul.d f4, f27, f11, rup
+fmul.d f5, f26, f12, rmm
+fmul.d f6, f25, f13, dyn
+fdiv.d f0, f31, f7
+fdiv.d f1, f30, f8, rne
+fdiv.d f2, f29, f9, rtz
+fdiv.d f3, f28, f10, rdn
+fdiv.d f4, f27, f11, rup
+fdiv.d f5, f26, f12, rmm
+fdiv.d f6, f25, f13, dyn
+fsqrt.d f0, f31
+fsqrt.d f1, f30, rne
+fsqrt.d f2, f29, rtz
+fsgnj.d f0, f31, f7
+fsgnj.d f31, f1, f0
+fsgnjn.d f0, f31, f7
+fsgnjn.d f31, f1, f0
+fsgnjx.d f0, f31, f7
+fsgnjx.d f31, f1, f0
+fmin.d f0, f31, f7
+fmin.d f31, f1, f0
+fmax.d f0, f31, f7
+fmax.d f31, f1, f0
+fcvt.w.d x1, f31, rtz
+fcvt.w.d x31, f0
+fcvt.d.w f1, x31
+fcvt.d.w f31, x0
+fcvt.wu.d x1, f31, rtz
+fcvt.wu.d x31, f0
+fcvt.d.wu f1, x31
+fcvt.d.wu f31, x0
+feq.d x1, f2, f3
+feq.d x31, f31, f0
+flt.d x1, f2, f3
+flt.d x31, f31, f0
+fle.d x1, f2, f3
+fle.d x31, f31, f0
+fclass.d x10, f11
+fcvt.s.d f1, f2, rup
+fcvt.s.d f31, f0
+fcvt.d.s f1, f2
+fcvt.d.s f31, f0
+# Zicsr
+csrrw x0, 1472, x1
+csrrs x0, 1472, x1
+csrrc x0, 1472, x1
+csrrwi x0, 1472, 0
+csrrsi x0, 1472, 0
+csrrci x0, 1472, 0
+csrrw x1, 1984, x31
+csrrs x1, 1984, x31
+csrrc x1, 1984, x31
+csrrwi x1, 1984, 1
+csrrsi x1, 1984, 1
+csrrci x1, 1984, 1
+csrrw x31, 2047, x10
+csrrs x31, 2047, x10
+csrrc x31, 2047, x10
+csrrwi x31, 2047, 31
+csrrsi x31, 2047, 31
+csrrci x31, 2047, 31
+csrrw x10, 2048, x0
+csrrs x10, 2048, x0
+csrrc x10, 2048, x0
+csrrwi x10, 2048, 16
+csrrsi x10, 2048, 16
+csrrci x10, 2048, 16
+csrrw x0, 4095, x1
+csrrs x0, 4095, x1
+csrrc x0, 4095, x1
+csrrwi x0, 4095, 5
+csrrsi x0, 4095, 5
+csrrci x0, 4095, 5
+# Zifencei
+fence.i
+# Zba
+sh1add x1, x2, x3
+sh1add x31, x0, x15
+sh1add x0, x31, x31
+sh2add x1, x2, x3
+sh2add x31, x0, x15
+sh2add x0, x31, x31
+sh3add x1, x2, x3
+sh3add x31, x0, x15
+sh3add x0, x31, x31
+# Zbb
+andn x1, x2, x3
+andn x31, x0, x15
+andn x0, x31, x31
+orn x1, x2, x3
+orn x31, x0, x15
+orn x0, x31, x31
+xnor x1, x2, x3
+xnor x31, x0, x15
+xnor x0, x31, x31
+min x1, x2, x3
+min x31, x0, x15
+min x0, x31, x31
+minu x1, x2, x3
+minu x31, x0, x15
+minu x0, x31, x31
+max x1, x2, x3
+max x31, x0, x15
+max x0, x31, x31
+maxu x1, x2, x3
+maxu x31, x0, x15
+maxu x0, x31, x31
+rol x1, x2, x3
+rol x31, x0, x15
+rol x0, x31, x31
+ror x1, x2, x3
+ror x31, x0, x15
+ror x0, x31, x31
+zext.h x1, x2
+zext.h x31, x0
+clz x1, x2
+clz x31, x0
+ctz x1, x2
+ctz x31, x0
+cpop x1, x2
+cpop x31, x0
+sext.b x1, x2
+sext.b x31, x0
+sext.h x1, x2
+sext.h x31, x0
+orc.b x1, x2
+orc.b x31, x0
+rev8 x1, x2
+rev8 x31, x0
+rori x0, x1, 0
+rori x1, x31, 1
+rori x31, x10, 31
+rori x10, x0, 17
+# Zbs
+bclr x1, x2, x3
+bclr x31, x0, x15
+bclr x0, x31, x31
+bext x1, x2, x3
+bext x31, x0, x15
+bext x0, x31, x31
+binv x1, x2, x3
+binv x31, x0, x15
+binv x0, x31, x31
+bset x1, x2, x3
+bset x31, x0, x15
+bset x0, x31, x31
+bclri x0, x1, 0
+bclri x1, x31, 1
+bclri x31, x10, 31
+bclri x10, x0, 17
+bexti x0, x1, 0
+bexti x1, x31, 1
+bexti x31, x10, 31
+bexti x10, x0, 17
+binvi x0, x1, 0
+binvi x1, x31, 1
+binvi x31, x10, 31
+binvi x10, x0, 17
+bseti x0, x1, 0
+bseti x1, x31, 1
+bseti x31, x10, 31
+bseti x10, x0, 17
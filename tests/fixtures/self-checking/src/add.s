@@ -0,0 +1,43 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, add, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, add, 0x00000002, 0x00000001, 0x00000001
+    TEST_RR_OP 4, add, 0x0000000a, 0x00000003, 0x00000007
+    TEST_RR_OP 5, add, 0xffff8000, 0x00000000, 0xffff8000
+    TEST_RR_OP 6, add, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 7, add, 0x7fff8000, 0x80000000, 0xffff8000
+    TEST_RR_OP 8, add, 0x00007fff, 0x00000000, 0x00007fff
+    TEST_RR_OP 9, add, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 10, add, 0x80007ffe, 0x7fffffff, 0x00007fff
+    TEST_RR_OP 11, add, 0x80007fff, 0x80000000, 0x00007fff
+    TEST_RR_OP 12, add, 0x7fff7fff, 0x7fffffff, 0xffff8000
+    TEST_RR_OP 13, add, 0xffffffff, 0x00000000, 0xffffffff
+    TEST_RR_OP 14, add, 0x00000000, 0xffffffff, 0x00000001
+    TEST_RR_OP 15, add, 0xfffffffe, 0xffffffff, 0xffffffff
+    TEST_RR_OP 16, add, 0x80000000, 0x00000001, 0x7fffffff
+    TEST_RR_OP 17, add, 0x0e100e0f, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 18, add, 0x00e100e0, 0x0ff00ff0, 0xf0f0f0f0
+    TEST_RR_OP 19, add, 0x100e100e, 0x00ff00ff, 0x0f0f0f0f
+    TEST_RR_OP 20, add, 0xe100e0ff, 0xf00ff00f, 0xf0f0f0f0
+    TEST_RR_SRC1_EQ_DEST 21, add, 0x0000000a, 0x00000003, 0x00000007
+    TEST_RR_SRC2_EQ_DEST 22, add, 0xffff8000, 0x00000000, 0xffff8000
+    TEST_RR_SRC12_EQ_DEST 23, add, 0x0000001a, 0x0000000d
+    TEST_RR_DEST_BYPASS 24, 0, add, 0x00007fff, 0x00000000, 0x00007fff
+    TEST_RR_DEST_BYPASS 25, 1, add, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_DEST_BYPASS 26, 2, add, 0x80007ffe, 0x7fffffff, 0x00007fff
+    TEST_RR_ZEROSRC1 27, add, 0x0000000f, 0x0000000f
+    TEST_RR_ZEROSRC2 28, add, 0x00000020, 0x00000020
+    TEST_RR_ZEROSRC12 29, add, 0x00000000
+    TEST_RR_ZERODEST 30, add, 0x00000010, 0x0000001e
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
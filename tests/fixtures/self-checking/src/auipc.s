@@ -0,0 +1,31 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    li gp, 2
+1:
+    auipc a0, 2
+    jal a1, 2f
+2:
+    sub x14, a0, a1
+    addi x14, x14, 8
+    TEST_CHECK 8192
+
+    li gp, 3
+1:
+    auipc a0, 0xfffff
+    jal a1, 2f
+2:
+    sub x14, a0, a1
+    addi x14, x14, 8
+    TEST_CHECK -4096
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
@@ -0,0 +1,26 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_BR2_OP_TAKEN 2, beq, 0x00000000, 0x00000000
+    TEST_BR2_OP_TAKEN 3, beq, 0x00000001, 0x00000001
+    TEST_BR2_OP_TAKEN 4, beq, 0xffffffff, 0xffffffff
+    TEST_BR2_OP_NOTTAKEN 5, beq, 0x00000000, 0x00000001
+    TEST_BR2_OP_NOTTAKEN 6, beq, 0x00000001, 0x00000000
+    TEST_BR2_OP_NOTTAKEN 7, beq, 0xffffffff, 0x00000001
+    TEST_BR2_OP_NOTTAKEN 8, beq, 0x00000001, 0xffffffff
+    TEST_BR2_OP_NOTTAKEN 9, beq, 0x80000000, 0x7fffffff
+    TEST_BR2_OP_NOTTAKEN 10, beq, 0x7fffffff, 0x80000000
+    TEST_BR2_OP_NOTTAKEN 11, beq, 0xfffffffe, 0xffffffff
+    TEST_BR2_OP_NOTTAKEN 12, beq, 0xffffffff, 0xfffffffe
+    TEST_BR2_OP_TAKEN 13, beq, 0x80000000, 0x80000000
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
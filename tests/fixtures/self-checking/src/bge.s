@@ -0,0 +1,26 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_BR2_OP_TAKEN 2, bge, 0x00000000, 0x00000000
+    TEST_BR2_OP_TAKEN 3, bge, 0x00000001, 0x00000001
+    TEST_BR2_OP_TAKEN 4, bge, 0xffffffff, 0xffffffff
+    TEST_BR2_OP_NOTTAKEN 5, bge, 0x00000000, 0x00000001
+    TEST_BR2_OP_TAKEN 6, bge, 0x00000001, 0x00000000
+    TEST_BR2_OP_NOTTAKEN 7, bge, 0xffffffff, 0x00000001
+    TEST_BR2_OP_TAKEN 8, bge, 0x00000001, 0xffffffff
+    TEST_BR2_OP_NOTTAKEN 9, bge, 0x80000000, 0x7fffffff
+    TEST_BR2_OP_TAKEN 10, bge, 0x7fffffff, 0x80000000
+    TEST_BR2_OP_NOTTAKEN 11, bge, 0xfffffffe, 0xffffffff
+    TEST_BR2_OP_TAKEN 12, bge, 0xffffffff, 0xfffffffe
+    TEST_BR2_OP_TAKEN 13, bge, 0x80000000, 0x80000000
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
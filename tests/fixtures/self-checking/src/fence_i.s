@@ -0,0 +1,53 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    li a3, 111
+    lh a0, insn
+    lh a1, insn+2
+    sh a0, 1f, t0
+    sh a1, 1f+2, t0
+    fence.i
+
+    la a5, 1f
+    jalr t1, a5, 0
+    li gp, 2
+    mv x14, a3
+    TEST_CHECK 444
+
+    # the same again, after the old instruction ran many times.
+    li a4, 100
+2:
+    addi a4, a4, -1
+    bnez a4, 2b
+
+    sh a0, 3f, t0
+    sh a1, 3f+2, t0
+    fence.i
+
+    .align 6
+    la a5, 3f
+    jalr t1, a5, 0
+    li gp, 3
+    mv x14, a3
+    TEST_CHECK 777
+
+    TEST_PASSFAIL
+
+1:
+    addi a3, a3, 222
+    jalr a5, t1, 0
+3:
+    addi a3, a3, 555
+    jalr a5, t1, 0
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+insn:
+    addi a3, a3, 333
+
+RVTEST_DATA_END
@@ -0,0 +1,34 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    li gp, 2
+    li ra, 0
+    jal x4, 1f
+2:
+    nop
+    nop
+    j fail
+1:
+    la x2, 2b
+    bne x2, x4, fail
+
+    li gp, 3
+    li x14, 1
+    jal x0, 1f
+    addi x14, x14, 1
+    addi x14, x14, 1
+    addi x14, x14, 1
+1:
+    addi x14, x14, 1
+    TEST_CHECK 2
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
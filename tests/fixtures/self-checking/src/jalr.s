@@ -0,0 +1,51 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    li gp, 2
+    li t0, 0
+    la t1, 1f
+    jalr t0, t1, 0
+3:
+    j fail
+1:
+    la t1, 3b
+    bne t0, t1, fail
+
+    # rd and rs1 are the same register.
+    li gp, 3
+    la t0, 1f
+    jalr t0, t0, 0
+3:
+    j fail
+1:
+    la t1, 3b
+    bne t0, t1, fail
+
+    # the lowest bit of the target is cleared.
+    li gp, 4
+    la t1, 1f
+    addi t1, t1, -3
+    jalr t0, t1, 4
+    j fail
+1:
+    li gp, 5
+    li x14, 1
+    la t1, 1f
+    jalr x0, t1, -8
+    addi x14, x14, 1
+    addi x14, x14, 1
+    addi x14, x14, 1
+1:
+    addi x14, x14, 1
+    TEST_CHECK 4
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
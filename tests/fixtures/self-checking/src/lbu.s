@@ -0,0 +1,28 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_LD_OP 2, lbu, 0x000000ff, 0, tdat
+    TEST_LD_OP 3, lbu, 0x00000000, 1, tdat
+    TEST_LD_OP 4, lbu, 0x000000f0, 2, tdat
+    TEST_LD_OP 5, lbu, 0x0000000f, 3, tdat
+    TEST_LD_OP 6, lbu, 0x000000ff, -3, tdat4
+    TEST_LD_OP 7, lbu, 0x00000000, -2, tdat4
+    TEST_LD_OP 8, lbu, 0x000000f0, -1, tdat4
+    TEST_LD_OP 9, lbu, 0x0000000f, 0, tdat4
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+tdat:
+tdat1: .byte 0xff
+tdat2: .byte 0x0
+tdat3: .byte 0xf0
+tdat4: .byte 0xf
+
+RVTEST_DATA_END
@@ -0,0 +1,28 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_LD_OP 2, lh, 0x000000ff, 0, tdat
+    TEST_LD_OP 3, lh, 0xffffff00, 2, tdat
+    TEST_LD_OP 4, lh, 0x00000ff0, 4, tdat
+    TEST_LD_OP 5, lh, 0xfffff00f, 6, tdat
+    TEST_LD_OP 6, lh, 0x000000ff, -6, tdat4
+    TEST_LD_OP 7, lh, 0xffffff00, -4, tdat4
+    TEST_LD_OP 8, lh, 0x00000ff0, -2, tdat4
+    TEST_LD_OP 9, lh, 0xfffff00f, 0, tdat4
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+tdat:
+tdat1: .half 0xff
+tdat2: .half 0xff00
+tdat3: .half 0xff0
+tdat4: .half 0xf00f
+
+RVTEST_DATA_END
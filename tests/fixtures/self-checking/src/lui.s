@@ -0,0 +1,37 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    li gp, 2
+    lui x14, 0x00000
+    TEST_CHECK 0x00000000
+
+    li gp, 3
+    lui x1, 0xfffff
+    sra x14, x1, 1
+    TEST_CHECK 0xfffff800
+
+    li gp, 4
+    lui x1, 0x7ffff
+    sra x14, x1, 20
+    TEST_CHECK 0x000007ff
+
+    li gp, 5
+    lui x1, 0x80000
+    sra x14, x1, 20
+    TEST_CHECK 0xfffff800
+
+    li gp, 6
+    lui x0, 0x80000
+    addi x14, x0, 0
+    TEST_CHECK 0x00000000
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
@@ -0,0 +1,28 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_LD_OP 2, lw, 0x00ff00ff, 0, tdat
+    TEST_LD_OP 3, lw, 0xff00ff00, 4, tdat
+    TEST_LD_OP 4, lw, 0x0ff00ff0, 8, tdat
+    TEST_LD_OP 5, lw, 0xf00ff00f, 12, tdat
+    TEST_LD_OP 6, lw, 0x00ff00ff, -12, tdat4
+    TEST_LD_OP 7, lw, 0xff00ff00, -8, tdat4
+    TEST_LD_OP 8, lw, 0x0ff00ff0, -4, tdat4
+    TEST_LD_OP 9, lw, 0xf00ff00f, 0, tdat4
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+tdat:
+tdat1: .word 0xff00ff
+tdat2: .word 0xff00ff00
+tdat3: .word 0xff00ff0
+tdat4: .word 0xf00ff00f
+
+RVTEST_DATA_END
@@ -0,0 +1,51 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    # loads at every offset that is not a multiple of their size.
+    TEST_LD_OP 2, lh, 0x00000201, 1, tdat
+    TEST_LD_OP 3, lhu, 0x00000403, 3, tdat
+    TEST_LD_OP 4, lh, 0xffff8281, 17, tdat
+    TEST_LD_OP 5, lhu, 0x00008887, 23, tdat
+    TEST_LD_OP 6, lw, 0x04030201, 1, tdat
+    TEST_LD_OP 7, lw, 0x05040302, 2, tdat
+    TEST_LD_OP 8, lw, 0x06050403, 3, tdat
+    TEST_LD_OP 9, lw, 0x84838281, 17, tdat
+    TEST_LD_OP 10, lw, 0x908f8e8d, 5, tdat4
+    TEST_LD_OP 11, lw, 0x88878685, -3, tdat4
+
+    # stores read back through the same and through other sizes.
+    TEST_ST_OP 12, lh, sh, 0x00001234, 1, tdat5
+    TEST_ST_OP 13, lh, sh, 0xffff8765, 5, tdat5
+    TEST_ST_OP 14, lhu, sh, 0x0000abcd, 7, tdat5
+    TEST_ST_OP 15, lw, sw, 0x12345678, 9, tdat5
+    TEST_ST_OP 16, lw, sw, 0x9abcdef0, 14, tdat5
+    TEST_ST_OP 17, lw, sw, 0xfedcba98, 19, tdat5
+    TEST_LD_OP 18, lbu, 0x00000056, 10, tdat5
+    TEST_LD_OP 19, lh, 0xffffdef0, 14, tdat5
+    TEST_LD_OP 20, lhu, 0x00009abc, 16, tdat5
+    TEST_LD_OP 21, lh, 0xffffba98, 19, tdat5
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+tdat:
+    .word 0x03020100
+    .word 0x07060504
+    .word 0x0b0a0908
+    .word 0x0f0e0d0c
+    .word 0x83828180
+    .word 0x87868584
+tdat4:
+    .word 0x8b8a8988
+    .word 0x8f8e8d8c
+    .word 0x93929190
+tdat5:
+    .zero 32
+
+RVTEST_DATA_END
@@ -0,0 +1,34 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_ST_OP 2, lb, sb, 0xffffffaa, 0, tdat
+    TEST_ST_OP 3, lb, sb, 0x00000000, 1, tdat
+    TEST_ST_OP 4, lb, sb, 0xffffffa0, 2, tdat
+    TEST_ST_OP 5, lb, sb, 0x0000000a, 3, tdat
+    TEST_ST_OP 6, lb, sb, 0x00000078, -4, tdat8
+    TEST_ST_OP 7, lb, sb, 0xffffffef, -3, tdat8
+    TEST_ST_OP 8, lb, sb, 0x00000000, -2, tdat8
+    TEST_ST_OP 9, lb, sb, 0xffffffff, -1, tdat8
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+tdat:
+tdat1: .byte 0xef
+tdat2: .byte 0xef
+tdat3: .byte 0xef
+tdat4: .byte 0xef
+tdat5: .byte 0xef
+tdat6: .byte 0xef
+tdat7: .byte 0xef
+tdat8: .byte 0xef
+tdat9: .byte 0xef
+tdat10: .byte 0xef
+
+RVTEST_DATA_END
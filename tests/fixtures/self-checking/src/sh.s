@@ -0,0 +1,34 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_ST_OP 2, lh, sh, 0x000000aa, 0, tdat
+    TEST_ST_OP 3, lh, sh, 0xffffaa00, 2, tdat
+    TEST_ST_OP 4, lh, sh, 0x00000aa0, 4, tdat
+    TEST_ST_OP 5, lh, sh, 0xffffa00a, 6, tdat
+    TEST_ST_OP 6, lh, sh, 0x00005678, -8, tdat8
+    TEST_ST_OP 7, lh, sh, 0xffffcdef, -6, tdat8
+    TEST_ST_OP 8, lh, sh, 0x00000000, -4, tdat8
+    TEST_ST_OP 9, lh, sh, 0xffffffff, -2, tdat8
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+tdat:
+tdat1: .half 0xbeef
+tdat2: .half 0xbeef
+tdat3: .half 0xbeef
+tdat4: .half 0xbeef
+tdat5: .half 0xbeef
+tdat6: .half 0xbeef
+tdat7: .half 0xbeef
+tdat8: .half 0xbeef
+tdat9: .half 0xbeef
+tdat10: .half 0xbeef
+
+RVTEST_DATA_END
@@ -0,0 +1,51 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, sll, 0x00000001, 0x00000001, 0x00000000
+    TEST_RR_OP 3, sll, 0x00000002, 0x00000001, 0x00000001
+    TEST_RR_OP 4, sll, 0x00000080, 0x00000001, 0x00000007
+    TEST_RR_OP 5, sll, 0x00004000, 0x00000001, 0x0000000e
+    TEST_RR_OP 6, sll, 0x80000000, 0x00000001, 0x0000001f
+    TEST_RR_OP 7, sll, 0xffffffff, 0xffffffff, 0x00000000
+    TEST_RR_OP 8, sll, 0xfffffffe, 0xffffffff, 0x00000001
+    TEST_RR_OP 9, sll, 0xffffff80, 0xffffffff, 0x00000007
+    TEST_RR_OP 10, sll, 0xffffc000, 0xffffffff, 0x0000000e
+    TEST_RR_OP 11, sll, 0x80000000, 0xffffffff, 0x0000001f
+    TEST_RR_OP 12, sll, 0x21212121, 0x21212121, 0x00000000
+    TEST_RR_OP 13, sll, 0x42424242, 0x21212121, 0x00000001
+    TEST_RR_OP 14, sll, 0x90909080, 0x21212121, 0x00000007
+    TEST_RR_OP 15, sll, 0x48484000, 0x21212121, 0x0000000e
+    TEST_RR_OP 16, sll, 0x80000000, 0x21212121, 0x0000001f
+    TEST_RR_OP 17, sll, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 18, sll, 0x00000000, 0x80000000, 0x00000001
+    TEST_RR_OP 19, sll, 0x00000000, 0x80000000, 0x00000007
+    TEST_RR_OP 20, sll, 0x00000000, 0x80000000, 0x0000000e
+    TEST_RR_OP 21, sll, 0x00000000, 0x80000000, 0x0000001f
+    TEST_RR_OP 22, sll, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 23, sll, 0xfffffffe, 0x7fffffff, 0x00000001
+    TEST_RR_OP 24, sll, 0x80000000, 0x7fffffff, 0x0000001f
+    TEST_RR_OP 25, sll, 0x21212121, 0x21212121, 0xffffffc0
+    TEST_RR_OP 26, sll, 0x42424242, 0x21212121, 0xffffffc1
+    TEST_RR_OP 27, sll, 0x90909080, 0x21212121, 0xffffffc7
+    TEST_RR_OP 28, sll, 0x48484000, 0x21212121, 0xffffffce
+    TEST_RR_SRC1_EQ_DEST 29, sll, 0x00000080, 0x00000001, 0x00000007
+    TEST_RR_SRC2_EQ_DEST 30, sll, 0x00004000, 0x00000001, 0x0000000e
+    TEST_RR_SRC12_EQ_DEST 31, sll, 0x00000380, 0x00000007
+    TEST_RR_DEST_BYPASS 32, 0, sll, 0xfffffffe, 0xffffffff, 0x00000001
+    TEST_RR_DEST_BYPASS 33, 1, sll, 0xffffff80, 0xffffffff, 0x00000007
+    TEST_RR_DEST_BYPASS 34, 2, sll, 0xffffc000, 0xffffffff, 0x0000000e
+    TEST_RR_ZEROSRC1 35, sll, 0x00000000, 0x00000001
+    TEST_RR_ZEROSRC2 36, sll, 0x80000000, 0x80000000
+    TEST_RR_ZEROSRC12 37, sll, 0x00000000
+    TEST_RR_ZERODEST 38, sll, 0x00000010, 0x00000003
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
@@ -0,0 +1,51 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, srl, 0x00000001, 0x00000001, 0x00000000
+    TEST_RR_OP 3, srl, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, srl, 0x00000000, 0x00000001, 0x00000007
+    TEST_RR_OP 5, srl, 0x00000000, 0x00000001, 0x0000000e
+    TEST_RR_OP 6, srl, 0x00000000, 0x00000001, 0x0000001f
+    TEST_RR_OP 7, srl, 0xffffffff, 0xffffffff, 0x00000000
+    TEST_RR_OP 8, srl, 0x7fffffff, 0xffffffff, 0x00000001
+    TEST_RR_OP 9, srl, 0x01ffffff, 0xffffffff, 0x00000007
+    TEST_RR_OP 10, srl, 0x0003ffff, 0xffffffff, 0x0000000e
+    TEST_RR_OP 11, srl, 0x00000001, 0xffffffff, 0x0000001f
+    TEST_RR_OP 12, srl, 0x21212121, 0x21212121, 0x00000000
+    TEST_RR_OP 13, srl, 0x10909090, 0x21212121, 0x00000001
+    TEST_RR_OP 14, srl, 0x00424242, 0x21212121, 0x00000007
+    TEST_RR_OP 15, srl, 0x00008484, 0x21212121, 0x0000000e
+    TEST_RR_OP 16, srl, 0x00000000, 0x21212121, 0x0000001f
+    TEST_RR_OP 17, srl, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 18, srl, 0x40000000, 0x80000000, 0x00000001
+    TEST_RR_OP 19, srl, 0x01000000, 0x80000000, 0x00000007
+    TEST_RR_OP 20, srl, 0x00020000, 0x80000000, 0x0000000e
+    TEST_RR_OP 21, srl, 0x00000001, 0x80000000, 0x0000001f
+    TEST_RR_OP 22, srl, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 23, srl, 0x3fffffff, 0x7fffffff, 0x00000001
+    TEST_RR_OP 24, srl, 0x00000000, 0x7fffffff, 0x0000001f
+    TEST_RR_OP 25, srl, 0x21212121, 0x21212121, 0xffffffc0
+    TEST_RR_OP 26, srl, 0x10909090, 0x21212121, 0xffffffc1
+    TEST_RR_OP 27, srl, 0x00424242, 0x21212121, 0xffffffc7
+    TEST_RR_OP 28, srl, 0x00008484, 0x21212121, 0xffffffce
+    TEST_RR_SRC1_EQ_DEST 29, srl, 0x00000000, 0x00000001, 0x00000007
+    TEST_RR_SRC2_EQ_DEST 30, srl, 0x00000000, 0x00000001, 0x0000000e
+    TEST_RR_SRC12_EQ_DEST 31, srl, 0x00000000, 0x00000007
+    TEST_RR_DEST_BYPASS 32, 0, srl, 0x7fffffff, 0xffffffff, 0x00000001
+    TEST_RR_DEST_BYPASS 33, 1, srl, 0x01ffffff, 0xffffffff, 0x00000007
+    TEST_RR_DEST_BYPASS 34, 2, srl, 0x0003ffff, 0xffffffff, 0x0000000e
+    TEST_RR_ZEROSRC1 35, srl, 0x00000000, 0x00000001
+    TEST_RR_ZEROSRC2 36, srl, 0x80000000, 0x80000000
+    TEST_RR_ZEROSRC12 37, srl, 0x00000000
+    TEST_RR_ZERODEST 38, srl, 0x00000010, 0x00000003
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
@@ -0,0 +1,43 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, sub, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, sub, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, sub, 0xfffffffc, 0x00000003, 0x00000007
+    TEST_RR_OP 5, sub, 0x00008000, 0x00000000, 0xffff8000
+    TEST_RR_OP 6, sub, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 7, sub, 0x80008000, 0x80000000, 0xffff8000
+    TEST_RR_OP 8, sub, 0xffff8001, 0x00000000, 0x00007fff
+    TEST_RR_OP 9, sub, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 10, sub, 0x7fff8000, 0x7fffffff, 0x00007fff
+    TEST_RR_OP 11, sub, 0x7fff8001, 0x80000000, 0x00007fff
+    TEST_RR_OP 12, sub, 0x80007fff, 0x7fffffff, 0xffff8000
+    TEST_RR_OP 13, sub, 0x00000001, 0x00000000, 0xffffffff
+    TEST_RR_OP 14, sub, 0xfffffffe, 0xffffffff, 0x00000001
+    TEST_RR_OP 15, sub, 0x00000000, 0xffffffff, 0xffffffff
+    TEST_RR_OP 16, sub, 0x80000002, 0x00000001, 0x7fffffff
+    TEST_RR_OP 17, sub, 0xeff1eff1, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 18, sub, 0x1eff1f00, 0x0ff00ff0, 0xf0f0f0f0
+    TEST_RR_OP 19, sub, 0xf1eff1f0, 0x00ff00ff, 0x0f0f0f0f
+    TEST_RR_OP 20, sub, 0xff1eff1f, 0xf00ff00f, 0xf0f0f0f0
+    TEST_RR_SRC1_EQ_DEST 21, sub, 0xfffffffc, 0x00000003, 0x00000007
+    TEST_RR_SRC2_EQ_DEST 22, sub, 0x00008000, 0x00000000, 0xffff8000
+    TEST_RR_SRC12_EQ_DEST 23, sub, 0x00000000, 0x0000000d
+    TEST_RR_DEST_BYPASS 24, 0, sub, 0xffff8001, 0x00000000, 0x00007fff
+    TEST_RR_DEST_BYPASS 25, 1, sub, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_DEST_BYPASS 26, 2, sub, 0x7fff8000, 0x7fffffff, 0x00007fff
+    TEST_RR_ZEROSRC1 27, sub, 0xfffffff1, 0x0000000f
+    TEST_RR_ZEROSRC2 28, sub, 0x00000020, 0x00000020
+    TEST_RR_ZEROSRC12 29, sub, 0x00000000
+    TEST_RR_ZERODEST 30, sub, 0x00000010, 0x0000001e
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+RVTEST_DATA_END
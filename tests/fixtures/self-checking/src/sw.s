@@ -0,0 +1,34 @@
+.include "env.inc"
+.include "macros.inc"
+
+RVTEST_RV32U
+RVTEST_CODE_BEGIN
+
+    TEST_ST_OP 2, lw, sw, 0x00aa00aa, 0, tdat
+    TEST_ST_OP 3, lw, sw, 0xaa00aa00, 4, tdat
+    TEST_ST_OP 4, lw, sw, 0x0aa00aa0, 8, tdat
+    TEST_ST_OP 5, lw, sw, 0xa00aa00a, 12, tdat
+    TEST_ST_OP 6, lw, sw, 0x12345678, -16, tdat8
+    TEST_ST_OP 7, lw, sw, 0x89abcdef, -12, tdat8
+    TEST_ST_OP 8, lw, sw, 0x00000000, -8, tdat8
+    TEST_ST_OP 9, lw, sw, 0xffffffff, -4, tdat8
+
+    TEST_PASSFAIL
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+
+tdat:
+tdat1: .word 0xdeadbeef
+tdat2: .word 0xdeadbeef
+tdat3: .word 0xdeadbeef
+tdat4: .word 0xdeadbeef
+tdat5: .word 0xdeadbeef
+tdat6: .word 0xdeadbeef
+tdat7: .word 0xdeadbeef
+tdat8: .word 0xdeadbeef
+tdat9: .word 0xdeadbeef
+tdat10: .word 0xdeadbeef
+
+RVTEST_DATA_END
@@ -0,0 +1,740 @@
+.include "env.inc"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_SIG add, 0x00000000, 0x00000000
+    TEST_RR_SIG add, 0x00000000, 0x00000001
+    TEST_RR_SIG add, 0x00000000, 0xffffffff
+    TEST_RR_SIG add, 0x00000000, 0x7fffffff
+    TEST_RR_SIG add, 0x00000000, 0x80000000
+    TEST_RR_SIG add, 0x00000000, 0x55555555
+    TEST_RR_SIG add, 0x00000000, 0xaaaaaaaa
+    TEST_RR_SIG add, 0x00000000, 0x0000001f
+    TEST_RR_SIG add, 0x00000000, 0x12345678
+    TEST_RR_SIG add, 0x00000001, 0x00000000
+    TEST_RR_SIG add, 0x00000001, 0x00000001
+    TEST_RR_SIG add, 0x00000001, 0xffffffff
+    TEST_RR_SIG add, 0x00000001, 0x7fffffff
+    TEST_RR_SIG add, 0x00000001, 0x80000000
+    TEST_RR_SIG add, 0x00000001, 0x55555555
+    TEST_RR_SIG add, 0x00000001, 0xaaaaaaaa
+    TEST_RR_SIG add, 0x00000001, 0x0000001f
+    TEST_RR_SIG add, 0x00000001, 0x12345678
+    TEST_RR_SIG add, 0xffffffff, 0x00000000
+    TEST_RR_SIG add, 0xffffffff, 0x00000001
+    TEST_RR_SIG add, 0xffffffff, 0xffffffff
+    TEST_RR_SIG add, 0xffffffff, 0x7fffffff
+    TEST_RR_SIG add, 0xffffffff, 0x80000000
+    TEST_RR_SIG add, 0xffffffff, 0x55555555
+    TEST_RR_SIG add, 0xffffffff, 0xaaaaaaaa
+    TEST_RR_SIG add, 0xffffffff, 0x0000001f
+    TEST_RR_SIG add, 0xffffffff, 0x12345678
+    TEST_RR_SIG add, 0x7fffffff, 0x00000000
+    TEST_RR_SIG add, 0x7fffffff, 0x00000001
+    TEST_RR_SIG add, 0x7fffffff, 0xffffffff
+    TEST_RR_SIG add, 0x7fffffff, 0x7fffffff
+    TEST_RR_SIG add, 0x7fffffff, 0x80000000
+    TEST_RR_SIG add, 0x7fffffff, 0x55555555
+    TEST_RR_SIG add, 0x7fffffff, 0xaaaaaaaa
+    TEST_RR_SIG add, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG add, 0x7fffffff, 0x12345678
+    TEST_RR_SIG add, 0x80000000, 0x00000000
+    TEST_RR_SIG add, 0x80000000, 0x00000001
+    TEST_RR_SIG add, 0x80000000, 0xffffffff
+    TEST_RR_SIG add, 0x80000000, 0x7fffffff
+    TEST_RR_SIG add, 0x80000000, 0x80000000
+    TEST_RR_SIG add, 0x80000000, 0x55555555
+    TEST_RR_SIG add, 0x80000000, 0xaaaaaaaa
+    TEST_RR_SIG add, 0x80000000, 0x0000001f
+    TEST_RR_SIG add, 0x80000000, 0x12345678
+    TEST_RR_SIG add, 0x55555555, 0x00000000
+    TEST_RR_SIG add, 0x55555555, 0x00000001
+    TEST_RR_SIG add, 0x55555555, 0xffffffff
+    TEST_RR_SIG add, 0x55555555, 0x7fffffff
+    TEST_RR_SIG add, 0x55555555, 0x80000000
+    TEST_RR_SIG add, 0x55555555, 0x55555555
+    TEST_RR_SIG add, 0x55555555, 0xaaaaaaaa
+    TEST_RR_SIG add, 0x55555555, 0x0000001f
+    TEST_RR_SIG add, 0x55555555, 0x12345678
+    TEST_RR_SIG add, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG add, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG add, 0xaaaaaaaa, 0xffffffff
+    TEST_RR_SIG add, 0xaaaaaaaa, 0x7fffffff
+    TEST_RR_SIG add, 0xaaaaaaaa, 0x80000000
+    TEST_RR_SIG add, 0xaaaaaaaa, 0x55555555
+    TEST_RR_SIG add, 0xaaaaaaaa, 0xaaaaaaaa
+    TEST_RR_SIG add, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG add, 0xaaaaaaaa, 0x12345678
+    TEST_RR_SIG add, 0x0000001f, 0x00000000
+    TEST_RR_SIG add, 0x0000001f, 0x00000001
+    TEST_RR_SIG add, 0x0000001f, 0xffffffff
+    TEST_RR_SIG add, 0x0000001f, 0x7fffffff
+    TEST_RR_SIG add, 0x0000001f, 0x80000000
+    TEST_RR_SIG add, 0x0000001f, 0x55555555
+    TEST_RR_SIG add, 0x0000001f, 0xaaaaaaaa
+    TEST_RR_SIG add, 0x0000001f, 0x0000001f
+    TEST_RR_SIG add, 0x0000001f, 0x12345678
+    TEST_RR_SIG add, 0x12345678, 0x00000000
+    TEST_RR_SIG add, 0x12345678, 0x00000001
+    TEST_RR_SIG add, 0x12345678, 0xffffffff
+    TEST_RR_SIG add, 0x12345678, 0x7fffffff
+    TEST_RR_SIG add, 0x12345678, 0x80000000
+    TEST_RR_SIG add, 0x12345678, 0x55555555
+    TEST_RR_SIG add, 0x12345678, 0xaaaaaaaa
+    TEST_RR_SIG add, 0x12345678, 0x0000001f
+    TEST_RR_SIG add, 0x12345678, 0x12345678
+    TEST_RR_SIG sub, 0x00000000, 0x00000000
+    TEST_RR_SIG sub, 0x00000000, 0x00000001
+    TEST_RR_SIG sub, 0x00000000, 0xffffffff
+    TEST_RR_SIG sub, 0x00000000, 0x7fffffff
+    TEST_RR_SIG sub, 0x00000000, 0x80000000
+    TEST_RR_SIG sub, 0x00000000, 0x55555555
+    TEST_RR_SIG sub, 0x00000000, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0x00000000, 0x0000001f
+    TEST_RR_SIG sub, 0x00000000, 0x12345678
+    TEST_RR_SIG sub, 0x00000001, 0x00000000
+    TEST_RR_SIG sub, 0x00000001, 0x00000001
+    TEST_RR_SIG sub, 0x00000001, 0xffffffff
+    TEST_RR_SIG sub, 0x00000001, 0x7fffffff
+    TEST_RR_SIG sub, 0x00000001, 0x80000000
+    TEST_RR_SIG sub, 0x00000001, 0x55555555
+    TEST_RR_SIG sub, 0x00000001, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0x00000001, 0x0000001f
+    TEST_RR_SIG sub, 0x00000001, 0x12345678
+    TEST_RR_SIG sub, 0xffffffff, 0x00000000
+    TEST_RR_SIG sub, 0xffffffff, 0x00000001
+    TEST_RR_SIG sub, 0xffffffff, 0xffffffff
+    TEST_RR_SIG sub, 0xffffffff, 0x7fffffff
+    TEST_RR_SIG sub, 0xffffffff, 0x80000000
+    TEST_RR_SIG sub, 0xffffffff, 0x55555555
+    TEST_RR_SIG sub, 0xffffffff, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0xffffffff, 0x0000001f
+    TEST_RR_SIG sub, 0xffffffff, 0x12345678
+    TEST_RR_SIG sub, 0x7fffffff, 0x00000000
+    TEST_RR_SIG sub, 0x7fffffff, 0x00000001
+    TEST_RR_SIG sub, 0x7fffffff, 0xffffffff
+    TEST_RR_SIG sub, 0x7fffffff, 0x7fffffff
+    TEST_RR_SIG sub, 0x7fffffff, 0x80000000
+    TEST_RR_SIG sub, 0x7fffffff, 0x55555555
+    TEST_RR_SIG sub, 0x7fffffff, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG sub, 0x7fffffff, 0x12345678
+    TEST_RR_SIG sub, 0x80000000, 0x00000000
+    TEST_RR_SIG sub, 0x80000000, 0x00000001
+    TEST_RR_SIG sub, 0x80000000, 0xffffffff
+    TEST_RR_SIG sub, 0x80000000, 0x7fffffff
+    TEST_RR_SIG sub, 0x80000000, 0x80000000
+    TEST_RR_SIG sub, 0x80000000, 0x55555555
+    TEST_RR_SIG sub, 0x80000000, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0x80000000, 0x0000001f
+    TEST_RR_SIG sub, 0x80000000, 0x12345678
+    TEST_RR_SIG sub, 0x55555555, 0x00000000
+    TEST_RR_SIG sub, 0x55555555, 0x00000001
+    TEST_RR_SIG sub, 0x55555555, 0xffffffff
+    TEST_RR_SIG sub, 0x55555555, 0x7fffffff
+    TEST_RR_SIG sub, 0x55555555, 0x80000000
+    TEST_RR_SIG sub, 0x55555555, 0x55555555
+    TEST_RR_SIG sub, 0x55555555, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0x55555555, 0x0000001f
+    TEST_RR_SIG sub, 0x55555555, 0x12345678
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0xffffffff
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0x7fffffff
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0x80000000
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0x55555555
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG sub, 0xaaaaaaaa, 0x12345678
+    TEST_RR_SIG sub, 0x0000001f, 0x00000000
+    TEST_RR_SIG sub, 0x0000001f, 0x00000001
+    TEST_RR_SIG sub, 0x0000001f, 0xffffffff
+    TEST_RR_SIG sub, 0x0000001f, 0x7fffffff
+    TEST_RR_SIG sub, 0x0000001f, 0x80000000
+    TEST_RR_SIG sub, 0x0000001f, 0x55555555
+    TEST_RR_SIG sub, 0x0000001f, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0x0000001f, 0x0000001f
+    TEST_RR_SIG sub, 0x0000001f, 0x12345678
+    TEST_RR_SIG sub, 0x12345678, 0x00000000
+    TEST_RR_SIG sub, 0x12345678, 0x00000001
+    TEST_RR_SIG sub, 0x12345678, 0xffffffff
+    TEST_RR_SIG sub, 0x12345678, 0x7fffffff
+    TEST_RR_SIG sub, 0x12345678, 0x80000000
+    TEST_RR_SIG sub, 0x12345678, 0x55555555
+    TEST_RR_SIG sub, 0x12345678, 0xaaaaaaaa
+    TEST_RR_SIG sub, 0x12345678, 0x0000001f
+    TEST_RR_SIG sub, 0x12345678, 0x12345678
+    TEST_RR_SIG and, 0x00000000, 0x00000000
+    TEST_RR_SIG and, 0x00000000, 0x00000001
+    TEST_RR_SIG and, 0x00000000, 0xffffffff
+    TEST_RR_SIG and, 0x00000000, 0x7fffffff
+    TEST_RR_SIG and, 0x00000000, 0x80000000
+    TEST_RR_SIG and, 0x00000000, 0x55555555
+    TEST_RR_SIG and, 0x00000000, 0xaaaaaaaa
+    TEST_RR_SIG and, 0x00000000, 0x0000001f
+    TEST_RR_SIG and, 0x00000000, 0x12345678
+    TEST_RR_SIG and, 0x00000001, 0x00000000
+    TEST_RR_SIG and, 0x00000001, 0x00000001
+    TEST_RR_SIG and, 0x00000001, 0xffffffff
+    TEST_RR_SIG and, 0x00000001, 0x7fffffff
+    TEST_RR_SIG and, 0x00000001, 0x80000000
+    TEST_RR_SIG and, 0x00000001, 0x55555555
+    TEST_RR_SIG and, 0x00000001, 0xaaaaaaaa
+    TEST_RR_SIG and, 0x00000001, 0x0000001f
+    TEST_RR_SIG and, 0x00000001, 0x12345678
+    TEST_RR_SIG and, 0xffffffff, 0x00000000
+    TEST_RR_SIG and, 0xffffffff, 0x00000001
+    TEST_RR_SIG and, 0xffffffff, 0xffffffff
+    TEST_RR_SIG and, 0xffffffff, 0x7fffffff
+    TEST_RR_SIG and, 0xffffffff, 0x80000000
+    TEST_RR_SIG and, 0xffffffff, 0x55555555
+    TEST_RR_SIG and, 0xffffffff, 0xaaaaaaaa
+    TEST_RR_SIG and, 0xffffffff, 0x0000001f
+    TEST_RR_SIG and, 0xffffffff, 0x12345678
+    TEST_RR_SIG and, 0x7fffffff, 0x00000000
+    TEST_RR_SIG and, 0x7fffffff, 0x00000001
+    TEST_RR_SIG and, 0x7fffffff, 0xffffffff
+    TEST_RR_SIG and, 0x7fffffff, 0x7fffffff
+    TEST_RR_SIG and, 0x7fffffff, 0x80000000
+    TEST_RR_SIG and, 0x7fffffff, 0x55555555
+    TEST_RR_SIG and, 0x7fffffff, 0xaaaaaaaa
+    TEST_RR_SIG and, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG and, 0x7fffffff, 0x12345678
+    TEST_RR_SIG and, 0x80000000, 0x00000000
+    TEST_RR_SIG and, 0x80000000, 0x00000001
+    TEST_RR_SIG and, 0x80000000, 0xffffffff
+    TEST_RR_SIG and, 0x80000000, 0x7fffffff
+    TEST_RR_SIG and, 0x80000000, 0x80000000
+    TEST_RR_SIG and, 0x80000000, 0x55555555
+    TEST_RR_SIG and, 0x80000000, 0xaaaaaaaa
+    TEST_RR_SIG and, 0x80000000, 0x0000001f
+    TEST_RR_SIG and, 0x80000000, 0x12345678
+    TEST_RR_SIG and, 0x55555555, 0x00000000
+    TEST_RR_SIG and, 0x55555555, 0x00000001
+    TEST_RR_SIG and, 0x55555555, 0xffffffff
+    TEST_RR_SIG and, 0x55555555, 0x7fffffff
+    TEST_RR_SIG and, 0x55555555, 0x80000000
+    TEST_RR_SIG and, 0x55555555, 0x55555555
+    TEST_RR_SIG and, 0x55555555, 0xaaaaaaaa
+    TEST_RR_SIG and, 0x55555555, 0x0000001f
+    TEST_RR_SIG and, 0x55555555, 0x12345678
+    TEST_RR_SIG and, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG and, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG and, 0xaaaaaaaa, 0xffffffff
+    TEST_RR_SIG and, 0xaaaaaaaa, 0x7fffffff
+    TEST_RR_SIG and, 0xaaaaaaaa, 0x80000000
+    TEST_RR_SIG and, 0xaaaaaaaa, 0x55555555
+    TEST_RR_SIG and, 0xaaaaaaaa, 0xaaaaaaaa
+    TEST_RR_SIG and, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG and, 0xaaaaaaaa, 0x12345678
+    TEST_RR_SIG and, 0x0000001f, 0x00000000
+    TEST_RR_SIG and, 0x0000001f, 0x00000001
+    TEST_RR_SIG and, 0x0000001f, 0xffffffff
+    TEST_RR_SIG and, 0x0000001f, 0x7fffffff
+    TEST_RR_SIG and, 0x0000001f, 0x80000000
+    TEST_RR_SIG and, 0x0000001f, 0x55555555
+    TEST_RR_SIG and, 0x0000001f, 0xaaaaaaaa
+    TEST_RR_SIG and, 0x0000001f, 0x0000001f
+    TEST_RR_SIG and, 0x0000001f, 0x12345678
+    TEST_RR_SIG and, 0x12345678, 0x00000000
+    TEST_RR_SIG and, 0x12345678, 0x00000001
+    TEST_RR_SIG and, 0x12345678, 0xffffffff
+    TEST_RR_SIG and, 0x12345678, 0x7fffffff
+    TEST_RR_SIG and, 0x12345678, 0x80000000
+    TEST_RR_SIG and, 0x12345678, 0x55555555
+    TEST_RR_SIG and, 0x12345678, 0xaaaaaaaa
+    TEST_RR_SIG and, 0x12345678, 0x0000001f
+    TEST_RR_SIG and, 0x12345678, 0x12345678
+    TEST_RR_SIG or, 0x00000000, 0x00000000
+    TEST_RR_SIG or, 0x00000000, 0x00000001
+    TEST_RR_SIG or, 0x00000000, 0xffffffff
+    TEST_RR_SIG or, 0x00000000, 0x7fffffff
+    TEST_RR_SIG or, 0x00000000, 0x80000000
+    TEST_RR_SIG or, 0x00000000, 0x55555555
+    TEST_RR_SIG or, 0x00000000, 0xaaaaaaaa
+    TEST_RR_SIG or, 0x00000000, 0x0000001f
+    TEST_RR_SIG or, 0x00000000, 0x12345678
+    TEST_RR_SIG or, 0x00000001, 0x00000000
+    TEST_RR_SIG or, 0x00000001, 0x00000001
+    TEST_RR_SIG or, 0x00000001, 0xffffffff
+    TEST_RR_SIG or, 0x00000001, 0x7fffffff
+    TEST_RR_SIG or, 0x00000001, 0x80000000
+    TEST_RR_SIG or, 0x00000001, 0x55555555
+    TEST_RR_SIG or, 0x00000001, 0xaaaaaaaa
+    TEST_RR_SIG or, 0x00000001, 0x0000001f
+    TEST_RR_SIG or, 0x00000001, 0x12345678
+    TEST_RR_SIG or, 0xffffffff, 0x00000000
+    TEST_RR_SIG or, 0xffffffff, 0x00000001
+    TEST_RR_SIG or, 0xffffffff, 0xffffffff
+    TEST_RR_SIG or, 0xffffffff, 0x7fffffff
+    TEST_RR_SIG or, 0xffffffff, 0x80000000
+    TEST_RR_SIG or, 0xffffffff, 0x55555555
+    TEST_RR_SIG or, 0xffffffff, 0xaaaaaaaa
+    TEST_RR_SIG or, 0xffffffff, 0x0000001f
+    TEST_RR_SIG or, 0xffffffff, 0x12345678
+    TEST_RR_SIG or, 0x7fffffff, 0x00000000
+    TEST_RR_SIG or, 0x7fffffff, 0x00000001
+    TEST_RR_SIG or, 0x7fffffff, 0xffffffff
+    TEST_RR_SIG or, 0x7fffffff, 0x7fffffff
+    TEST_RR_SIG or, 0x7fffffff, 0x80000000
+    TEST_RR_SIG or, 0x7fffffff, 0x55555555
+    TEST_RR_SIG or, 0x7fffffff, 0xaaaaaaaa
+    TEST_RR_SIG or, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG or, 0x7fffffff, 0x12345678
+    TEST_RR_SIG or, 0x80000000, 0x00000000
+    TEST_RR_SIG or, 0x80000000, 0x00000001
+    TEST_RR_SIG or, 0x80000000, 0xffffffff
+    TEST_RR_SIG or, 0x80000000, 0x7fffffff
+    TEST_RR_SIG or, 0x80000000, 0x80000000
+    TEST_RR_SIG or, 0x80000000, 0x55555555
+    TEST_RR_SIG or, 0x80000000, 0xaaaaaaaa
+    TEST_RR_SIG or, 0x80000000, 0x0000001f
+    TEST_RR_SIG or, 0x80000000, 0x12345678
+    TEST_RR_SIG or, 0x55555555, 0x00000000
+    TEST_RR_SIG or, 0x55555555, 0x00000001
+    TEST_RR_SIG or, 0x55555555, 0xffffffff
+    TEST_RR_SIG or, 0x55555555, 0x7fffffff
+    TEST_RR_SIG or, 0x55555555, 0x80000000
+    TEST_RR_SIG or, 0x55555555, 0x55555555
+    TEST_RR_SIG or, 0x55555555, 0xaaaaaaaa
+    TEST_RR_SIG or, 0x55555555, 0x0000001f
+    TEST_RR_SIG or, 0x55555555, 0x12345678
+    TEST_RR_SIG or, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG or, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG or, 0xaaaaaaaa, 0xffffffff
+    TEST_RR_SIG or, 0xaaaaaaaa, 0x7fffffff
+    TEST_RR_SIG or, 0xaaaaaaaa, 0x80000000
+    TEST_RR_SIG or, 0xaaaaaaaa, 0x55555555
+    TEST_RR_SIG or, 0xaaaaaaaa, 0xaaaaaaaa
+    TEST_RR_SIG or, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG or, 0xaaaaaaaa, 0x12345678
+    TEST_RR_SIG or, 0x0000001f, 0x00000000
+    TEST_RR_SIG or, 0x0000001f, 0x00000001
+    TEST_RR_SIG or, 0x0000001f, 0xffffffff
+    TEST_RR_SIG or, 0x0000001f, 0x7fffffff
+    TEST_RR_SIG or, 0x0000001f, 0x80000000
+    TEST_RR_SIG or, 0x0000001f, 0x55555555
+    TEST_RR_SIG or, 0x0000001f, 0xaaaaaaaa
+    TEST_RR_SIG or, 0x0000001f, 0x0000001f
+    TEST_RR_SIG or, 0x0000001f, 0x12345678
+    TEST_RR_SIG or, 0x12345678, 0x00000000
+    TEST_RR_SIG or, 0x12345678, 0x00000001
+    TEST_RR_SIG or, 0x12345678, 0xffffffff
+    TEST_RR_SIG or, 0x12345678, 0x7fffffff
+    TEST_RR_SIG or, 0x12345678, 0x80000000
+    TEST_RR_SIG or, 0x12345678, 0x55555555
+    TEST_RR_SIG or, 0x12345678, 0xaaaaaaaa
+    TEST_RR_SIG or, 0x12345678, 0x0000001f
+    TEST_RR_SIG or, 0x12345678, 0x12345678
+    TEST_RR_SIG xor, 0x00000000, 0x00000000
+    TEST_RR_SIG xor, 0x00000000, 0x00000001
+    TEST_RR_SIG xor, 0x00000000, 0xffffffff
+    TEST_RR_SIG xor, 0x00000000, 0x7fffffff
+    TEST_RR_SIG xor, 0x00000000, 0x80000000
+    TEST_RR_SIG xor, 0x00000000, 0x55555555
+    TEST_RR_SIG xor, 0x00000000, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0x00000000, 0x0000001f
+    TEST_RR_SIG xor, 0x00000000, 0x12345678
+    TEST_RR_SIG xor, 0x00000001, 0x00000000
+    TEST_RR_SIG xor, 0x00000001, 0x00000001
+    TEST_RR_SIG xor, 0x00000001, 0xffffffff
+    TEST_RR_SIG xor, 0x00000001, 0x7fffffff
+    TEST_RR_SIG xor, 0x00000001, 0x80000000
+    TEST_RR_SIG xor, 0x00000001, 0x55555555
+    TEST_RR_SIG xor, 0x00000001, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0x00000001, 0x0000001f
+    TEST_RR_SIG xor, 0x00000001, 0x12345678
+    TEST_RR_SIG xor, 0xffffffff, 0x00000000
+    TEST_RR_SIG xor, 0xffffffff, 0x00000001
+    TEST_RR_SIG xor, 0xffffffff, 0xffffffff
+    TEST_RR_SIG xor, 0xffffffff, 0x7fffffff
+    TEST_RR_SIG xor, 0xffffffff, 0x80000000
+    TEST_RR_SIG xor, 0xffffffff, 0x55555555
+    TEST_RR_SIG xor, 0xffffffff, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0xffffffff, 0x0000001f
+    TEST_RR_SIG xor, 0xffffffff, 0x12345678
+    TEST_RR_SIG xor, 0x7fffffff, 0x00000000
+    TEST_RR_SIG xor, 0x7fffffff, 0x00000001
+    TEST_RR_SIG xor, 0x7fffffff, 0xffffffff
+    TEST_RR_SIG xor, 0x7fffffff, 0x7fffffff
+    TEST_RR_SIG xor, 0x7fffffff, 0x80000000
+    TEST_RR_SIG xor, 0x7fffffff, 0x55555555
+    TEST_RR_SIG xor, 0x7fffffff, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG xor, 0x7fffffff, 0x12345678
+    TEST_RR_SIG xor, 0x80000000, 0x00000000
+    TEST_RR_SIG xor, 0x80000000, 0x00000001
+    TEST_RR_SIG xor, 0x80000000, 0xffffffff
+    TEST_RR_SIG xor, 0x80000000, 0x7fffffff
+    TEST_RR_SIG xor, 0x80000000, 0x80000000
+    TEST_RR_SIG xor, 0x80000000, 0x55555555
+    TEST_RR_SIG xor, 0x80000000, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0x80000000, 0x0000001f
+    TEST_RR_SIG xor, 0x80000000, 0x12345678
+    TEST_RR_SIG xor, 0x55555555, 0x00000000
+    TEST_RR_SIG xor, 0x55555555, 0x00000001
+    TEST_RR_SIG xor, 0x55555555, 0xffffffff
+    TEST_RR_SIG xor, 0x55555555, 0x7fffffff
+    TEST_RR_SIG xor, 0x55555555, 0x80000000
+    TEST_RR_SIG xor, 0x55555555, 0x55555555
+    TEST_RR_SIG xor, 0x55555555, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0x55555555, 0x0000001f
+    TEST_RR_SIG xor, 0x55555555, 0x12345678
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0xffffffff
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0x7fffffff
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0x80000000
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0x55555555
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG xor, 0xaaaaaaaa, 0x12345678
+    TEST_RR_SIG xor, 0x0000001f, 0x00000000
+    TEST_RR_SIG xor, 0x0000001f, 0x00000001
+    TEST_RR_SIG xor, 0x0000001f, 0xffffffff
+    TEST_RR_SIG xor, 0x0000001f, 0x7fffffff
+    TEST_RR_SIG xor, 0x0000001f, 0x80000000
+    TEST_RR_SIG xor, 0x0000001f, 0x55555555
+    TEST_RR_SIG xor, 0x0000001f, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0x0000001f, 0x0000001f
+    TEST_RR_SIG xor, 0x0000001f, 0x12345678
+    TEST_RR_SIG xor, 0x12345678, 0x00000000
+    TEST_RR_SIG xor, 0x12345678, 0x00000001
+    TEST_RR_SIG xor, 0x12345678, 0xffffffff
+    TEST_RR_SIG xor, 0x12345678, 0x7fffffff
+    TEST_RR_SIG xor, 0x12345678, 0x80000000
+    TEST_RR_SIG xor, 0x12345678, 0x55555555
+    TEST_RR_SIG xor, 0x12345678, 0xaaaaaaaa
+    TEST_RR_SIG xor, 0x12345678, 0x0000001f
+    TEST_RR_SIG xor, 0x12345678, 0x12345678
+    TEST_RR_SIG sll, 0x00000000, 0x00000000
+    TEST_RR_SIG sll, 0x00000000, 0x00000001
+    TEST_RR_SIG sll, 0x00000000, 0x0000000f
+    TEST_RR_SIG sll, 0x00000000, 0x0000001f
+    TEST_RR_SIG sll, 0x00000000, 0x00000020
+    TEST_RR_SIG sll, 0x00000000, 0xffffffe1
+    TEST_RR_SIG sll, 0x00000001, 0x00000000
+    TEST_RR_SIG sll, 0x00000001, 0x00000001
+    TEST_RR_SIG sll, 0x00000001, 0x0000000f
+    TEST_RR_SIG sll, 0x00000001, 0x0000001f
+    TEST_RR_SIG sll, 0x00000001, 0x00000020
+    TEST_RR_SIG sll, 0x00000001, 0xffffffe1
+    TEST_RR_SIG sll, 0xffffffff, 0x00000000
+    TEST_RR_SIG sll, 0xffffffff, 0x00000001
+    TEST_RR_SIG sll, 0xffffffff, 0x0000000f
+    TEST_RR_SIG sll, 0xffffffff, 0x0000001f
+    TEST_RR_SIG sll, 0xffffffff, 0x00000020
+    TEST_RR_SIG sll, 0xffffffff, 0xffffffe1
+    TEST_RR_SIG sll, 0x7fffffff, 0x00000000
+    TEST_RR_SIG sll, 0x7fffffff, 0x00000001
+    TEST_RR_SIG sll, 0x7fffffff, 0x0000000f
+    TEST_RR_SIG sll, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG sll, 0x7fffffff, 0x00000020
+    TEST_RR_SIG sll, 0x7fffffff, 0xffffffe1
+    TEST_RR_SIG sll, 0x80000000, 0x00000000
+    TEST_RR_SIG sll, 0x80000000, 0x00000001
+    TEST_RR_SIG sll, 0x80000000, 0x0000000f
+    TEST_RR_SIG sll, 0x80000000, 0x0000001f
+    TEST_RR_SIG sll, 0x80000000, 0x00000020
+    TEST_RR_SIG sll, 0x80000000, 0xffffffe1
+    TEST_RR_SIG sll, 0x55555555, 0x00000000
+    TEST_RR_SIG sll, 0x55555555, 0x00000001
+    TEST_RR_SIG sll, 0x55555555, 0x0000000f
+    TEST_RR_SIG sll, 0x55555555, 0x0000001f
+    TEST_RR_SIG sll, 0x55555555, 0x00000020
+    TEST_RR_SIG sll, 0x55555555, 0xffffffe1
+    TEST_RR_SIG sll, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG sll, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG sll, 0xaaaaaaaa, 0x0000000f
+    TEST_RR_SIG sll, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG sll, 0xaaaaaaaa, 0x00000020
+    TEST_RR_SIG sll, 0xaaaaaaaa, 0xffffffe1
+    TEST_RR_SIG sll, 0x0000001f, 0x00000000
+    TEST_RR_SIG sll, 0x0000001f, 0x00000001
+    TEST_RR_SIG sll, 0x0000001f, 0x0000000f
+    TEST_RR_SIG sll, 0x0000001f, 0x0000001f
+    TEST_RR_SIG sll, 0x0000001f, 0x00000020
+    TEST_RR_SIG sll, 0x0000001f, 0xffffffe1
+    TEST_RR_SIG sll, 0x12345678, 0x00000000
+    TEST_RR_SIG sll, 0x12345678, 0x00000001
+    TEST_RR_SIG sll, 0x12345678, 0x0000000f
+    TEST_RR_SIG sll, 0x12345678, 0x0000001f
+    TEST_RR_SIG sll, 0x12345678, 0x00000020
+    TEST_RR_SIG sll, 0x12345678, 0xffffffe1
+    TEST_RR_SIG srl, 0x00000000, 0x00000000
+    TEST_RR_SIG srl, 0x00000000, 0x00000001
+    TEST_RR_SIG srl, 0x00000000, 0x0000000f
+    TEST_RR_SIG srl, 0x00000000, 0x0000001f
+    TEST_RR_SIG srl, 0x00000000, 0x00000020
+    TEST_RR_SIG srl, 0x00000000, 0xffffffe1
+    TEST_RR_SIG srl, 0x00000001, 0x00000000
+    TEST_RR_SIG srl, 0x00000001, 0x00000001
+    TEST_RR_SIG srl, 0x00000001, 0x0000000f
+    TEST_RR_SIG srl, 0x00000001, 0x0000001f
+    TEST_RR_SIG srl, 0x00000001, 0x00000020
+    TEST_RR_SIG srl, 0x00000001, 0xffffffe1
+    TEST_RR_SIG srl, 0xffffffff, 0x00000000
+    TEST_RR_SIG srl, 0xffffffff, 0x00000001
+    TEST_RR_SIG srl, 0xffffffff, 0x0000000f
+    TEST_RR_SIG srl, 0xffffffff, 0x0000001f
+    TEST_RR_SIG srl, 0xffffffff, 0x00000020
+    TEST_RR_SIG srl, 0xffffffff, 0xffffffe1
+    TEST_RR_SIG srl, 0x7fffffff, 0x00000000
+    TEST_RR_SIG srl, 0x7fffffff, 0x00000001
+    TEST_RR_SIG srl, 0x7fffffff, 0x0000000f
+    TEST_RR_SIG srl, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG srl, 0x7fffffff, 0x00000020
+    TEST_RR_SIG srl, 0x7fffffff, 0xffffffe1
+    TEST_RR_SIG srl, 0x80000000, 0x00000000
+    TEST_RR_SIG srl, 0x80000000, 0x00000001
+    TEST_RR_SIG srl, 0x80000000, 0x0000000f
+    TEST_RR_SIG srl, 0x80000000, 0x0000001f
+    TEST_RR_SIG srl, 0x80000000, 0x00000020
+    TEST_RR_SIG srl, 0x80000000, 0xffffffe1
+    TEST_RR_SIG srl, 0x55555555, 0x00000000
+    TEST_RR_SIG srl, 0x55555555, 0x00000001
+    TEST_RR_SIG srl, 0x55555555, 0x0000000f
+    TEST_RR_SIG srl, 0x55555555, 0x0000001f
+    TEST_RR_SIG srl, 0x55555555, 0x00000020
+    TEST_RR_SIG srl, 0x55555555, 0xffffffe1
+    TEST_RR_SIG srl, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG srl, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG srl, 0xaaaaaaaa, 0x0000000f
+    TEST_RR_SIG srl, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG srl, 0xaaaaaaaa, 0x00000020
+    TEST_RR_SIG srl, 0xaaaaaaaa, 0xffffffe1
+    TEST_RR_SIG srl, 0x0000001f, 0x00000000
+    TEST_RR_SIG srl, 0x0000001f, 0x00000001
+    TEST_RR_SIG srl, 0x0000001f, 0x0000000f
+    TEST_RR_SIG srl, 0x0000001f, 0x0000001f
+    TEST_RR_SIG srl, 0x0000001f, 0x00000020
+    TEST_RR_SIG srl, 0x0000001f, 0xffffffe1
+    TEST_RR_SIG srl, 0x12345678, 0x00000000
+    TEST_RR_SIG srl, 0x12345678, 0x00000001
+    TEST_RR_SIG srl, 0x12345678, 0x0000000f
+    TEST_RR_SIG srl, 0x12345678, 0x0000001f
+    TEST_RR_SIG srl, 0x12345678, 0x00000020
+    TEST_RR_SIG srl, 0x12345678, 0xffffffe1
+    TEST_RR_SIG sra, 0x00000000, 0x00000000
+    TEST_RR_SIG sra, 0x00000000, 0x00000001
+    TEST_RR_SIG sra, 0x00000000, 0x0000000f
+    TEST_RR_SIG sra, 0x00000000, 0x0000001f
+    TEST_RR_SIG sra, 0x00000000, 0x00000020
+    TEST_RR_SIG sra, 0x00000000, 0xffffffe1
+    TEST_RR_SIG sra, 0x00000001, 0x00000000
+    TEST_RR_SIG sra, 0x00000001, 0x00000001
+    TEST_RR_SIG sra, 0x00000001, 0x0000000f
+    TEST_RR_SIG sra, 0x00000001, 0x0000001f
+    TEST_RR_SIG sra, 0x00000001, 0x00000020
+    TEST_RR_SIG sra, 0x00000001, 0xffffffe1
+    TEST_RR_SIG sra, 0xffffffff, 0x00000000
+    TEST_RR_SIG sra, 0xffffffff, 0x00000001
+    TEST_RR_SIG sra, 0xffffffff, 0x0000000f
+    TEST_RR_SIG sra, 0xffffffff, 0x0000001f
+    TEST_RR_SIG sra, 0xffffffff, 0x00000020
+    TEST_RR_SIG sra, 0xffffffff, 0xffffffe1
+    TEST_RR_SIG sra, 0x7fffffff, 0x00000000
+    TEST_RR_SIG sra, 0x7fffffff, 0x00000001
+    TEST_RR_SIG sra, 0x7fffffff, 0x0000000f
+    TEST_RR_SIG sra, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG sra, 0x7fffffff, 0x00000020
+    TEST_RR_SIG sra, 0x7fffffff, 0xffffffe1
+    TEST_RR_SIG sra, 0x80000000, 0x00000000
+    TEST_RR_SIG sra, 0x80000000, 0x00000001
+    TEST_RR_SIG sra, 0x80000000, 0x0000000f
+    TEST_RR_SIG sra, 0x80000000, 0x0000001f
+    TEST_RR_SIG sra, 0x80000000, 0x00000020
+    TEST_RR_SIG sra, 0x80000000, 0xffffffe1
+    TEST_RR_SIG sra, 0x55555555, 0x00000000
+    TEST_RR_SIG sra, 0x55555555, 0x00000001
+    TEST_RR_SIG sra, 0x55555555, 0x0000000f
+    TEST_RR_SIG sra, 0x55555555, 0x0000001f
+    TEST_RR_SIG sra, 0x55555555, 0x00000020
+    TEST_RR_SIG sra, 0x55555555, 0xffffffe1
+    TEST_RR_SIG sra, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG sra, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG sra, 0xaaaaaaaa, 0x0000000f
+    TEST_RR_SIG sra, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG sra, 0xaaaaaaaa, 0x00000020
+    TEST_RR_SIG sra, 0xaaaaaaaa, 0xffffffe1
+    TEST_RR_SIG sra, 0x0000001f, 0x00000000
+    TEST_RR_SIG sra, 0x0000001f, 0x00000001
+    TEST_RR_SIG sra, 0x0000001f, 0x0000000f
+    TEST_RR_SIG sra, 0x0000001f, 0x0000001f
+    TEST_RR_SIG sra, 0x0000001f, 0x00000020
+    TEST_RR_SIG sra, 0x0000001f, 0xffffffe1
+    TEST_RR_SIG sra, 0x12345678, 0x00000000
+    TEST_RR_SIG sra, 0x12345678, 0x00000001
+    TEST_RR_SIG sra, 0x12345678, 0x0000000f
+    TEST_RR_SIG sra, 0x12345678, 0x0000001f
+    TEST_RR_SIG sra, 0x12345678, 0x00000020
+    TEST_RR_SIG sra, 0x12345678, 0xffffffe1
+    TEST_RR_SIG slt, 0x00000000, 0x00000000
+    TEST_RR_SIG slt, 0x00000000, 0x00000001
+    TEST_RR_SIG slt, 0x00000000, 0xffffffff
+    TEST_RR_SIG slt, 0x00000000, 0x7fffffff
+    TEST_RR_SIG slt, 0x00000000, 0x80000000
+    TEST_RR_SIG slt, 0x00000000, 0x55555555
+    TEST_RR_SIG slt, 0x00000000, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0x00000000, 0x0000001f
+    TEST_RR_SIG slt, 0x00000000, 0x12345678
+    TEST_RR_SIG slt, 0x00000001, 0x00000000
+    TEST_RR_SIG slt, 0x00000001, 0x00000001
+    TEST_RR_SIG slt, 0x00000001, 0xffffffff
+    TEST_RR_SIG slt, 0x00000001, 0x7fffffff
+    TEST_RR_SIG slt, 0x00000001, 0x80000000
+    TEST_RR_SIG slt, 0x00000001, 0x55555555
+    TEST_RR_SIG slt, 0x00000001, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0x00000001, 0x0000001f
+    TEST_RR_SIG slt, 0x00000001, 0x12345678
+    TEST_RR_SIG slt, 0xffffffff, 0x00000000
+    TEST_RR_SIG slt, 0xffffffff, 0x00000001
+    TEST_RR_SIG slt, 0xffffffff, 0xffffffff
+    TEST_RR_SIG slt, 0xffffffff, 0x7fffffff
+    TEST_RR_SIG slt, 0xffffffff, 0x80000000
+    TEST_RR_SIG slt, 0xffffffff, 0x55555555
+    TEST_RR_SIG slt, 0xffffffff, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0xffffffff, 0x0000001f
+    TEST_RR_SIG slt, 0xffffffff, 0x12345678
+    TEST_RR_SIG slt, 0x7fffffff, 0x00000000
+    TEST_RR_SIG slt, 0x7fffffff, 0x00000001
+    TEST_RR_SIG slt, 0x7fffffff, 0xffffffff
+    TEST_RR_SIG slt, 0x7fffffff, 0x7fffffff
+    TEST_RR_SIG slt, 0x7fffffff, 0x80000000
+    TEST_RR_SIG slt, 0x7fffffff, 0x55555555
+    TEST_RR_SIG slt, 0x7fffffff, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG slt, 0x7fffffff, 0x12345678
+    TEST_RR_SIG slt, 0x80000000, 0x00000000
+    TEST_RR_SIG slt, 0x80000000, 0x00000001
+    TEST_RR_SIG slt, 0x80000000, 0xffffffff
+    TEST_RR_SIG slt, 0x80000000, 0x7fffffff
+    TEST_RR_SIG slt, 0x80000000, 0x80000000
+    TEST_RR_SIG slt, 0x80000000, 0x55555555
+    TEST_RR_SIG slt, 0x80000000, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0x80000000, 0x0000001f
+    TEST_RR_SIG slt, 0x80000000, 0x12345678
+    TEST_RR_SIG slt, 0x55555555, 0x00000000
+    TEST_RR_SIG slt, 0x55555555, 0x00000001
+    TEST_RR_SIG slt, 0x55555555, 0xffffffff
+    TEST_RR_SIG slt, 0x55555555, 0x7fffffff
+    TEST_RR_SIG slt, 0x55555555, 0x80000000
+    TEST_RR_SIG slt, 0x55555555, 0x55555555
+    TEST_RR_SIG slt, 0x55555555, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0x55555555, 0x0000001f
+    TEST_RR_SIG slt, 0x55555555, 0x12345678
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0xffffffff
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0x7fffffff
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0x80000000
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0x55555555
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG slt, 0xaaaaaaaa, 0x12345678
+    TEST_RR_SIG slt, 0x0000001f, 0x00000000
+    TEST_RR_SIG slt, 0x0000001f, 0x00000001
+    TEST_RR_SIG slt, 0x0000001f, 0xffffffff
+    TEST_RR_SIG slt, 0x0000001f, 0x7fffffff
+    TEST_RR_SIG slt, 0x0000001f, 0x80000000
+    TEST_RR_SIG slt, 0x0000001f, 0x55555555
+    TEST_RR_SIG slt, 0x0000001f, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0x0000001f, 0x0000001f
+    TEST_RR_SIG slt, 0x0000001f, 0x12345678
+    TEST_RR_SIG slt, 0x12345678, 0x00000000
+    TEST_RR_SIG slt, 0x12345678, 0x00000001
+    TEST_RR_SIG slt, 0x12345678, 0xffffffff
+    TEST_RR_SIG slt, 0x12345678, 0x7fffffff
+    TEST_RR_SIG slt, 0x12345678, 0x80000000
+    TEST_RR_SIG slt, 0x12345678, 0x55555555
+    TEST_RR_SIG slt, 0x12345678, 0xaaaaaaaa
+    TEST_RR_SIG slt, 0x12345678, 0x0000001f
+    TEST_RR_SIG slt, 0x12345678, 0x12345678
+    TEST_RR_SIG sltu, 0x00000000, 0x00000000
+    TEST_RR_SIG sltu, 0x00000000, 0x00000001
+    TEST_RR_SIG sltu, 0x00000000, 0xffffffff
+    TEST_RR_SIG sltu, 0x00000000, 0x7fffffff
+    TEST_RR_SIG sltu, 0x00000000, 0x80000000
+    TEST_RR_SIG sltu, 0x00000000, 0x55555555
+    TEST_RR_SIG sltu, 0x00000000, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0x00000000, 0x0000001f
+    TEST_RR_SIG sltu, 0x00000000, 0x12345678
+    TEST_RR_SIG sltu, 0x00000001, 0x00000000
+    TEST_RR_SIG sltu, 0x00000001, 0x00000001
+    TEST_RR_SIG sltu, 0x00000001, 0xffffffff
+    TEST_RR_SIG sltu, 0x00000001, 0x7fffffff
+    TEST_RR_SIG sltu, 0x00000001, 0x80000000
+    TEST_RR_SIG sltu, 0x00000001, 0x55555555
+    TEST_RR_SIG sltu, 0x00000001, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0x00000001, 0x0000001f
+    TEST_RR_SIG sltu, 0x00000001, 0x12345678
+    TEST_RR_SIG sltu, 0xffffffff, 0x00000000
+    TEST_RR_SIG sltu, 0xffffffff, 0x00000001
+    TEST_RR_SIG sltu, 0xffffffff, 0xffffffff
+    TEST_RR_SIG sltu, 0xffffffff, 0x7fffffff
+    TEST_RR_SIG sltu, 0xffffffff, 0x80000000
+    TEST_RR_SIG sltu, 0xffffffff, 0x55555555
+    TEST_RR_SIG sltu, 0xffffffff, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0xffffffff, 0x0000001f
+    TEST_RR_SIG sltu, 0xffffffff, 0x12345678
+    TEST_RR_SIG sltu, 0x7fffffff, 0x00000000
+    TEST_RR_SIG sltu, 0x7fffffff, 0x00000001
+    TEST_RR_SIG sltu, 0x7fffffff, 0xffffffff
+    TEST_RR_SIG sltu, 0x7fffffff, 0x7fffffff
+    TEST_RR_SIG sltu, 0x7fffffff, 0x80000000
+    TEST_RR_SIG sltu, 0x7fffffff, 0x55555555
+    TEST_RR_SIG sltu, 0x7fffffff, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0x7fffffff, 0x0000001f
+    TEST_RR_SIG sltu, 0x7fffffff, 0x12345678
+    TEST_RR_SIG sltu, 0x80000000, 0x00000000
+    TEST_RR_SIG sltu, 0x80000000, 0x00000001
+    TEST_RR_SIG sltu, 0x80000000, 0xffffffff
+    TEST_RR_SIG sltu, 0x80000000, 0x7fffffff
+    TEST_RR_SIG sltu, 0x80000000, 0x80000000
+    TEST_RR_SIG sltu, 0x80000000, 0x55555555
+    TEST_RR_SIG sltu, 0x80000000, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0x80000000, 0x0000001f
+    TEST_RR_SIG sltu, 0x80000000, 0x12345678
+    TEST_RR_SIG sltu, 0x55555555, 0x00000000
+    TEST_RR_SIG sltu, 0x55555555, 0x00000001
+    TEST_RR_SIG sltu, 0x55555555, 0xffffffff
+    TEST_RR_SIG sltu, 0x55555555, 0x7fffffff
+    TEST_RR_SIG sltu, 0x55555555, 0x80000000
+    TEST_RR_SIG sltu, 0x55555555, 0x55555555
+    TEST_RR_SIG sltu, 0x55555555, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0x55555555, 0x0000001f
+    TEST_RR_SIG sltu, 0x55555555, 0x12345678
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0x00000000
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0x00000001
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0xffffffff
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0x7fffffff
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0x80000000
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0x55555555
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0x0000001f
+    TEST_RR_SIG sltu, 0xaaaaaaaa, 0x12345678
+    TEST_RR_SIG sltu, 0x0000001f, 0x00000000
+    TEST_RR_SIG sltu, 0x0000001f, 0x00000001
+    TEST_RR_SIG sltu, 0x0000001f, 0xffffffff
+    TEST_RR_SIG sltu, 0x0000001f, 0x7fffffff
+    TEST_RR_SIG sltu, 0x0000001f, 0x80000000
+    TEST_RR_SIG sltu, 0x0000001f, 0x55555555
+    TEST_RR_SIG sltu, 0x0000001f, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0x0000001f, 0x0000001f
+    TEST_RR_SIG sltu, 0x0000001f, 0x12345678
+    TEST_RR_SIG sltu, 0x12345678, 0x00000000
+    TEST_RR_SIG sltu, 0x12345678, 0x00000001
+    TEST_RR_SIG sltu, 0x12345678, 0xffffffff
+    TEST_RR_SIG sltu, 0x12345678, 0x7fffffff
+    TEST_RR_SIG sltu, 0x12345678, 0x80000000
+    TEST_RR_SIG sltu, 0x12345678, 0x55555555
+    TEST_RR_SIG sltu, 0x12345678, 0xaaaaaaaa
+    TEST_RR_SIG sltu, 0x12345678, 0x0000001f
+    TEST_RR_SIG sltu, 0x12345678, 0x12345678
+
+    RVMODEL_HALT
+
+RVTEST_DATA_BEGIN
+
+RVTEST_SIG_BEGIN 732
+RVTEST_SIG_END
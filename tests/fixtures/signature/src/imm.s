@@ -0,0 +1,551 @@
+.include "env.inc"
+
+RVTEST_CODE_BEGIN
+
+    TEST_IMM_SIG addi, 0x00000000, 0
+    TEST_IMM_SIG addi, 0x00000000, 1
+    TEST_IMM_SIG addi, 0x00000000, -1
+    TEST_IMM_SIG addi, 0x00000000, 2047
+    TEST_IMM_SIG addi, 0x00000000, -2048
+    TEST_IMM_SIG addi, 0x00000000, 1365
+    TEST_IMM_SIG addi, 0x00000000, -1366
+    TEST_IMM_SIG addi, 0x00000000, 31
+    TEST_IMM_SIG addi, 0x00000001, 0
+    TEST_IMM_SIG addi, 0x00000001, 1
+    TEST_IMM_SIG addi, 0x00000001, -1
+    TEST_IMM_SIG addi, 0x00000001, 2047
+    TEST_IMM_SIG addi, 0x00000001, -2048
+    TEST_IMM_SIG addi, 0x00000001, 1365
+    TEST_IMM_SIG addi, 0x00000001, -1366
+    TEST_IMM_SIG addi, 0x00000001, 31
+    TEST_IMM_SIG addi, 0xffffffff, 0
+    TEST_IMM_SIG addi, 0xffffffff, 1
+    TEST_IMM_SIG addi, 0xffffffff, -1
+    TEST_IMM_SIG addi, 0xffffffff, 2047
+    TEST_IMM_SIG addi, 0xffffffff, -2048
+    TEST_IMM_SIG addi, 0xffffffff, 1365
+    TEST_IMM_SIG addi, 0xffffffff, -1366
+    TEST_IMM_SIG addi, 0xffffffff, 31
+    TEST_IMM_SIG addi, 0x7fffffff, 0
+    TEST_IMM_SIG addi, 0x7fffffff, 1
+    TEST_IMM_SIG addi, 0x7fffffff, -1
+    TEST_IMM_SIG addi, 0x7fffffff, 2047
+    TEST_IMM_SIG addi, 0x7fffffff, -2048
+    TEST_IMM_SIG addi, 0x7fffffff, 1365
+    TEST_IMM_SIG addi, 0x7fffffff, -1366
+    TEST_IMM_SIG addi, 0x7fffffff, 31
+    TEST_IMM_SIG addi, 0x80000000, 0
+    TEST_IMM_SIG addi, 0x80000000, 1
+    TEST_IMM_SIG addi, 0x80000000, -1
+    TEST_IMM_SIG addi, 0x80000000, 2047
+    TEST_IMM_SIG addi, 0x80000000, -2048
+    TEST_IMM_SIG addi, 0x80000000, 1365
+    TEST_IMM_SIG addi, 0x80000000, -1366
+    TEST_IMM_SIG addi, 0x80000000, 31
+    TEST_IMM_SIG addi, 0x55555555, 0
+    TEST_IMM_SIG addi, 0x55555555, 1
+    TEST_IMM_SIG addi, 0x55555555, -1
+    TEST_IMM_SIG addi, 0x55555555, 2047
+    TEST_IMM_SIG addi, 0x55555555, -2048
+    TEST_IMM_SIG addi, 0x55555555, 1365
+    TEST_IMM_SIG addi, 0x55555555, -1366
+    TEST_IMM_SIG addi, 0x55555555, 31
+    TEST_IMM_SIG addi, 0xaaaaaaaa, 0
+    TEST_IMM_SIG addi, 0xaaaaaaaa, 1
+    TEST_IMM_SIG addi, 0xaaaaaaaa, -1
+    TEST_IMM_SIG addi, 0xaaaaaaaa, 2047
+    TEST_IMM_SIG addi, 0xaaaaaaaa, -2048
+    TEST_IMM_SIG addi, 0xaaaaaaaa, 1365
+    TEST_IMM_SIG addi, 0xaaaaaaaa, -1366
+    TEST_IMM_SIG addi, 0xaaaaaaaa, 31
+    TEST_IMM_SIG addi, 0x0000001f, 0
+    TEST_IMM_SIG addi, 0x0000001f, 1
+    TEST_IMM_SIG addi, 0x0000001f, -1
+    TEST_IMM_SIG addi, 0x0000001f, 2047
+    TEST_IMM_SIG addi, 0x0000001f, -2048
+    TEST_IMM_SIG addi, 0x0000001f, 1365
+    TEST_IMM_SIG addi, 0x0000001f, -1366
+    TEST_IMM_SIG addi, 0x0000001f, 31
+    TEST_IMM_SIG addi, 0x12345678, 0
+    TEST_IMM_SIG addi, 0x12345678, 1
+    TEST_IMM_SIG addi, 0x12345678, -1
+    TEST_IMM_SIG addi, 0x12345678, 2047
+    TEST_IMM_SIG addi, 0x12345678, -2048
+    TEST_IMM_SIG addi, 0x12345678, 1365
+    TEST_IMM_SIG addi, 0x12345678, -1366
+    TEST_IMM_SIG addi, 0x12345678, 31
+    TEST_IMM_SIG andi, 0x00000000, 0
+    TEST_IMM_SIG andi, 0x00000000, 1
+    TEST_IMM_SIG andi, 0x00000000, -1
+    TEST_IMM_SIG andi, 0x00000000, 2047
+    TEST_IMM_SIG andi, 0x00000000, -2048
+    TEST_IMM_SIG andi, 0x00000000, 1365
+    TEST_IMM_SIG andi, 0x00000000, -1366
+    TEST_IMM_SIG andi, 0x00000000, 31
+    TEST_IMM_SIG andi, 0x00000001, 0
+    TEST_IMM_SIG andi, 0x00000001, 1
+    TEST_IMM_SIG andi, 0x00000001, -1
+    TEST_IMM_SIG andi, 0x00000001, 2047
+    TEST_IMM_SIG andi, 0x00000001, -2048
+    TEST_IMM_SIG andi, 0x00000001, 1365
+    TEST_IMM_SIG andi, 0x00000001, -1366
+    TEST_IMM_SIG andi, 0x00000001, 31
+    TEST_IMM_SIG andi, 0xffffffff, 0
+    TEST_IMM_SIG andi, 0xffffffff, 1
+    TEST_IMM_SIG andi, 0xffffffff, -1
+    TEST_IMM_SIG andi, 0xffffffff, 2047
+    TEST_IMM_SIG andi, 0xffffffff, -2048
+    TEST_IMM_SIG andi, 0xffffffff, 1365
+    TEST_IMM_SIG andi, 0xffffffff, -1366
+    TEST_IMM_SIG andi, 0xffffffff, 31
+    TEST_IMM_SIG andi, 0x7fffffff, 0
+    TEST_IMM_SIG andi, 0x7fffffff, 1
+    TEST_IMM_SIG andi, 0x7fffffff, -1
+    TEST_IMM_SIG andi, 0x7fffffff, 2047
+    TEST_IMM_SIG andi, 0x7fffffff, -2048
+    TEST_IMM_SIG andi, 0x7fffffff, 1365
+    TEST_IMM_SIG andi, 0x7fffffff, -1366
+    TEST_IMM_SIG andi, 0x7fffffff, 31
+    TEST_IMM_SIG andi, 0x80000000, 0
+    TEST_IMM_SIG andi, 0x80000000, 1
+    TEST_IMM_SIG andi, 0x80000000, -1
+    TEST_IMM_SIG andi, 0x80000000, 2047
+    TEST_IMM_SIG andi, 0x80000000, -2048
+    TEST_IMM_SIG andi, 0x80000000, 1365
+    TEST_IMM_SIG andi, 0x80000000, -1366
+    TEST_IMM_SIG andi, 0x80000000, 31
+    TEST_IMM_SIG andi, 0x55555555, 0
+    TEST_IMM_SIG andi, 0x55555555, 1
+    TEST_IMM_SIG andi, 0x55555555, -1
+    TEST_IMM_SIG andi, 0x55555555, 2047
+    TEST_IMM_SIG andi, 0x55555555, -2048
+    TEST_IMM_SIG andi, 0x55555555, 1365
+    TEST_IMM_SIG andi, 0x55555555, -1366
+    TEST_IMM_SIG andi, 0x55555555, 31
+    TEST_IMM_SIG andi, 0xaaaaaaaa, 0
+    TEST_IMM_SIG andi, 0xaaaaaaaa, 1
+    TEST_IMM_SIG andi, 0xaaaaaaaa, -1
+    TEST_IMM_SIG andi, 0xaaaaaaaa, 2047
+    TEST_IMM_SIG andi, 0xaaaaaaaa, -2048
+    TEST_IMM_SIG andi, 0xaaaaaaaa, 1365
+    TEST_IMM_SIG andi, 0xaaaaaaaa, -1366
+    TEST_IMM_SIG andi, 0xaaaaaaaa, 31
+    TEST_IMM_SIG andi, 0x0000001f, 0
+    TEST_IMM_SIG andi, 0x0000001f, 1
+    TEST_IMM_SIG andi, 0x0000001f, -1
+    TEST_IMM_SIG andi, 0x0000001f, 2047
+    TEST_IMM_SIG andi, 0x0000001f, -2048
+    TEST_IMM_SIG andi, 0x0000001f, 1365
+    TEST_IMM_SIG andi, 0x0000001f, -1366
+    TEST_IMM_SIG andi, 0x0000001f, 31
+    TEST_IMM_SIG andi, 0x12345678, 0
+    TEST_IMM_SIG andi, 0x12345678, 1
+    TEST_IMM_SIG andi, 0x12345678, -1
+    TEST_IMM_SIG andi, 0x12345678, 2047
+    TEST_IMM_SIG andi, 0x12345678, -2048
+    TEST_IMM_SIG andi, 0x12345678, 1365
+    TEST_IMM_SIG andi, 0x12345678, -1366
+    TEST_IMM_SIG andi, 0x12345678, 31
+    TEST_IMM_SIG ori, 0x00000000, 0
+    TEST_IMM_SIG ori, 0x00000000, 1
+    TEST_IMM_SIG ori, 0x00000000, -1
+    TEST_IMM_SIG ori, 0x00000000, 2047
+    TEST_IMM_SIG ori, 0x00000000, -2048
+    TEST_IMM_SIG ori, 0x00000000, 1365
+    TEST_IMM_SIG ori, 0x00000000, -1366
+    TEST_IMM_SIG ori, 0x00000000, 31
+    TEST_IMM_SIG ori, 0x00000001, 0
+    TEST_IMM_SIG ori, 0x00000001, 1
+    TEST_IMM_SIG ori, 0x00000001, -1
+    TEST_IMM_SIG ori, 0x00000001, 2047
+    TEST_IMM_SIG ori, 0x00000001, -2048
+    TEST_IMM_SIG ori, 0x00000001, 1365
+    TEST_IMM_SIG ori, 0x00000001, -1366
+    TEST_IMM_SIG ori, 0x00000001, 31
+    TEST_IMM_SIG ori, 0xffffffff, 0
+    TEST_IMM_SIG ori, 0xffffffff, 1
+    TEST_IMM_SIG ori, 0xffffffff, -1
+    TEST_IMM_SIG ori, 0xffffffff, 2047
+    TEST_IMM_SIG ori, 0xffffffff, -2048
+    TEST_IMM_SIG ori, 0xffffffff, 1365
+    TEST_IMM_SIG ori, 0xffffffff, -1366
+    TEST_IMM_SIG ori, 0xffffffff, 31
+    TEST_IMM_SIG ori, 0x7fffffff, 0
+    TEST_IMM_SIG ori, 0x7fffffff, 1
+    TEST_IMM_SIG ori, 0x7fffffff, -1
+    TEST_IMM_SIG ori, 0x7fffffff, 2047
+    TEST_IMM_SIG ori, 0x7fffffff, -2048
+    TEST_IMM_SIG ori, 0x7fffffff, 1365
+    TEST_IMM_SIG ori, 0x7fffffff, -1366
+    TEST_IMM_SIG ori, 0x7fffffff, 31
+    TEST_IMM_SIG ori, 0x80000000, 0
+    TEST_IMM_SIG ori, 0x80000000, 1
+    TEST_IMM_SIG ori, 0x80000000, -1
+    TEST_IMM_SIG ori, 0x80000000, 2047
+    TEST_IMM_SIG ori, 0x80000000, -2048
+    TEST_IMM_SIG ori, 0x80000000, 1365
+    TEST_IMM_SIG ori, 0x80000000, -1366
+    TEST_IMM_SIG ori, 0x80000000, 31
+    TEST_IMM_SIG ori, 0x55555555, 0
+    TEST_IMM_SIG ori, 0x55555555, 1
+    TEST_IMM_SIG ori, 0x55555555, -1
+    TEST_IMM_SIG ori, 0x55555555, 2047
+    TEST_IMM_SIG ori, 0x55555555, -2048
+    TEST_IMM_SIG ori, 0x55555555, 1365
+    TEST_IMM_SIG ori, 0x55555555, -1366
+    TEST_IMM_SIG ori, 0x55555555, 31
+    TEST_IMM_SIG ori, 0xaaaaaaaa, 0
+    TEST_IMM_SIG ori, 0xaaaaaaaa, 1
+    TEST_IMM_SIG ori, 0xaaaaaaaa, -1
+    TEST_IMM_SIG ori, 0xaaaaaaaa, 2047
+    TEST_IMM_SIG ori, 0xaaaaaaaa, -2048
+    TEST_IMM_SIG ori, 0xaaaaaaaa, 1365
+    TEST_IMM_SIG ori, 0xaaaaaaaa, -1366
+    TEST_IMM_SIG ori, 0xaaaaaaaa, 31
+    TEST_IMM_SIG ori, 0x0000001f, 0
+    TEST_IMM_SIG ori, 0x0000001f, 1
+    TEST_IMM_SIG ori, 0x0000001f, -1
+    TEST_IMM_SIG ori, 0x0000001f, 2047
+    TEST_IMM_SIG ori, 0x0000001f, -2048
+    TEST_IMM_SIG ori, 0x0000001f, 1365
+    TEST_IMM_SIG ori, 0x0000001f, -1366
+    TEST_IMM_SIG ori, 0x0000001f, 31
+    TEST_IMM_SIG ori, 0x12345678, 0
+    TEST_IMM_SIG ori, 0x12345678, 1
+    TEST_IMM_SIG ori, 0x12345678, -1
+    TEST_IMM_SIG ori, 0x12345678, 2047
+    TEST_IMM_SIG ori, 0x12345678, -2048
+    TEST_IMM_SIG ori, 0x12345678, 1365
+    TEST_IMM_SIG ori, 0x12345678, -1366
+    TEST_IMM_SIG ori, 0x12345678, 31
+    TEST_IMM_SIG xori, 0x00000000, 0
+    TEST_IMM_SIG xori, 0x00000000, 1
+    TEST_IMM_SIG xori, 0x00000000, -1
+    TEST_IMM_SIG xori, 0x00000000, 2047
+    TEST_IMM_SIG xori, 0x00000000, -2048
+    TEST_IMM_SIG xori, 0x00000000, 1365
+    TEST_IMM_SIG xori, 0x00000000, -1366
+    TEST_IMM_SIG xori, 0x00000000, 31
+    TEST_IMM_SIG xori, 0x00000001, 0
+    TEST_IMM_SIG xori, 0x00000001, 1
+    TEST_IMM_SIG xori, 0x00000001, -1
+    TEST_IMM_SIG xori, 0x00000001, 2047
+    TEST_IMM_SIG xori, 0x00000001, -2048
+    TEST_IMM_SIG xori, 0x00000001, 1365
+    TEST_IMM_SIG xori, 0x00000001, -1366
+    TEST_IMM_SIG xori, 0x00000001, 31
+    TEST_IMM_SIG xori, 0xffffffff, 0
+    TEST_IMM_SIG xori, 0xffffffff, 1
+    TEST_IMM_SIG xori, 0xffffffff, -1
+    TEST_IMM_SIG xori, 0xffffffff, 2047
+    TEST_IMM_SIG xori, 0xffffffff, -2048
+    TEST_IMM_SIG xori, 0xffffffff, 1365
+    TEST_IMM_SIG xori, 0xffffffff, -1366
+    TEST_IMM_SIG xori, 0xffffffff, 31
+    TEST_IMM_SIG xori, 0x7fffffff, 0
+    TEST_IMM_SIG xori, 0x7fffffff, 1
+    TEST_IMM_SIG xori, 0x7fffffff, -1
+    TEST_IMM_SIG xori, 0x7fffffff, 2047
+    TEST_IMM_SIG xori, 0x7fffffff, -2048
+    TEST_IMM_SIG xori, 0x7fffffff, 1365
+    TEST_IMM_SIG xori, 0x7fffffff, -1366
+    TEST_IMM_SIG xori, 0x7fffffff, 31
+    TEST_IMM_SIG xori, 0x80000000, 0
+    TEST_IMM_SIG xori, 0x80000000, 1
+    TEST_IMM_SIG xori, 0x80000000, -1
+    TEST_IMM_SIG xori, 0x80000000, 2047
+    TEST_IMM_SIG xori, 0x80000000, -2048
+    TEST_IMM_SIG xori, 0x80000000, 1365
+    TEST_IMM_SIG xori, 0x80000000, -1366
+    TEST_IMM_SIG xori, 0x80000000, 31
+    TEST_IMM_SIG xori, 0x55555555, 0
+    TEST_IMM_SIG xori, 0x55555555, 1
+    TEST_IMM_SIG xori, 0x55555555, -1
+    TEST_IMM_SIG xori, 0x55555555, 2047
+    TEST_IMM_SIG xori, 0x55555555, -2048
+    TEST_IMM_SIG xori, 0x55555555, 1365
+    TEST_IMM_SIG xori, 0x55555555, -1366
+    TEST_IMM_SIG xori, 0x55555555, 31
+    TEST_IMM_SIG xori, 0xaaaaaaaa, 0
+    TEST_IMM_SIG xori, 0xaaaaaaaa, 1
+    TEST_IMM_SIG xori, 0xaaaaaaaa, -1
+    TEST_IMM_SIG xori, 0xaaaaaaaa, 2047
+    TEST_IMM_SIG xori, 0xaaaaaaaa, -2048
+    TEST_IMM_SIG xori, 0xaaaaaaaa, 1365
+    TEST_IMM_SIG xori, 0xaaaaaaaa, -1366
+    TEST_IMM_SIG xori, 0xaaaaaaaa, 31
+    TEST_IMM_SIG xori, 0x0000001f, 0
+    TEST_IMM_SIG xori, 0x0000001f, 1
+    TEST_IMM_SIG xori, 0x0000001f, -1
+    TEST_IMM_SIG xori, 0x0000001f, 2047
+    TEST_IMM_SIG xori, 0x0000001f, -2048
+    TEST_IMM_SIG xori, 0x0000001f, 1365
+    TEST_IMM_SIG xori, 0x0000001f, -1366
+    TEST_IMM_SIG xori, 0x0000001f, 31
+    TEST_IMM_SIG xori, 0x12345678, 0
+    TEST_IMM_SIG xori, 0x12345678, 1
+    TEST_IMM_SIG xori, 0x12345678, -1
+    TEST_IMM_SIG xori, 0x12345678, 2047
+    TEST_IMM_SIG xori, 0x12345678, -2048
+    TEST_IMM_SIG xori, 0x12345678, 1365
+    TEST_IMM_SIG xori, 0x12345678, -1366
+    TEST_IMM_SIG xori, 0x12345678, 31
+    TEST_IMM_SIG slli, 0x00000000, 0
+    TEST_IMM_SIG slli, 0x00000000, 1
+    TEST_IMM_SIG slli, 0x00000000, 15
+    TEST_IMM_SIG slli, 0x00000000, 31
+    TEST_IMM_SIG slli, 0x00000001, 0
+    TEST_IMM_SIG slli, 0x00000001, 1
+    TEST_IMM_SIG slli, 0x00000001, 15
+    TEST_IMM_SIG slli, 0x00000001, 31
+    TEST_IMM_SIG slli, 0xffffffff, 0
+    TEST_IMM_SIG slli, 0xffffffff, 1
+    TEST_IMM_SIG slli, 0xffffffff, 15
+    TEST_IMM_SIG slli, 0xffffffff, 31
+    TEST_IMM_SIG slli, 0x7fffffff, 0
+    TEST_IMM_SIG slli, 0x7fffffff, 1
+    TEST_IMM_SIG slli, 0x7fffffff, 15
+    TEST_IMM_SIG slli, 0x7fffffff, 31
+    TEST_IMM_SIG slli, 0x80000000, 0
+    TEST_IMM_SIG slli, 0x80000000, 1
+    TEST_IMM_SIG slli, 0x80000000, 15
+    TEST_IMM_SIG slli, 0x80000000, 31
+    TEST_IMM_SIG slli, 0x55555555, 0
+    TEST_IMM_SIG slli, 0x55555555, 1
+    TEST_IMM_SIG slli, 0x55555555, 15
+    TEST_IMM_SIG slli, 0x55555555, 31
+    TEST_IMM_SIG slli, 0xaaaaaaaa, 0
+    TEST_IMM_SIG slli, 0xaaaaaaaa, 1
+    TEST_IMM_SIG slli, 0xaaaaaaaa, 15
+    TEST_IMM_SIG slli, 0xaaaaaaaa, 31
+    TEST_IMM_SIG slli, 0x0000001f, 0
+    TEST_IMM_SIG slli, 0x0000001f, 1
+    TEST_IMM_SIG slli, 0x0000001f, 15
+    TEST_IMM_SIG slli, 0x0000001f, 31
+    TEST_IMM_SIG slli, 0x12345678, 0
+    TEST_IMM_SIG slli, 0x12345678, 1
+    TEST_IMM_SIG slli, 0x12345678, 15
+    TEST_IMM_SIG slli, 0x12345678, 31
+    TEST_IMM_SIG srli, 0x00000000, 0
+    TEST_IMM_SIG srli, 0x00000000, 1
+    TEST_IMM_SIG srli, 0x00000000, 15
+    TEST_IMM_SIG srli, 0x00000000, 31
+    TEST_IMM_SIG srli, 0x00000001, 0
+    TEST_IMM_SIG srli, 0x00000001, 1
+    TEST_IMM_SIG srli, 0x00000001, 15
+    TEST_IMM_SIG srli, 0x00000001, 31
+    TEST_IMM_SIG srli, 0xffffffff, 0
+    TEST_IMM_SIG srli, 0xffffffff, 1
+    TEST_IMM_SIG srli, 0xffffffff, 15
+    TEST_IMM_SIG srli, 0xffffffff, 31
+    TEST_IMM_SIG srli, 0x7fffffff, 0
+    TEST_IMM_SIG srli, 0x7fffffff, 1
+    TEST_IMM_SIG srli, 0x7fffffff, 15
+    TEST_IMM_SIG srli, 0x7fffffff, 31
+    TEST_IMM_SIG srli, 0x80000000, 0
+    TEST_IMM_SIG srli, 0x80000000, 1
+    TEST_IMM_SIG srli, 0x80000000, 15
+    TEST_IMM_SIG srli, 0x80000000, 31
+    TEST_IMM_SIG srli, 0x55555555, 0
+    TEST_IMM_SIG srli, 0x55555555, 1
+    TEST_IMM_SIG srli, 0x55555555, 15
+    TEST_IMM_SIG srli, 0x55555555, 31
+    TEST_IMM_SIG srli, 0xaaaaaaaa, 0
+    TEST_IMM_SIG srli, 0xaaaaaaaa, 1
+    TEST_IMM_SIG srli, 0xaaaaaaaa, 15
+    TEST_IMM_SIG srli, 0xaaaaaaaa, 31
+    TEST_IMM_SIG srli, 0x0000001f, 0
+    TEST_IMM_SIG srli, 0x0000001f, 1
+    TEST_IMM_SIG srli, 0x0000001f, 15
+    TEST_IMM_SIG srli, 0x0000001f, 31
+    TEST_IMM_SIG srli, 0x12345678, 0
+    TEST_IMM_SIG srli, 0x12345678, 1
+    TEST_IMM_SIG srli, 0x12345678, 15
+    TEST_IMM_SIG srli, 0x12345678, 31
+    TEST_IMM_SIG srai, 0x00000000, 0
+    TEST_IMM_SIG srai, 0x00000000, 1
+    TEST_IMM_SIG srai, 0x00000000, 15
+    TEST_IMM_SIG srai, 0x00000000, 31
+    TEST_IMM_SIG srai, 0x00000001, 0
+    TEST_IMM_SIG srai, 0x00000001, 1
+    TEST_IMM_SIG srai, 0x00000001, 15
+    TEST_IMM_SIG srai, 0x00000001, 31
+    TEST_IMM_SIG srai, 0xffffffff, 0
+    TEST_IMM_SIG srai, 0xffffffff, 1
+    TEST_IMM_SIG srai, 0xffffffff, 15
+    TEST_IMM_SIG srai, 0xffffffff, 31
+    TEST_IMM_SIG srai, 0x7fffffff, 0
+    TEST_IMM_SIG srai, 0x7fffffff, 1
+    TEST_IMM_SIG srai, 0x7fffffff, 15
+    TEST_IMM_SIG srai, 0x7fffffff, 31
+    TEST_IMM_SIG srai, 0x80000000, 0
+    TEST_IMM_SIG srai, 0x80000000, 1
+    TEST_IMM_SIG srai, 0x80000000, 15
+    TEST_IMM_SIG srai, 0x80000000, 31
+    TEST_IMM_SIG srai, 0x55555555, 0
+    TEST_IMM_SIG srai, 0x55555555, 1
+    TEST_IMM_SIG srai, 0x55555555, 15
+    TEST_IMM_SIG srai, 0x55555555, 31
+    TEST_IMM_SIG srai, 0xaaaaaaaa, 0
+    TEST_IMM_SIG srai, 0xaaaaaaaa, 1
+    TEST_IMM_SIG srai, 0xaaaaaaaa, 15
+    TEST_IMM_SIG srai, 0xaaaaaaaa, 31
+    TEST_IMM_SIG srai, 0x0000001f, 0
+    TEST_IMM_SIG srai, 0x0000001f, 1
+    TEST_IMM_SIG srai, 0x0000001f, 15
+    TEST_IMM_SIG srai, 0x0000001f, 31
+    TEST_IMM_SIG srai, 0x12345678, 0
+    TEST_IMM_SIG srai, 0x12345678, 1
+    TEST_IMM_SIG srai, 0x12345678, 15
+    TEST_IMM_SIG srai, 0x12345678, 31
+    TEST_IMM_SIG slti, 0x00000000, 0
+    TEST_IMM_SIG slti, 0x00000000, 1
+    TEST_IMM_SIG slti, 0x00000000, -1
+    TEST_IMM_SIG slti, 0x00000000, 2047
+    TEST_IMM_SIG slti, 0x00000000, -2048
+    TEST_IMM_SIG slti, 0x00000000, 1365
+    TEST_IMM_SIG slti, 0x00000000, -1366
+    TEST_IMM_SIG slti, 0x00000000, 31
+    TEST_IMM_SIG slti, 0x00000001, 0
+    TEST_IMM_SIG slti, 0x00000001, 1
+    TEST_IMM_SIG slti, 0x00000001, -1
+    TEST_IMM_SIG slti, 0x00000001, 2047
+    TEST_IMM_SIG slti, 0x00000001, -2048
+    TEST_IMM_SIG slti, 0x00000001, 1365
+    TEST_IMM_SIG slti, 0x00000001, -1366
+    TEST_IMM_SIG slti, 0x00000001, 31
+    TEST_IMM_SIG slti, 0xffffffff, 0
+    TEST_IMM_SIG slti, 0xffffffff, 1
+    TEST_IMM_SIG slti, 0xffffffff, -1
+    TEST_IMM_SIG slti, 0xffffffff, 2047
+    TEST_IMM_SIG slti, 0xffffffff, -2048
+    TEST_IMM_SIG slti, 0xffffffff, 1365
+    TEST_IMM_SIG slti, 0xffffffff, -1366
+    TEST_IMM_SIG slti, 0xffffffff, 31
+    TEST_IMM_SIG slti, 0x7fffffff, 0
+    TEST_IMM_SIG slti, 0x7fffffff, 1
+    TEST_IMM_SIG slti, 0x7fffffff, -1
+    TEST_IMM_SIG slti, 0x7fffffff, 2047
+    TEST_IMM_SIG slti, 0x7fffffff, -2048
+    TEST_IMM_SIG slti, 0x7fffffff, 1365
+    TEST_IMM_SIG slti, 0x7fffffff, -1366
+    TEST_IMM_SIG slti, 0x7fffffff, 31
+    TEST_IMM_SIG slti, 0x80000000, 0
+    TEST_IMM_SIG slti, 0x80000000, 1
+    TEST_IMM_SIG slti, 0x80000000, -1
+    TEST_IMM_SIG slti, 0x80000000, 2047
+    TEST_IMM_SIG slti, 0x80000000, -2048
+    TEST_IMM_SIG slti, 0x80000000, 1365
+    TEST_IMM_SIG slti, 0x80000000, -1366
+    TEST_IMM_SIG slti, 0x80000000, 31
+    TEST_IMM_SIG slti, 0x55555555, 0
+    TEST_IMM_SIG slti, 0x55555555, 1
+    TEST_IMM_SIG slti, 0x55555555, -1
+    TEST_IMM_SIG slti, 0x55555555, 2047
+    TEST_IMM_SIG slti, 0x55555555, -2048
+    TEST_IMM_SIG slti, 0x55555555, 1365
+    TEST_IMM_SIG slti, 0x55555555, -1366
+    TEST_IMM_SIG slti, 0x55555555, 31
+    TEST_IMM_SIG slti, 0xaaaaaaaa, 0
+    TEST_IMM_SIG slti, 0xaaaaaaaa, 1
+    TEST_IMM_SIG slti, 0xaaaaaaaa, -1
+    TEST_IMM_SIG slti, 0xaaaaaaaa, 2047
+    TEST_IMM_SIG slti, 0xaaaaaaaa, -2048
+    TEST_IMM_SIG slti, 0xaaaaaaaa, 1365
+    TEST_IMM_SIG slti, 0xaaaaaaaa, -1366
+    TEST_IMM_SIG slti, 0xaaaaaaaa, 31
+    TEST_IMM_SIG slti, 0x0000001f, 0
+    TEST_IMM_SIG slti, 0x0000001f, 1
+    TEST_IMM_SIG slti, 0x0000001f, -1
+    TEST_IMM_SIG slti, 0x0000001f, 2047
+    TEST_IMM_SIG slti, 0x0000001f, -2048
+    TEST_IMM_SIG slti, 0x0000001f, 1365
+    TEST_IMM_SIG slti, 0x0000001f, -1366
+    TEST_IMM_SIG slti, 0x0000001f, 31
+    TEST_IMM_SIG slti, 0x12345678, 0
+    TEST_IMM_SIG slti, 0x12345678, 1
+    TEST_IMM_SIG slti, 0x12345678, -1
+    TEST_IMM_SIG slti, 0x12345678, 2047
+    TEST_IMM_SIG slti, 0x12345678, -2048
+    TEST_IMM_SIG slti, 0x12345678, 1365
+    TEST_IMM_SIG slti, 0x12345678, -1366
+    TEST_IMM_SIG slti, 0x12345678, 31
+    TEST_IMM_SIG sltiu, 0x00000000, 0
+    TEST_IMM_SIG sltiu, 0x00000000, 1
+    TEST_IMM_SIG sltiu, 0x00000000, -1
+    TEST_IMM_SIG sltiu, 0x00000000, 2047
+    TEST_IMM_SIG sltiu, 0x00000000, -2048
+    TEST_IMM_SIG sltiu, 0x00000000, 1365
+    TEST_IMM_SIG sltiu, 0x00000000, -1366
+    TEST_IMM_SIG sltiu, 0x00000000, 31
+    TEST_IMM_SIG sltiu, 0x00000001, 0
+    TEST_IMM_SIG sltiu, 0x00000001, 1
+    TEST_IMM_SIG sltiu, 0x00000001, -1
+    TEST_IMM_SIG sltiu, 0x00000001, 2047
+    TEST_IMM_SIG sltiu, 0x00000001, -2048
+    TEST_IMM_SIG sltiu, 0x00000001, 1365
+    TEST_IMM_SIG sltiu, 0x00000001, -1366
+    TEST_IMM_SIG sltiu, 0x00000001, 31
+    TEST_IMM_SIG sltiu, 0xffffffff, 0
+    TEST_IMM_SIG sltiu, 0xffffffff, 1
+    TEST_IMM_SIG sltiu, 0xffffffff, -1
+    TEST_IMM_SIG sltiu, 0xffffffff, 2047
+    TEST_IMM_SIG sltiu, 0xffffffff, -2048
+    TEST_IMM_SIG sltiu, 0xffffffff, 1365
+    TEST_IMM_SIG sltiu, 0xffffffff, -1366
+    TEST_IMM_SIG sltiu, 0xffffffff, 31
+    TEST_IMM_SIG sltiu, 0x7fffffff, 0
+    TEST_IMM_SIG sltiu, 0x7fffffff, 1
+    TEST_IMM_SIG sltiu, 0x7fffffff, -1
+    TEST_IMM_SIG sltiu, 0x7fffffff, 2047
+    TEST_IMM_SIG sltiu, 0x7fffffff, -2048
+    TEST_IMM_SIG sltiu, 0x7fffffff, 1365
+    TEST_IMM_SIG sltiu, 0x7fffffff, -1366
+    TEST_IMM_SIG sltiu, 0x7fffffff, 31
+    TEST_IMM_SIG sltiu, 0x80000000, 0
+    TEST_IMM_SIG sltiu, 0x80000000, 1
+    TEST_IMM_SIG sltiu, 0x80000000, -1
+    TEST_IMM_SIG sltiu, 0x80000000, 2047
+    TEST_IMM_SIG sltiu, 0x80000000, -2048
+    TEST_IMM_SIG sltiu, 0x80000000, 1365
+    TEST_IMM_SIG sltiu, 0x80000000, -1366
+    TEST_IMM_SIG sltiu, 0x80000000, 31
+    TEST_IMM_SIG sltiu, 0x55555555, 0
+    TEST_IMM_SIG sltiu, 0x55555555, 1
+    TEST_IMM_SIG sltiu, 0x55555555, -1
+    TEST_IMM_SIG sltiu, 0x55555555, 2047
+    TEST_IMM_SIG sltiu, 0x55555555, -2048
+    TEST_IMM_SIG sltiu, 0x55555555, 1365
+    TEST_IMM_SIG sltiu, 0x55555555, -1366
+    TEST_IMM_SIG sltiu, 0x55555555, 31
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, 0
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, 1
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, -1
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, 2047
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, -2048
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, 1365
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, -1366
+    TEST_IMM_SIG sltiu, 0xaaaaaaaa, 31
+    TEST_IMM_SIG sltiu, 0x0000001f, 0
+    TEST_IMM_SIG sltiu, 0x0000001f, 1
+    TEST_IMM_SIG sltiu, 0x0000001f, -1
+    TEST_IMM_SIG sltiu, 0x0000001f, 2047
+    TEST_IMM_SIG sltiu, 0x0000001f, -2048
+    TEST_IMM_SIG sltiu, 0x0000001f, 1365
+    TEST_IMM_SIG sltiu, 0x0000001f, -1366
+    TEST_IMM_SIG sltiu, 0x0000001f, 31
+    TEST_IMM_SIG sltiu, 0x12345678, 0
+    TEST_IMM_SIG sltiu, 0x12345678, 1
+    TEST_IMM_SIG sltiu, 0x12345678, -1
+    TEST_IMM_SIG sltiu, 0x12345678, 2047
+    TEST_IMM_SIG sltiu, 0x12345678, -2048
+    TEST_IMM_SIG sltiu, 0x12345678, 1365
+    TEST_IMM_SIG sltiu, 0x12345678, -1366
+    TEST_IMM_SIG sltiu, 0x12345678, 31
+
+    RVMODEL_HALT
+
+RVTEST_DATA_BEGIN
+
+RVTEST_SIG_BEGIN 540
+RVTEST_SIG_END
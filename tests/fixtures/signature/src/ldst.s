@@ -0,0 +1,73 @@
+.include "env.inc"
+
+RVTEST_CODE_BEGIN
+
+    TEST_LOAD_SIG lb, 0, rvtest_data
+    TEST_LOAD_SIG lb, 1, rvtest_data
+    TEST_LOAD_SIG lb, 2, rvtest_data
+    TEST_LOAD_SIG lb, 3, rvtest_data
+    TEST_LOAD_SIG lb, 4, rvtest_data
+    TEST_LOAD_SIG lb, 5, rvtest_data
+    TEST_LOAD_SIG lb, 6, rvtest_data
+    TEST_LOAD_SIG lb, 7, rvtest_data
+    TEST_LOAD_SIG lb, 8, rvtest_data
+    TEST_LOAD_SIG lb, 9, rvtest_data
+    TEST_LOAD_SIG lb, 10, rvtest_data
+    TEST_LOAD_SIG lb, 11, rvtest_data
+    TEST_LOAD_SIG lb, 12, rvtest_data
+    TEST_LOAD_SIG lb, 13, rvtest_data
+    TEST_LOAD_SIG lb, 14, rvtest_data
+    TEST_LOAD_SIG lb, 15, rvtest_data
+    TEST_LOAD_SIG lbu, 0, rvtest_data
+    TEST_LOAD_SIG lbu, 1, rvtest_data
+    TEST_LOAD_SIG lbu, 2, rvtest_data
+    TEST_LOAD_SIG lbu, 3, rvtest_data
+    TEST_LOAD_SIG lbu, 4, rvtest_data
+    TEST_LOAD_SIG lbu, 5, rvtest_data
+    TEST_LOAD_SIG lbu, 6, rvtest_data
+    TEST_LOAD_SIG lbu, 7, rvtest_data
+    TEST_LOAD_SIG lbu, 8, rvtest_data
+    TEST_LOAD_SIG lbu, 9, rvtest_data
+    TEST_LOAD_SIG lbu, 10, rvtest_data
+    TEST_LOAD_SIG lbu, 11, rvtest_data
+    TEST_LOAD_SIG lbu, 12, rvtest_data
+    TEST_LOAD_SIG lbu, 13, rvtest_data
+    TEST_LOAD_SIG lbu, 14, rvtest_data
+    TEST_LOAD_SIG lbu, 15, rvtest_data
+    TEST_LOAD_SIG lh, 0, rvtest_data
+    TEST_LOAD_SIG lh, 2, rvtest_data
+    TEST_LOAD_SIG lh, 4, rvtest_data
+    TEST_LOAD_SIG lh, 6, rvtest_data
+    TEST_LOAD_SIG lh, 8, rvtest_data
+    TEST_LOAD_SIG lh, 10, rvtest_data
+    TEST_LOAD_SIG lh, 12, rvtest_data
+    TEST_LOAD_SIG lh, 14, rvtest_data
+    TEST_LOAD_SIG lhu, 0, rvtest_data
+    TEST_LOAD_SIG lhu, 2, rvtest_data
+    TEST_LOAD_SIG lhu, 4, rvtest_data
+    TEST_LOAD_SIG lhu, 6, rvtest_data
+    TEST_LOAD_SIG lhu, 8, rvtest_data
+    TEST_LOAD_SIG lhu, 10, rvtest_data
+    TEST_LOAD_SIG lhu, 12, rvtest_data
+    TEST_LOAD_SIG lhu, 14, rvtest_data
+    TEST_LOAD_SIG lw, 0, rvtest_data
+    TEST_LOAD_SIG lw, 4, rvtest_data
+    TEST_LOAD_SIG lw, 8, rvtest_data
+    TEST_LOAD_SIG lw, 12, rvtest_data
+    TEST_STORE_SIG sb, 0x12345678, 0
+    TEST_STORE_SIG sb, 0x9abcdef0, 1
+    TEST_STORE_SIG sh, 0x12345678, 0
+    TEST_STORE_SIG sh, 0x9abcdef0, 2
+    TEST_STORE_SIG sw, 0x12345678, 0
+    TEST_STORE_SIG sw, 0x9abcdef0, 0
+
+    RVMODEL_HALT
+
+RVTEST_DATA_BEGIN
+
+    .align 4
+rvtest_data:
+    .byte 0x80, 0x7f, 0x01, 0xfe, 0x34, 0x12, 0xcd, 0xab, 0xff, 0xff, 0x00, 0x80, 0x78, 0x56, 0x34, 0x12
+
+RVTEST_SIG_BEGIN 60
+RVTEST_SIG_END
@@ -0,0 +1,663 @@
+//! Runs single precision ops of the F extension and double precision ops of the D
+//! extension on chosen operands and checks the result, the fflags they raise, and how
+//! single values are NaN-boxed in the 64-bit f registers, as well as how mstatus.FS turns
+//! the FPU off and records writes to its state. The assembler takes no F or D
+//! instructions, so they are encoded as `.word`s.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    assembler,
+    instruction::{Instruction, definitions::Op},
+    isa::Xlen,
+    machine::{Machine, MachineError},
+};
+
+/* Rounding modes, as in the rm field and frm. */
+const RNE: i32 = 0;
+const RTZ: i32 = 1;
+const RDN: i32 = 2;
+const RUP: i32 = 3;
+const RMM: i32 = 4;
+const DYN: i32 = 7;
+
+/* fflags bits. */
+const NX: u8 = 1 << 0;
+const UF: u8 = 1 << 1;
+const OF: u8 = 1 << 2;
+const DZ: u8 = 1 << 3;
+const NV: u8 = 1 << 4;
+
+const CANONICAL_NAN: u32 = 0x7FC0_0000;
+const SIGNALING_NAN: u32 = 0x7F80_0001;
+const CANONICAL_NAN_D: u64 = 0x7FF8_0000_0000_0000;
+const SIGNALING_NAN_D: u64 = 0x7FF0_0000_0000_0001;
+
+/// What an op left behind.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    /// f10, all 64 bits of it.
+    f: u64,
+    /// x10, for ops with an integer result.
+    x: i64,
+    fflags: u8,
+}
+
+impl Outcome {
+    /* The single precision result in f10, which must be NaN-boxed. */
+    fn single(&self) -> u32 {
+        assert_eq!(self.f >> 32, 0xFFFF_FFFF, "{:#X} is not NaN-boxed", self.f);
+        self.f as u32
+    }
+}
+
+fn boxed(bits: u32) -> u64 {
+    0xFFFF_FFFF_0000_0000 | bits as u64
+}
+
+fn single(value: f32) -> u64 {
+    boxed(value.to_bits())
+}
+
+fn double(value: f64) -> u64 {
+    value.to_bits()
+}
+
+fn word(op: Op, rd: u8, rs1: u8, rs2: u8, imm: i32) -> String {
+    let raw =
+        Instruction::encode(op, u5::new(rd), u5::new(rs1), u5::new(rs2), imm, Xlen::Rv32).unwrap();
+
+    format!(".word {raw:#010x}")
+}
+
+/* Runs `op f10/x10, f1/x1, f2/x2, f3` with frm set and the three operands in f1 to f3.
+ * x1 and x2 hold the low words of the first two, for ops reading integer registers.
+ * imm is the rounding mode, or `3 << 3 | rm` for fused multiply-add. */
+fn execute(op: Op, imm: i32, frm: u8, operands: [u64; 3]) -> Result<Outcome, MachineError> {
+    let uses_rs2 = op.encoding(Xlen::Rv32).unwrap().rs2.is_none();
+    let source = format!(
+        "
+        la      s0, operands
+        {}
+        {}
+        {}
+        lw      ra, 0(s0)
+        lw      sp, 8(s0)
+        csrwi   frm, {frm}
+        {}
+        {}
+        csrr    a1, fflags
+1:      j       1b
+
+        .data
+operands: .dword {:#x}, {:#x}, {:#x}, 0
+        ",
+        word(Op::fld, 1, 8, 0, 0),
+        word(Op::fld, 2, 8, 0, 8),
+        word(Op::fld, 3, 8, 0, 16),
+        word(op, 10, 1, if uses_rs2 { 2 } else { 0 }, imm),
+        word(Op::fsd, 0, 8, 10, 24),
+        operands[0],
+        operands[1],
+        operands[2],
+    );
+    let program = assembler::assemble(&source).unwrap();
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.execute_until_loop()?;
+
+    let result = program.symbol("operands").unwrap() + 24;
+    let f = machine.read_memory(result, 8).unwrap();
+    Ok(Outcome {
+        f: u64::from_le_bytes(f.try_into().unwrap()),
+        x: machine.registers()[10],
+        fflags: machine.registers()[11] as u8,
+    })
+}
+
+/* Runs an op with a static rounding mode. */
+fn run(op: Op, rm: i32, a: u64, b: u64) -> Outcome {
+    execute(op, rm, 0, [a, b, 0]).unwrap()
+}
+
+#[test]
+fn rounding_modes() {
+    // 1/3 and -1/3 are inexact, and 1 + 2^-24 is halfway between 1 and its successor.
+    let tie = f32::from_bits(0x3380_0000);
+    for (rm, third, minus_third, halfway) in [
+        (RNE, 0x3EAA_AAAB, 0xBEAA_AAAB, 0x3F80_0000),
+        (RTZ, 0x3EAA_AAAA, 0xBEAA_AAAA, 0x3F80_0000),
+        (RDN, 0x3EAA_AAAA, 0xBEAA_AAAB, 0x3F80_0000),
+        (RUP, 0x3EAA_AAAB, 0xBEAA_AAAA, 0x3F80_0001),
+        (RMM, 0x3EAA_AAAB, 0xBEAA_AAAB, 0x3F80_0001),
+    ] {
+        let outcome = run(Op::fdiv_s, rm, single(1.0), single(3.0));
+        assert_eq!(
+            (outcome.single(), outcome.fflags),
+            (third, NX),
+            "1/3, rm {rm}"
+        );
+        let outcome = run(Op::fdiv_s, rm, single(-1.0), single(3.0));
+        assert_eq!(outcome.single(), minus_third, "-1/3, rm {rm}");
+        let outcome = run(Op::fadd_s, rm, single(1.0), single(tie));
+        assert_eq!(
+            (outcome.single(), outcome.fflags),
+            (halfway, NX),
+            "tie, rm {rm}"
+        );
+
+        // the dynamic rounding mode is the one in frm.
+        let outcome = execute(Op::fadd_s, DYN, rm as u8, [single(1.0), single(tie), 0]).unwrap();
+        assert_eq!(outcome.single(), halfway, "tie, frm {rm}");
+    }
+}
+
+#[test]
+fn invalid_rounding_modes() {
+    for (rm, frm) in [(5, 0), (6, 0), (DYN, 5), (DYN, 7)] {
+        assert!(
+            matches!(
+                execute(Op::fadd_s, rm, frm, [single(1.0), single(2.0), 0]),
+                Err(MachineError::InvalidRoundingMode(_))
+            ),
+            "rm {rm}, frm {frm}"
+        );
+    }
+
+    // ops without a rounding mode do not care what frm holds.
+    let outcome = execute(Op::fmin_s, 0, 5, [single(1.0), single(2.0), 0]).unwrap();
+    assert_eq!(outcome.single(), 1.0f32.to_bits());
+}
+
+#[test]
+fn exception_flags() {
+    let max = f32::MAX;
+    let tiny = f32::from_bits(1);
+    for (op, rm, a, b, expected, fflags) in [
+        (Op::fadd_s, RNE, 1.0, 2.0, 3.0f32.to_bits(), 0),
+        (Op::fdiv_s, RNE, 1.0, 0.0, 0x7F80_0000, DZ),
+        (Op::fdiv_s, RNE, -1.0, 0.0, 0xFF80_0000, DZ),
+        (Op::fdiv_s, RNE, 0.0, 0.0, CANONICAL_NAN, NV),
+        (
+            Op::fsub_s,
+            RNE,
+            f32::INFINITY,
+            f32::INFINITY,
+            CANONICAL_NAN,
+            NV,
+        ),
+        (Op::fmul_s, RNE, 0.0, f32::INFINITY, CANONICAL_NAN, NV),
+        (Op::fmul_s, RNE, max, 2.0, 0x7F80_0000, OF | NX),
+        (Op::fmul_s, RTZ, max, 2.0, max.to_bits(), OF | NX),
+        (Op::fmul_s, RDN, -max, 2.0, 0xFF80_0000, OF | NX),
+        (Op::fmul_s, RNE, 1e-30, 1e-30, 0, UF | NX),
+        (Op::fmul_s, RUP, 1e-30, 1e-30, 1, UF | NX),
+        // an exact subnormal result does not underflow.
+        (Op::fmul_s, RNE, tiny, 1.0, 1, 0),
+        (Op::fsqrt_s, RNE, -1.0, 0.0, CANONICAL_NAN, NV),
+        (Op::fsqrt_s, RNE, -0.0, 0.0, 0x8000_0000, 0),
+        (Op::fsqrt_s, RNE, 2.0, 0.0, 2f32.sqrt().to_bits(), NX),
+    ] {
+        let outcome = run(op, rm, single(a), single(b));
+        assert_eq!(
+            (outcome.single(), outcome.fflags),
+            (expected, fflags),
+            "{op} {a} {b}, rm {rm}"
+        );
+    }
+
+    // a signaling NaN operand is invalid, and NaN results are canonical.
+    let outcome = run(Op::fadd_s, RNE, boxed(SIGNALING_NAN), single(1.0));
+    assert_eq!((outcome.single(), outcome.fflags), (CANONICAL_NAN, NV));
+    let outcome = run(Op::fadd_s, RNE, boxed(0xFFC0_1234), single(1.0));
+    assert_eq!((outcome.single(), outcome.fflags), (CANONICAL_NAN, 0));
+}
+
+#[test]
+fn fused_multiply_add_rounds_once() {
+    // (1 + 2^-12)^2 - (1 + 2^-11) is exactly 2^-24, while rounding the product first
+    // loses it.
+    let a = single(f32::from_bits(0x3F80_0800));
+    let c = single(-f32::from_bits(0x3F80_1000));
+    let outcome = execute(Op::fmadd_s, 3 << 3 | RNE, 0, [a, a, c]).unwrap();
+    assert_eq!((outcome.single(), outcome.fflags), (0x3380_0000, 0));
+
+    let outcome = execute(Op::fnmadd_s, 3 << 3 | RNE, 0, [a, a, c]).unwrap();
+    assert_eq!(outcome.single(), 0xB380_0000);
+
+    // inf * 0 is invalid, even when the addend is a quiet NaN.
+    let outcome = execute(
+        Op::fmadd_s,
+        3 << 3 | RNE,
+        0,
+        [single(f32::INFINITY), single(0.0), boxed(CANONICAL_NAN)],
+    )
+    .unwrap();
+    assert_eq!((outcome.single(), outcome.fflags), (CANONICAL_NAN, NV));
+}
+
+#[test]
+fn conversions_saturate() {
+    let nan = f32::from_bits(CANONICAL_NAN);
+    for (op, rm, a, expected, fflags) in [
+        (Op::fcvt_w_s, RTZ, 3e9, i32::MAX as i64, NV),
+        (Op::fcvt_w_s, RTZ, -3e9, i32::MIN as i64, NV),
+        (Op::fcvt_w_s, RTZ, f32::INFINITY, i32::MAX as i64, NV),
+        (Op::fcvt_w_s, RTZ, f32::NEG_INFINITY, i32::MIN as i64, NV),
+        (Op::fcvt_w_s, RTZ, nan, i32::MAX as i64, NV),
+        (Op::fcvt_w_s, RTZ, -2147483648.0, i32::MIN as i64, 0),
+        (Op::fcvt_w_s, RNE, 2.5, 2, NX),
+        (Op::fcvt_w_s, RMM, 2.5, 3, NX),
+        (Op::fcvt_w_s, RUP, 2.5, 3, NX),
+        (Op::fcvt_w_s, RDN, -2.5, -3, NX),
+        (Op::fcvt_wu_s, RTZ, -1.0, 0, NV),
+        // rounds to 0, so it is only inexact.
+        (Op::fcvt_wu_s, RTZ, -0.5, 0, NX),
+        // 32-bit results are sign-extended, even unsigned ones.
+        (Op::fcvt_wu_s, RTZ, 4e9, 4_000_000_000u32 as i32 as i64, 0),
+        (Op::fcvt_wu_s, RTZ, 5e9, -1, NV),
+        (Op::fcvt_wu_s, RTZ, nan, -1, NV),
+    ] {
+        let outcome = run(op, rm, single(a), 0);
+        assert_eq!(
+            (outcome.x, outcome.fflags),
+            (expected, fflags),
+            "{op} {a}, rm {rm}"
+        );
+    }
+
+    // from integers: x1 holds the low word of the first operand.
+    let outcome = run(Op::fcvt_s_w, RNE, (-7i32) as u32 as u64, 0);
+    assert_eq!((outcome.single(), outcome.fflags), ((-7.0f32).to_bits(), 0));
+    let outcome = run(Op::fcvt_s_wu, RTZ, u32::MAX as u64, 0);
+    assert_eq!((outcome.single(), outcome.fflags), (0x4F7F_FFFF, NX));
+}
+
+#[test]
+fn nan_boxing() {
+    // a single operand without its upper 32 bits set reads as the canonical NaN.
+    let unboxed = 1.0f32.to_bits() as u64;
+    let outcome = run(Op::fadd_s, RNE, unboxed, single(1.0));
+    assert_eq!((outcome.single(), outcome.fflags), (CANONICAL_NAN, 0));
+    let outcome = run(Op::fsgnjn_s, 0, unboxed, single(1.0));
+    assert_eq!(outcome.single(), 0xFFC0_0000);
+
+    // results are boxed, moves from x included.
+    let outcome = run(Op::fsgnj_s, 0, single(2.0), single(-1.0));
+    assert_eq!(outcome.f, single(-2.0));
+    let outcome = run(Op::fmv_w_x, 0, 0x1234_5678, 0);
+    assert_eq!(outcome.f, boxed(0x1234_5678));
+
+    // moves to x take the low bits as they are, boxed or not.
+    let outcome = run(Op::fmv_x_w, 0, unboxed, 0);
+    assert_eq!(outcome.x, 1.0f32.to_bits() as i64);
+    let outcome = run(Op::fmv_x_w, 0, single(-1.0), 0);
+    assert_eq!(outcome.x, (-1.0f32).to_bits() as i32 as i64);
+
+    // fclass sees the canonical NaN too: quiet NaN is bit 9.
+    let outcome = run(Op::fclass_s, 0, unboxed, 0);
+    assert_eq!(outcome.x, 1 << 9);
+}
+
+#[test]
+fn min_max() {
+    let quiet = boxed(CANONICAL_NAN);
+    let signaling = boxed(SIGNALING_NAN);
+    for (op, a, b, expected, fflags) in [
+        (Op::fmin_s, single(-0.0), single(0.0), 0x8000_0000, 0),
+        (Op::fmin_s, single(0.0), single(-0.0), 0x8000_0000, 0),
+        (Op::fmax_s, single(-0.0), single(0.0), 0, 0),
+        (Op::fmax_s, single(0.0), single(-0.0), 0, 0),
+        (
+            Op::fmin_s,
+            single(-1.0),
+            single(2.0),
+            (-1.0f32).to_bits(),
+            0,
+        ),
+        (Op::fmax_s, single(-1.0), single(2.0), 2.0f32.to_bits(), 0),
+        // a NaN operand gives the other one, and only a signaling one is invalid.
+        (Op::fmin_s, quiet, single(1.0), 1.0f32.to_bits(), 0),
+        (Op::fmax_s, single(1.0), quiet, 1.0f32.to_bits(), 0),
+        (Op::fmin_s, signaling, single(1.0), 1.0f32.to_bits(), NV),
+        (Op::fmax_s, single(1.0), signaling, 1.0f32.to_bits(), NV),
+        (Op::fmin_s, quiet, quiet, CANONICAL_NAN, 0),
+        (Op::fmax_s, signaling, quiet, CANONICAL_NAN, NV),
+    ] {
+        let outcome = run(op, 0, a, b);
+        assert_eq!(
+            (outcome.single(), outcome.fflags),
+            (expected, fflags),
+            "{op} {a:#X} {b:#X}"
+        );
+    }
+}
+
+#[test]
+fn comparisons() {
+    let quiet = boxed(CANONICAL_NAN);
+    let signaling = boxed(SIGNALING_NAN);
+    for (op, a, b, expected, fflags) in [
+        (Op::feq_s, single(-0.0), single(0.0), 1, 0),
+        (Op::fle_s, single(-0.0), single(0.0), 1, 0),
+        (Op::flt_s, single(-0.0), single(0.0), 0, 0),
+        (Op::flt_s, single(-1.0), single(0.0), 1, 0),
+        // feq is quiet, flt and fle signal on any NaN.
+        (Op::feq_s, quiet, single(1.0), 0, 0),
+        (Op::feq_s, signaling, single(1.0), 0, NV),
+        (Op::flt_s, quiet, single(1.0), 0, NV),
+        (Op::fle_s, single(1.0), quiet, 0, NV),
+    ] {
+        let outcome = run(op, 0, a, b);
+        assert_eq!((outcome.x, outcome.fflags), (expected, fflags), "{op}");
+    }
+}
+
+#[test]
+fn double_rounding_modes() {
+    // 1 + 2^-53 is halfway between 1 and its successor.
+    let tie = 2f64.powi(-53);
+    for (rm, third, minus_third, halfway) in [
+        (
+            RNE,
+            0x3FD5_5555_5555_5555,
+            0xBFD5_5555_5555_5555,
+            0x3FF0_0000_0000_0000,
+        ),
+        (
+            RTZ,
+            0x3FD5_5555_5555_5555,
+            0xBFD5_5555_5555_5555,
+            0x3FF0_0000_0000_0000,
+        ),
+        (
+            RDN,
+            0x3FD5_5555_5555_5555,
+            0xBFD5_5555_5555_5556,
+            0x3FF0_0000_0000_0000,
+        ),
+        (
+            RUP,
+            0x3FD5_5555_5555_5556,
+            0xBFD5_5555_5555_5555,
+            0x3FF0_0000_0000_0001,
+        ),
+        (
+            RMM,
+            0x3FD5_5555_5555_5555,
+            0xBFD5_5555_5555_5555,
+            0x3FF0_0000_0000_0001,
+        ),
+    ] {
+        let outcome = run(Op::fdiv_d, rm, double(1.0), double(3.0));
+        assert_eq!((outcome.f, outcome.fflags), (third, NX), "1/3, rm {rm}");
+        let outcome = run(Op::fdiv_d, rm, double(-1.0), double(3.0));
+        assert_eq!(outcome.f, minus_third, "-1/3, rm {rm}");
+        let outcome = run(Op::fadd_d, rm, double(1.0), double(tie));
+        assert_eq!((outcome.f, outcome.fflags), (halfway, NX), "tie, rm {rm}");
+
+        let outcome = execute(Op::fadd_d, DYN, rm as u8, [double(1.0), double(tie), 0]).unwrap();
+        assert_eq!(outcome.f, halfway, "tie, frm {rm}");
+    }
+}
+
+#[test]
+fn double_exception_flags() {
+    let max = f64::MAX;
+    for (op, rm, a, b, expected, fflags) in [
+        (Op::fadd_d, RNE, 1.0, 2.0, double(3.0), 0),
+        (Op::fsub_d, RNE, 1.0, 3.0, double(-2.0), 0),
+        (Op::fmul_d, RNE, 1.5, -4.0, double(-6.0), 0),
+        (Op::fdiv_d, RNE, 1.0, 0.0, double(f64::INFINITY), DZ),
+        (Op::fdiv_d, RNE, 0.0, 0.0, CANONICAL_NAN_D, NV),
+        (
+            Op::fsub_d,
+            RNE,
+            f64::INFINITY,
+            f64::INFINITY,
+            CANONICAL_NAN_D,
+            NV,
+        ),
+        (Op::fmul_d, RNE, max, 2.0, double(f64::INFINITY), OF | NX),
+        (Op::fmul_d, RTZ, max, 2.0, double(max), OF | NX),
+        (Op::fmul_d, RNE, 1e-200, 1e-200, 0, UF | NX),
+        (Op::fmul_d, RUP, 1e-200, 1e-200, 1, UF | NX),
+        (Op::fsqrt_d, RNE, -1.0, 0.0, CANONICAL_NAN_D, NV),
+        (Op::fsqrt_d, RNE, 2.0, 0.0, double(2f64.sqrt()), NX),
+        (Op::fsqrt_d, RNE, 4.0, 0.0, double(2.0), 0),
+        (Op::fmin_d, 0, -0.0, 0.0, double(-0.0), 0),
+        (Op::fmax_d, 0, -0.0, 0.0, double(0.0), 0),
+        (Op::fsgnjn_d, 0, 2.0, 1.0, double(-2.0), 0),
+    ] {
+        let outcome = run(op, rm, double(a), double(b));
+        assert_eq!(
+            (outcome.f, outcome.fflags),
+            (expected, fflags),
+            "{op} {a} {b}, rm {rm}"
+        );
+    }
+
+    // a signaling NaN operand is invalid, and NaN results are canonical.
+    let outcome = run(Op::fadd_d, RNE, SIGNALING_NAN_D, double(1.0));
+    assert_eq!((outcome.f, outcome.fflags), (CANONICAL_NAN_D, NV));
+    let outcome = run(Op::fmin_d, 0, CANONICAL_NAN_D, double(1.0));
+    assert_eq!((outcome.f, outcome.fflags), (double(1.0), 0));
+    let outcome = run(Op::flt_d, 0, CANONICAL_NAN_D, double(1.0));
+    assert_eq!((outcome.x, outcome.fflags), (0, NV));
+    let outcome = run(Op::fle_d, 0, double(-1.0), double(-1.0));
+    assert_eq!((outcome.x, outcome.fflags), (1, 0));
+}
+
+#[test]
+fn double_fused_multiply_add_rounds_once() {
+    // (1 + 2^-27)^2 - (1 + 2^-26) is exactly 2^-54, while rounding the product first
+    // loses it.
+    let a = double(1.0 + 2f64.powi(-27));
+    let c = double(-(1.0 + 2f64.powi(-26)));
+    let outcome = execute(Op::fmadd_d, 3 << 3 | RNE, 0, [a, a, c]).unwrap();
+    assert_eq!((outcome.f, outcome.fflags), (double(2f64.powi(-54)), 0));
+
+    // the other forms negate the product, the addend or both.
+    let minus_c = double(1.0 + 2f64.powi(-26));
+    let outcome = execute(Op::fmsub_d, 3 << 3 | RNE, 0, [a, a, minus_c]).unwrap();
+    assert_eq!(outcome.f, double(2f64.powi(-54)));
+    let outcome = execute(Op::fnmsub_d, 3 << 3 | RNE, 0, [a, a, minus_c]).unwrap();
+    assert_eq!(outcome.f, double(-(2f64.powi(-54))));
+    let outcome = execute(Op::fnmadd_d, 3 << 3 | RNE, 0, [a, a, c]).unwrap();
+    assert_eq!(outcome.f, double(-(2f64.powi(-54))));
+
+    // inf * 0 is invalid, even when the addend is a quiet NaN.
+    let outcome = execute(
+        Op::fmadd_d,
+        3 << 3 | RNE,
+        0,
+        [double(f64::INFINITY), double(0.0), CANONICAL_NAN_D],
+    )
+    .unwrap();
+    assert_eq!((outcome.f, outcome.fflags), (CANONICAL_NAN_D, NV));
+}
+
+#[test]
+fn conversions_between_precisions() {
+    // narrowing rounds, and may overflow or underflow.
+    for (rm, a, expected, fflags) in [
+        (RNE, 1.5, 1.5f32.to_bits(), 0),
+        (RNE, 1.0 / 3.0, 0x3EAA_AAAB, NX),
+        (RTZ, 1.0 / 3.0, 0x3EAA_AAAA, NX),
+        (RNE, 1e300, 0x7F80_0000, OF | NX),
+        (RTZ, 1e300, f32::MAX.to_bits(), OF | NX),
+        (RNE, -1e-50, 0x8000_0000, UF | NX),
+        (RNE, f64::NEG_INFINITY, 0xFF80_0000, 0),
+    ] {
+        let outcome = run(Op::fcvt_s_d, rm, double(a), 0);
+        assert_eq!(
+            (outcome.single(), outcome.fflags),
+            (expected, fflags),
+            "fcvt.s.d {a}, rm {rm}"
+        );
+    }
+    let outcome = run(Op::fcvt_s_d, RNE, 0x7FF8_0000_0000_1234, 0);
+    assert_eq!((outcome.single(), outcome.fflags), (CANONICAL_NAN, 0));
+    let outcome = run(Op::fcvt_s_d, RNE, SIGNALING_NAN_D, 0);
+    assert_eq!((outcome.single(), outcome.fflags), (CANONICAL_NAN, NV));
+
+    // widening is exact.
+    let third = 1.0f32 / 3.0;
+    let outcome = run(Op::fcvt_d_s, RNE, single(third), 0);
+    assert_eq!((outcome.f, outcome.fflags), (double(third as f64), 0));
+    let outcome = run(Op::fcvt_d_s, RNE, single(f32::from_bits(1)), 0);
+    assert_eq!((outcome.f, outcome.fflags), (double(2f64.powi(-149)), 0));
+    let outcome = run(Op::fcvt_d_s, RNE, boxed(SIGNALING_NAN), 0);
+    assert_eq!((outcome.f, outcome.fflags), (CANONICAL_NAN_D, NV));
+}
+
+#[test]
+fn double_integer_conversions_saturate() {
+    let nan = f64::from_bits(CANONICAL_NAN_D);
+    for (op, rm, a, expected, fflags) in [
+        (Op::fcvt_w_d, RTZ, 3e9, i32::MAX as i64, NV),
+        (Op::fcvt_w_d, RTZ, -3e9, i32::MIN as i64, NV),
+        (Op::fcvt_w_d, RTZ, f64::INFINITY, i32::MAX as i64, NV),
+        (Op::fcvt_w_d, RTZ, f64::NEG_INFINITY, i32::MIN as i64, NV),
+        (Op::fcvt_w_d, RTZ, nan, i32::MAX as i64, NV),
+        (Op::fcvt_w_d, RTZ, -nan, i32::MAX as i64, NV),
+        // in range once rounded, so only inexact.
+        (Op::fcvt_w_d, RTZ, 2147483647.5, i32::MAX as i64, NX),
+        (Op::fcvt_w_d, RTZ, -2147483648.5, i32::MIN as i64, NX),
+        (Op::fcvt_w_d, RNE, 2147483647.5, i32::MAX as i64, NV),
+        (Op::fcvt_w_d, RTZ, 2147483648.0, i32::MAX as i64, NV),
+        (Op::fcvt_w_d, RNE, 2.5, 2, NX),
+        (Op::fcvt_w_d, RMM, 2.5, 3, NX),
+        (Op::fcvt_w_d, RDN, -2.5, -3, NX),
+        (Op::fcvt_wu_d, RTZ, -1.0, 0, NV),
+        (Op::fcvt_wu_d, RTZ, -0.5, 0, NX),
+        (Op::fcvt_wu_d, RTZ, 4e9, 4_000_000_000u32 as i32 as i64, 0),
+        (Op::fcvt_wu_d, RTZ, 4294967295.0, -1, 0),
+        (Op::fcvt_wu_d, RTZ, 4294967296.0, -1, NV),
+        (Op::fcvt_wu_d, RTZ, nan, -1, NV),
+        (Op::fcvt_wu_d, RTZ, f64::NEG_INFINITY, 0, NV),
+    ] {
+        let outcome = run(op, rm, double(a), 0);
+        assert_eq!(
+            (outcome.x, outcome.fflags),
+            (expected, fflags),
+            "{op} {a}, rm {rm}"
+        );
+    }
+
+    // every 32-bit integer is exact as a double: x1 holds the low word of the first
+    // operand.
+    for (op, a, expected) in [
+        (Op::fcvt_d_w, -7i32 as u32, -7.0),
+        (Op::fcvt_d_w, i32::MIN as u32, -2147483648.0),
+        (Op::fcvt_d_wu, u32::MAX, 4294967295.0),
+        (Op::fcvt_d_wu, 0x8000_0000, 2147483648.0),
+    ] {
+        let outcome = run(op, RTZ, a as u64, 0);
+        assert_eq!(
+            (outcome.f, outcome.fflags),
+            (double(expected), 0),
+            "{op} {a:#X}"
+        );
+    }
+}
+
+#[test]
+fn double_classes() {
+    let subnormal = f64::from_bits(1);
+    for (a, class) in [
+        (double(f64::NEG_INFINITY), 0),
+        (double(-1.0), 1),
+        (double(-subnormal), 2),
+        (double(-0.0), 3),
+        (double(0.0), 4),
+        (double(subnormal), 5),
+        (double(f64::MAX), 6),
+        (double(f64::INFINITY), 7),
+        (SIGNALING_NAN_D, 8),
+        (CANONICAL_NAN_D, 9),
+    ] {
+        let outcome = run(Op::fclass_d, 0, a, 0);
+        assert_eq!(outcome.x, 1 << class, "{a:#X}");
+    }
+
+    // a boxed single is a NaN as a double, quiet as the box sets bit 51.
+    let outcome = run(Op::fclass_d, 0, single(1.0), 0);
+    assert_eq!(outcome.x, 1 << 9);
+}
+
+#[test]
+fn single_ops_read_doubles_as_nan() {
+    // a double written by a D op is not NaN-boxed, so single ops see the canonical NaN.
+    let source = format!(
+        "
+        li      t0, 3
+        {}
+        {}
+        {}
+        {}
+        {}
+1:      j       1b
+        ",
+        word(Op::fcvt_d_w, 1, 5, 0, RNE),
+        word(Op::fadd_s, 2, 1, 1, RNE),
+        word(Op::fmv_x_w, 10, 2, 0, 0),
+        word(Op::fcvt_w_s, 11, 1, 0, RTZ),
+        word(Op::fcvt_w_d, 12, 1, 0, RTZ),
+    );
+    let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+    machine.execute_until_loop().unwrap();
+    let registers = machine.registers();
+    assert_eq!(registers[10], CANONICAL_NAN as i64);
+    // NaN converts to the largest integer.
+    assert_eq!(registers[11], i32::MAX as i64);
+    assert_eq!(registers[12], 3);
+
+    // and a single op on them only boxes its own result.
+    let outcome = run(Op::fsgnj_s, 0, double(1.0), single(-1.0));
+    assert_eq!(outcome.f, boxed(0xFFC0_0000));
+    let outcome = run(Op::fmv_x_w, 0, double(-2.0), 0);
+    assert_eq!(outcome.x, 0);
+}
+
+#[test]
+fn mstatus_fs_turns_the_fpu_off() {
+    let run = |text: &str| {
+        let source = format!("{text}\n1: j 1b");
+        let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+        machine.execute_until_loop().map(|_| machine)
+    };
+    let fs = |mstatus: i64| mstatus >> 13 & 0b11;
+    let (read_f0, write_f1) = (
+        word(Op::fmv_x_w, 10, 0, 0, 0),
+        word(Op::fmv_w_x, 1, 0, 0, 0),
+    );
+
+    // the FPU starts Initial, and only writes to the f registers or fcsr make it Dirty.
+    let text =
+        format!("csrr s1, mstatus\n{read_f0}\ncsrr s2, mstatus\n{write_f1}\ncsrr s3, mstatus");
+    let registers = *run(&text).unwrap().registers();
+    assert_eq!(
+        [fs(registers[9]), fs(registers[18]), fs(registers[19])],
+        [1, 1, 3]
+    );
+    // SD, bit 31, is set with Dirty.
+    assert!(registers[18] > 0 && registers[19] < 0);
+    let registers = *run("csrwi frm, 1\ncsrr s1, mstatus").unwrap().registers();
+    assert_eq!(fs(registers[9]), 3);
+
+    // with FS Off, F instructions and fcsr are illegal.
+    let off = "li t0, 0x6000\ncsrc mstatus, t0";
+    let fsw = word(Op::fsw, 0, 2, 1, -4);
+    for instruction in [&read_f0, &write_f1, &fsw, "csrr a0, fflags", "csrwi frm, 0"] {
+        assert!(
+            matches!(
+                run(&format!("{off}\n{instruction}")),
+                Err(MachineError::FloatingPointOff(_))
+            ),
+            "{instruction}"
+        );
+    }
+    run(&format!(
+        "{off}\nli t0, 0x2000\ncsrs mstatus, t0\n{write_f1}"
+    ))
+    .unwrap();
+}
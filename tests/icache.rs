@@ -0,0 +1,87 @@
+//! Runs code that changes instructions the machine has already decoded and cached, by
+//! its own stores or by writes from outside followed by fence.i, and checks that the new
+//! instructions are the ones executed.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    assembler,
+    instruction::{Instruction, definitions::Op},
+    isa::Xlen,
+    loader::Image,
+    machine::{Machine, Permissions},
+};
+
+/* li a0, value as a single addi, to store over another instruction. */
+fn li_a0(value: i32) -> u32 {
+    Instruction::encode(
+        Op::addi,
+        u5::new(10),
+        u5::new(0),
+        u5::new(0),
+        value,
+        Xlen::Rv32,
+    )
+    .unwrap()
+}
+
+/* A machine running source with writable text, as the code changes itself. */
+fn machine(source: &str) -> Machine {
+    let mut image = Image::from(&assembler::assemble(source).unwrap());
+    for segment in &mut image.segments {
+        segment.permissions = Permissions::ALL;
+    }
+
+    Machine::from_image(&image).unwrap()
+}
+
+#[test]
+fn stores_replace_cached_instructions() {
+    // target runs once as li a0, 1, then again after being overwritten with li a0, 2.
+    let source = format!(
+        "
+        li      s1, 0
+again:
+target: li      a0, 1
+        bnez    s1, 1f
+        li      s1, 1
+        la      t0, target
+        li      t1, {}
+        sw      t1, 0(t0)
+        j       again
+1:      j       1b
+        ",
+        li_a0(2)
+    );
+    let mut machine = machine(&source);
+    machine.execute_until_loop().unwrap();
+
+    assert_eq!(machine.registers()[10], 2);
+}
+
+#[test]
+fn fence_i_drops_cached_instructions() {
+    // target is cached by the first pass, then overwritten behind the hart's back.
+    for (fence, expected) in [("nop", 1), ("fence.i", 2)] {
+        let source = format!(
+            "
+            li      s1, 2
+target:     li      a0, 1
+            addi    s1, s1, -1
+            {fence}
+            bnez    s1, target
+1:          j       1b
+            "
+        );
+        let program = assembler::assemble(&source).unwrap();
+        let mut machine = Machine::from_program(&program).unwrap();
+        machine.execute_next_instruction().unwrap();
+        machine.execute_next_instruction().unwrap();
+        let target = program.symbol("target").unwrap();
+        machine
+            .write_memory(target, &li_a0(2).to_le_bytes())
+            .unwrap();
+        machine.execute_until_loop().unwrap();
+
+        assert_eq!(machine.registers()[10], expected, "{fence}");
+    }
+}
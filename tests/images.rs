@@ -0,0 +1,138 @@
+//! Runs the self-checking and signature test images in tests/fixtures, which are modelled
+//! on riscv-tests and riscv-arch-test but are not those suites.
+//!
+//! Self-checking images exit through HTIF: code 0 is a pass, and any other code is the
+//! number of the first failing test. Signature images also halt through HTIF, after
+//! leaving their results between `begin_signature` and `end_signature`, which are
+//! compared with the reference signature of the test. Both are linked at 0x80000000, as
+//! riscv-tests links its images, where the machine gets its memory.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use periscvcope::{
+    file_parser::ElfFile,
+    loader::{Elf, Entry, Loader},
+    machine::{Machine, MemoryLayout},
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+const MAX_STEPS: usize = 100_000;
+const MEMORY: MemoryLayout = MemoryLayout {
+    base: 0x8000_0000,
+    size: 4 * 1024 * 1024,
+};
+
+fn fixtures(dir: &str, keep: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let mut paths: Vec<_> = fs::read_dir(Path::new(FIXTURES).join(dir))
+        .expect("fixture directory should exist")
+        .map(|entry| entry.expect("fixture directory should be readable").path())
+        .filter(|path| path.is_file())
+        .filter(|path| keep(&path.file_name().unwrap().to_string_lossy()))
+        .collect();
+    paths.sort();
+
+    paths
+}
+
+fn symbol(file: &ElfFile, name: &str) -> u64 {
+    file.symbol(name)
+        .expect("symbol table should parse")
+        .unwrap_or_else(|| panic!("test image has no {name} symbol"))
+}
+
+/* Runs a test image until it exits through HTIF, and returns the machine along with
+ * its exit code. */
+fn run_until_exit(data: &[u8]) -> Result<(Machine, u64), String> {
+    let image = Elf.load(data).map_err(|err| err.to_string())?;
+    let mut machine = Machine::from_images_in(&[image], &Entry::Image(0), MEMORY)
+        .map_err(|err| err.to_string())?;
+
+    for _ in 0..MAX_STEPS {
+        machine
+            .execute_next_instruction()
+            .map_err(|err| err.to_string())?;
+
+        if let Some(code) = machine.exit_code() {
+            return Ok((machine, code));
+        }
+    }
+
+    Err(format!(
+        "no exit through tohost after {MAX_STEPS} instructions"
+    ))
+}
+
+#[test]
+fn self_checking() {
+    let tests = fixtures("self-checking", |name| name.ends_with(".elf"));
+    assert!(!tests.is_empty(), "no self-checking images in the fixtures");
+
+    let failures: Vec<_> = tests
+        .iter()
+        .filter_map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy();
+            let data = fs::read(path).expect("fixture should be readable");
+            match run_until_exit(&data) {
+                Ok((_, 0)) => None,
+                Ok((_, code)) => Some(format!("{name}: failed test {code}")),
+                Err(err) => Some(format!("{name}: {err}")),
+            }
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/* The signature region as riscv-arch-test dumps it: one 32-bit word per line, in
+ * lowercase hex. */
+fn signature(machine: &Machine, file: &ElfFile) -> Result<String, String> {
+    let begin = symbol(file, "begin_signature");
+    let end = symbol(file, "end_signature");
+    let bytes = machine
+        .read_memory(begin, (end - begin) as usize)
+        .map_err(|err| err.to_string())?;
+
+    Ok(bytes
+        .chunks(4)
+        .map(|word| format!("{:08x}\n", u32::from_le_bytes(word.try_into().unwrap())))
+        .collect())
+}
+
+#[test]
+fn signatures() {
+    let tests = fixtures("signature", |name| name.ends_with(".elf"));
+    assert!(!tests.is_empty(), "no signature images in the fixtures");
+
+    let failures: Vec<_> = tests
+        .iter()
+        .filter_map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy();
+            let data = fs::read(path).expect("fixture should be readable");
+            let reference = fs::read_to_string(path.with_extension("reference_output"))
+                .expect("every test should have a reference signature");
+            let file = ElfFile::from_buffer(&data).expect("fixture should be an ELF file");
+
+            let signature =
+                match run_until_exit(&data).and_then(|(machine, _)| signature(&machine, &file)) {
+                    Ok(signature) => signature,
+                    Err(err) => return Some(format!("{name}: {err}")),
+                };
+
+            // keep the dump around to diff against the reference.
+            let dump = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("{name}.signature"));
+            fs::write(&dump, &signature).expect("signature dump should be writable");
+
+            (signature != reference).then(|| {
+                format!(
+                    "{name}: signature differs from the reference, see {}",
+                    dump.display()
+                )
+            })
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
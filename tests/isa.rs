@@ -0,0 +1,143 @@
+//! Parses ISA strings as compilers write them, e.g. in Tag_RISCV_arch, and checks the
+//! base width and the extensions they enable.
+
+use periscvcope::{
+    instruction::definitions::Extension::{self, *},
+    isa::{Error, Isa, Xlen},
+};
+
+fn parse(s: &str) -> Isa {
+    s.parse().unwrap_or_else(|err| panic!("{s}: {err}"))
+}
+
+fn extensions(s: &str) -> Vec<Extension> {
+    parse(s).extensions().collect()
+}
+
+#[test]
+fn single_letters() {
+    assert_eq!(extensions("rv32i"), [I]);
+    // M is Zmmul with division, and A is Zaamo and Zalrsc.
+    assert_eq!(extensions("rv32im"), [I, M, Zmmul]);
+    assert_eq!(extensions("rv32ima"), [I, M, A, Zmmul, Zaamo, Zalrsc]);
+    // F and D need Zicsr.
+    assert_eq!(extensions("rv32if"), [I, F, Zicsr]);
+    assert_eq!(extensions("rv32ifd"), [I, F, D, Zicsr]);
+    assert_eq!(extensions("RV32IM"), [I, M, Zmmul]);
+
+    let isa = parse("rv64ima");
+    assert_eq!(isa.xlen(), Xlen::Rv64);
+    let isa = parse("rv32em");
+    assert!(isa.is_embedded());
+    assert_eq!(isa.register_count(), 16);
+}
+
+#[test]
+fn general() {
+    let general = [I, M, A, F, D, Zicsr, Zifencei, Zmmul, Zaamo, Zalrsc];
+    assert_eq!(extensions("rv32g"), general);
+    assert_eq!(extensions("rv64g"), general);
+    assert_eq!(parse("rv64g").xlen(), Xlen::Rv64);
+    assert_eq!(
+        extensions("rv32g_zba"),
+        [general.as_slice(), &[Zba]].concat()
+    );
+}
+
+#[test]
+fn multi_letter_extensions() {
+    assert_eq!(extensions("rv32i_zicsr_zifencei"), [I, Zicsr, Zifencei]);
+    assert_eq!(
+        extensions("rv32im_zba_zbb_zbs"),
+        [I, M, Zmmul, Zba, Zbb, Zbs]
+    );
+    // without an underscore after the single letters.
+    assert_eq!(extensions("rv32imzicsr"), [I, M, Zicsr, Zmmul]);
+    // hints decode as base instructions.
+    assert_eq!(extensions("rv32i_zihintpause"), [I]);
+    // subsets enable only themselves.
+    assert_eq!(extensions("rv32i_zmmul"), [I, Zmmul]);
+    assert_eq!(extensions("rv32i_zaamo"), [I, Zaamo]);
+    assert_eq!(extensions("rv32i_zalrsc"), [I, Zalrsc]);
+    assert_eq!(extensions("rv32i_zaamo_zalrsc"), [I, Zaamo, Zalrsc]);
+}
+
+#[test]
+fn versions() {
+    assert_eq!(
+        extensions("rv32i2p1_m2p0_a2p1_zicsr2p0_zifencei2p0_zmmul1p0"),
+        [I, M, A, Zicsr, Zifencei, Zmmul, Zaamo, Zalrsc]
+    );
+    assert_eq!(extensions("rv32i2p1_m2p0_zba1p0"), [I, M, Zmmul, Zba]);
+    assert_eq!(extensions("rv32i2p1m2p0"), [I, M, Zmmul]);
+    assert_eq!(extensions("rv32i2p1_m2"), [I, M, Zmmul]);
+    assert_eq!(extensions("rv32i2p1"), [I]);
+
+    // version 2.0 of the base still included Zicsr and Zifencei.
+    assert_eq!(extensions("rv32i2p0_m2p0"), [I, M, Zicsr, Zifencei, Zmmul]);
+    assert_eq!(extensions("rv32i2p0"), [I, Zicsr, Zifencei]);
+    assert_eq!(extensions("rv64i2_m"), [I, M, Zicsr, Zifencei, Zmmul]);
+}
+
+#[test]
+fn compressed_is_unsupported() {
+    for s in [
+        "rv32imac_zicsr",
+        "rv32gc",
+        "rv64gc",
+        "rv32ic",
+        "rv32i2p1_m2p0_c2p0",
+    ] {
+        assert!(
+            matches!(s.parse::<Isa>(), Err(Error::CompressedUnsupported(_))),
+            "{s}"
+        );
+    }
+
+    // files built for C are run without it.
+    let (isa, compressed) = Isa::parse_without_compressed("rv32i2p1_m2p0_c2p0").unwrap();
+    assert_eq!(isa.extensions().collect::<Vec<_>>(), [I, M, Zmmul]);
+    assert!(compressed);
+    let (isa, compressed) = Isa::parse_without_compressed("rv32gc").unwrap();
+    assert_eq!(isa, parse("rv32g"));
+    assert!(compressed);
+    let (isa, compressed) = Isa::parse_without_compressed("rv32im").unwrap();
+    assert_eq!(isa, parse("rv32im"));
+    assert!(!compressed);
+    assert!(Isa::parse_without_compressed("rv32icv").is_err());
+}
+
+#[test]
+fn unknown_extensions() {
+    for (s, name) in [
+        ("rv32ip", "p"),
+        ("rv32imp", "p"),
+        ("rv32iq", "q"),
+        ("rv32iv", "v"),
+        ("rv32i_p", "p"),
+        ("rv32i_zfh", "zfh"),
+        ("rv32i_xfoo", "xfoo"),
+    ] {
+        match s.parse::<Isa>() {
+            Err(Error::UnsupportedExtension(unknown)) => assert_eq!(unknown, name, "{s}"),
+            result => panic!("{s}: {result:?}"),
+        }
+    }
+
+    for s in ["riscv32i", "rv128i", "rv32", "rv32m", "rv32i+m"] {
+        assert!(s.parse::<Isa>().is_err(), "{s}");
+    }
+}
+
+#[test]
+fn display() {
+    for s in [
+        "rv32i",
+        "rv32imafd_zicsr_zmmul_zaamo_zalrsc",
+        "rv64im_zmmul_zba_zbb_zbs",
+        "rv32em_zmmul",
+        "rv32i_zaamo",
+    ] {
+        assert_eq!(parse(s).to_string(), s);
+    }
+}
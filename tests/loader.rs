@@ -0,0 +1,520 @@
+//! Loads the program of tests/fixtures/assembler as Intel HEX, S-records and a raw
+//! binary, all converted from its ELF file by objcopy, and checks that each gives the
+//! same memory and runs the same way. Inline records cover what that program does not,
+//! programs assembled at different addresses cover loading several images at once, and
+//! a program counting in .bss covers zeroing it again when the machine is reset, and
+//! running from memory at 0x80000000.
+
+use std::fs;
+
+use periscvcope::{
+    assembler::{self, Assembler},
+    file_parser::{self, ElfFile},
+    isa::Xlen,
+    loader::{Binary, Elf, Entry, Error, Format, Image, IntelHex, Loader, SRecord, Segment},
+    machine::{Machine, MachineError, MemoryLayout, Permissions},
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/assembler");
+const MAX_STEPS: usize = 10_000;
+
+fn fixture(extension: &str) -> Vec<u8> {
+    fs::read(format!("{FIXTURES}/program.{extension}")).unwrap()
+}
+
+/* The loaded bytes of an image below 16 KiB, with zeros where nothing is loaded. */
+fn flatten(image: &Image) -> Vec<u8> {
+    let mut memory = vec![0; 0x4000];
+    for segment in &image.segments {
+        let start = segment.addr as usize;
+        memory[start..start + segment.data.len()].copy_from_slice(&segment.data);
+    }
+
+    memory
+}
+
+#[test]
+fn formats_match_elf() {
+    let elf = fixture("elf");
+    let expected = Elf.load(&elf).unwrap();
+    let tohost = ElfFile::from_buffer(&elf)
+        .unwrap()
+        .symbol("tohost")
+        .unwrap()
+        .unwrap();
+
+    for (extension, format) in [
+        ("hex", Format::IntelHex),
+        ("srec", Format::SRecord),
+        ("bin", Format::Binary),
+    ] {
+        let data = fixture(extension);
+        assert_eq!(
+            Format::detect(&data),
+            format,
+            "format of program.{extension}"
+        );
+
+        let mut image = format.loader(0x1000, 0x1000).load(&data).unwrap();
+        assert_eq!(flatten(&image), flatten(&expected), "program.{extension}");
+        assert_eq!(image.entry_point(), 0x1000, "entry of program.{extension}");
+
+        // only the ELF file has symbols.
+        image.symbols.insert("tohost".to_string(), tohost);
+        let mut machine = Machine::from_image(&image).unwrap();
+        for _ in 0..MAX_STEPS {
+            machine.execute_next_instruction().unwrap();
+            if machine.exit_code().is_some() {
+                break;
+            }
+        }
+        assert_eq!(
+            machine.exit_code(),
+            Some(108),
+            "exit of program.{extension}"
+        );
+    }
+
+    assert_eq!(Format::detect(&elf), Format::Elf);
+}
+
+#[test]
+fn records_merge_into_segments() {
+    let ranges = |image: Image| -> Vec<_> {
+        image
+            .segments
+            .iter()
+            .map(|segment| segment.addr..segment.end().unwrap())
+            .collect()
+    };
+    let expected = ranges(Elf.load(&fixture("elf")).unwrap());
+
+    assert_eq!(ranges(IntelHex.load(&fixture("hex")).unwrap()), expected);
+    assert_eq!(ranges(SRecord.load(&fixture("srec")).unwrap()), expected);
+}
+
+#[test]
+fn intel_hex_addresses() {
+    let image = IntelHex
+        .load(
+            b"
+            :020000040001F9
+            :04000000DEADBEEFC4
+            :020000021000EC
+            :020010000102EB
+            :0400000500010004F2
+            :00000001FF
+            ",
+        )
+        .unwrap();
+
+    assert_eq!(image.segments.len(), 2);
+    assert_eq!(image.segments[0].addr, 0x10000);
+    assert_eq!(image.segments[0].data, [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(image.segments[1].addr, 0x10010);
+    assert_eq!(image.segments[1].data, [1, 2]);
+    assert_eq!(image.entry, Some(0x10004));
+}
+
+#[test]
+fn s_record_addresses() {
+    let image = SRecord
+        .load(b"S0050000686929\nS1051234AABB4F\nS205123456CC92\nS5030002FA\nS9031234B6\n")
+        .unwrap();
+
+    assert_eq!(image.segments.len(), 2);
+    assert_eq!(image.segments[0].addr, 0x1234);
+    assert_eq!(image.segments[0].data, [0xAA, 0xBB]);
+    assert_eq!(image.segments[1].addr, 0x123456);
+    assert_eq!(image.segments[1].data, [0xCC]);
+    assert_eq!(image.entry, Some(0x1234));
+
+    let image = SRecord.load(b"S8041234565F").unwrap();
+    assert!(image.segments.is_empty());
+    assert_eq!(image.entry, Some(0x123456));
+}
+
+#[test]
+fn raw_binary() {
+    let image = Binary {
+        base: 0x3000,
+        entry: 0x3004,
+    }
+    .load(&[0x13, 0, 0, 0])
+    .unwrap();
+
+    assert_eq!(image.segments.len(), 1);
+    assert_eq!(image.segments[0].addr, 0x3000);
+    assert_eq!(image.segments[0].size, 4);
+    assert_eq!(image.entry_point(), 0x3004);
+    assert_eq!(Format::detect(&[0x13, 0, 0, 0]), Format::Binary);
+}
+
+#[test]
+fn invalid_files() {
+    let hex = |text: &str| IntelHex.load(text.as_bytes()).unwrap_err();
+    let srec = |text: &str| SRecord.load(text.as_bytes()).unwrap_err();
+
+    assert!(matches!(
+        hex(":04000000DEADBEEFC5\n:00000001FF"),
+        Error::ChecksumMismatch(1, 0xC5, 0xC4)
+    ));
+    assert!(matches!(
+        hex(":04000000DEADBEEFC4"),
+        Error::MissingEndOfFile()
+    ));
+    assert!(matches!(hex(":00000006FA"), Error::UnknownRecordType(1, kind) if kind == "06"));
+    assert!(matches!(
+        hex("\n:0400000DEADBEEFC4"),
+        Error::InvalidRecord(2, _)
+    ));
+    assert!(matches!(
+        hex("04000000DEADBEEFC4"),
+        Error::InvalidRecord(1, _)
+    ));
+    assert!(matches!(
+        hex(":04000000DEADBEEFC4\n:0100020009F4\n:00000001FF"),
+        Error::Overlap(2)
+    ));
+
+    assert!(matches!(
+        srec("S1051234AABB4E\nS9031234B6"),
+        Error::ChecksumMismatch(1, 0x4E, 0x4F)
+    ));
+    assert!(matches!(srec("S1051234AABB4F"), Error::MissingEndOfFile()));
+    assert!(matches!(srec("S4030000FC"), Error::UnknownRecordType(1, kind) if kind == "S4"));
+    // the count says three data records, where there is one.
+    assert!(matches!(
+        srec("S1051234AABB4F\nS5030003F9\nS9031234B6"),
+        Error::InvalidRecord(2, _)
+    ));
+}
+
+#[test]
+fn images_must_fit_in_memory() {
+    let image = Binary {
+        base: 0x3F_FFFE,
+        entry: 0,
+    }
+    .load(&[0; 4])
+    .unwrap();
+
+    assert!(matches!(
+        Machine::from_image(&image),
+        Err(MachineError::LoadError(Error::OutOfMemory(
+            0x3F_FFFE, 0x40_0002
+        )))
+    ));
+}
+
+#[test]
+fn segments_must_end_below_2_to_the_64() {
+    let segment = Segment {
+        addr: u64::MAX - 8,
+        data: vec![],
+        size: 0x100,
+        permissions: Permissions::ALL,
+    };
+    assert!(matches!(
+        segment.end(),
+        Err(Error::AddressOverflow(0xFFFF_FFFF_FFFF_FFF7, 0x100))
+    ));
+
+    let image = Image {
+        segments: vec![segment],
+        ..Image::default()
+    };
+    assert!(matches!(
+        Image::combine(std::slice::from_ref(&image), &Entry::Image(0)),
+        Err(Error::AddressOverflow(_, 0x100))
+    ));
+    assert!(matches!(
+        Machine::from_image(&image),
+        Err(MachineError::LoadError(Error::AddressOverflow(_, 0x100)))
+    ));
+}
+
+#[test]
+fn sections_must_fit_in_memory() {
+    let section = |addr, size| elf::section::SectionHeader {
+        sh_name: 0,
+        sh_type: elf::abi::SHT_PROGBITS,
+        sh_flags: 0,
+        sh_addr: addr,
+        sh_offset: 0,
+        sh_size: size,
+        sh_link: 0,
+        sh_info: 0,
+        sh_addralign: 4,
+        sh_entsize: 0,
+    };
+    let memory = [0x13, 0, 0, 0].repeat(4);
+
+    let instructions = ElfFile::load_section(section(8, 8), &memory, Xlen::Rv32).unwrap();
+    assert_eq!(instructions.keys().copied().max(), Some(12));
+    assert_eq!(instructions.len(), 2);
+
+    for (addr, size) in [(8, 12), (u64::MAX - 2, 4)] {
+        assert!(
+            matches!(
+                ElfFile::load_section(section(addr, size), &memory, Xlen::Rv32),
+                Err(file_parser::Error::SectionOutOfRange(a, s, 16)) if (a, s) == (addr, size)
+            ),
+            "{addr:#X}"
+        );
+    }
+}
+
+/* An image of source assembled with its text at base. */
+fn assemble(base: u64, source: &str) -> Image {
+    let mut assembler = Assembler::default();
+    assembler.set_text_base(base);
+
+    (&assembler.assemble(source).unwrap()).into()
+}
+
+/* A bootloader at 0x1000 jumping to a kernel at 0x8000, which exits through tohost
+ * with the word of a data blob at 0x20000. */
+fn boot_images() -> Vec<Image> {
+    let boot = assemble(0x1000, "li t0, 0x8000\njr t0");
+    let kernel = assemble(
+        0x8000,
+        "
+        .globl _start
+        _start:
+            li t0, 0x20000
+            lw a0, 0(t0)
+            slli a0, a0, 1
+            ori a0, a0, 1
+            la t1, tohost
+            sw a0, 0(t1)
+        1:  j 1b
+        .data
+        .globl tohost
+        tohost: .word 0, 0
+        fromhost: .word 0, 0
+        ",
+    );
+    let blob = Binary {
+        base: 0x20000,
+        entry: 0x20000,
+    }
+    .load(&42u32.to_le_bytes())
+    .unwrap();
+
+    vec![boot, kernel, blob]
+}
+
+fn exit_code(images: &[Image], entry: &Entry) -> Option<u64> {
+    let mut machine = Machine::from_images(images, entry).unwrap();
+    for _ in 0..MAX_STEPS {
+        machine.execute_next_instruction().unwrap();
+        if machine.exit_code().is_some() {
+            break;
+        }
+    }
+
+    machine.exit_code()
+}
+
+#[test]
+fn several_images() {
+    let images = boot_images();
+
+    assert_eq!(exit_code(&images, &Entry::Image(0)), Some(42));
+    assert_eq!(exit_code(&images, &Entry::Image(1)), Some(42));
+    assert_eq!(exit_code(&images, &Entry::Address(0x1000)), Some(42));
+    assert_eq!(
+        exit_code(&images, &Entry::Symbol("_start".into())),
+        Some(42)
+    );
+
+    let image = Image::combine(&images, &Entry::Image(0)).unwrap();
+    assert_eq!(image.entry, Some(0x1000));
+    assert_eq!(image.symbols.get("_start"), Some(&0x8000));
+    let addresses: Vec<_> = image.segments.iter().map(|s| s.addr).collect();
+    assert_eq!(addresses, [0x1000, 0x8000, 0x9000, 0x20000]);
+}
+
+#[test]
+fn overlapping_images() {
+    let mut images = boot_images();
+    images.push(
+        Binary {
+            base: 0x8010,
+            entry: 0,
+        }
+        .load(&[0; 4])
+        .unwrap(),
+    );
+
+    assert!(matches!(
+        Machine::from_images(&images, &Entry::Image(0)),
+        Err(MachineError::LoadError(Error::OverlappingImages(
+            1, 3, 0x8010
+        )))
+    ));
+    // touching is not overlapping.
+    images[3].segments[0].addr = 0x20004;
+    assert!(Machine::from_images(&images, &Entry::Image(0)).is_ok());
+
+    let images = boot_images();
+    assert!(matches!(
+        Image::combine(&images, &Entry::Image(3)),
+        Err(Error::NoSuchImage(3))
+    ));
+    assert!(matches!(
+        Image::combine(&images, &Entry::Symbol("main".into())),
+        Err(Error::UndefinedSymbol(name)) if name == "main"
+    ));
+}
+
+/* Counts in .bss and exits through tohost with the count plus a word of .data, which it
+ * then overwrites: run twice without a fresh .bss and .data, it exits differently. */
+const COUNTER: &str = "
+        la      t0, count
+        lw      t1, 0(t0)
+        addi    t1, t1, 1
+        sw      t1, 0(t0)
+        la      t2, value
+        lw      a0, 0(t2)
+        sw      zero, 0(t2)
+        add     a0, a0, t1
+        slli    a0, a0, 1
+        ori     a0, a0, 1
+        la      t0, tohost
+        sw      a0, 0(t0)
+1:      j       1b
+
+        .data
+        .globl  tohost
+tohost: .word   0, 0
+value:  .word   41
+
+        .bss
+count:  .zero   64
+";
+
+#[test]
+fn elf_bss_is_zeroed() {
+    let program = assembler::assemble(COUNTER).unwrap();
+    let elf = program.to_elf();
+    let file = ElfFile::from_buffer(&elf).unwrap();
+
+    // memory that still holds an earlier program.
+    let mut memory = vec![0xAA; 0x4000];
+    file.load_into(&mut memory).unwrap();
+    for section in [&program.text, &program.data] {
+        assert_eq!(
+            memory[section.base as usize..][..section.bytes.len()],
+            section.bytes
+        );
+    }
+    let bss = program.bss.start as usize..program.bss.end as usize;
+    assert!(memory[bss.clone()].iter().all(|&byte| byte == 0));
+    assert_eq!(memory[bss.end], 0xAA);
+
+    assert!(matches!(
+        file.load_memory(0x2000),
+        Err(file_parser::Error::SegmentOutOfRange(0x2000, end, 0x2000)) if end == program.bss.end
+    ));
+
+    // p_filesz of the data segment, the second program header, past its p_memsz.
+    let mut elf = elf.clone();
+    elf[84 + 16..84 + 20].copy_from_slice(&0x1000u32.to_le_bytes());
+    let file = ElfFile::from_buffer(&elf).unwrap();
+    assert!(matches!(
+        file.load_memory(0x4000),
+        Err(file_parser::Error::SegmentSizeMismatch(0x2000, 0x1000, _))
+    ));
+    assert!(matches!(Elf.load(&elf), Err(Error::Elf(_))));
+}
+
+#[test]
+fn reset() {
+    let program = assembler::assemble(COUNTER).unwrap();
+    let mut machine = Machine::new(&program.to_elf()).unwrap();
+    let initial = machine.registers().to_owned();
+
+    for _ in 0..2 {
+        machine.execute_until_loop().unwrap();
+        assert_eq!(machine.exit_code(), Some(42));
+        assert_ne!(machine.registers(), &initial);
+
+        machine.reset();
+        assert_eq!(machine.exit_code(), None);
+        assert_eq!(machine.registers(), &initial);
+        let bss = program.bss.start;
+        let size = (program.bss.end - bss) as usize;
+        assert!(
+            machine
+                .read_memory(bss, size)
+                .unwrap()
+                .iter()
+                .all(|&b| b == 0)
+        );
+    }
+
+    // what a program left on the stack is gone too.
+    machine.set_arguments(&["prog"], &[]).unwrap();
+    let sp = machine.registers()[2] as u64;
+    machine.reset();
+    assert_eq!(machine.read_memory(sp, 8).unwrap(), [0; 8]);
+}
+
+#[test]
+fn memory_layout() {
+    let mut assembler = Assembler::default();
+    assembler.set_text_base(0x8000_0000);
+    assembler.set_data_base(0x8000_1000);
+    let image = Elf
+        .load(&assembler.assemble(COUNTER).unwrap().to_elf())
+        .unwrap();
+    let images = [image];
+    let layout = MemoryLayout {
+        base: 0x8000_0000,
+        size: 0x10000,
+    };
+
+    // the default memory ends at 4 MiB.
+    assert!(matches!(
+        Machine::from_images(&images, &Entry::Image(0)),
+        Err(MachineError::LoadError(Error::OutOfMemory(0x8000_0000, _)))
+    ));
+
+    let mut machine = Machine::from_images_in(&images, &Entry::Image(0), layout).unwrap();
+    // sp starts at the end of memory, sign-extended as every RV32 register.
+    assert_eq!(machine.registers()[2], 0x8001_0000u32 as i32 as i64);
+    machine.execute_until_loop().unwrap();
+    assert_eq!(machine.exit_code(), Some(42));
+    assert!(matches!(
+        machine.read_memory(0x7FFF_FFFF, 1),
+        Err(MachineError::MemoryError(0x7FFF_FFFF))
+    ));
+    assert!(matches!(
+        machine.read_memory(0x8000_FFFF, 2),
+        Err(MachineError::MemoryError(0x8001_0000))
+    ));
+    machine.reset();
+    machine.set_arguments(&["prog"], &[]).unwrap();
+    assert!(machine.registers()[2] as u64 >= 0xFFFF_FFFF_8000_0000);
+
+    // segments must not start below memory either.
+    let layout = MemoryLayout {
+        base: 0x8000_1000,
+        size: 0x10000,
+    };
+    assert!(matches!(
+        Machine::from_images_in(&images, &Entry::Image(0), layout),
+        Err(MachineError::LoadError(Error::OutOfMemory(0x8000_0000, _)))
+    ));
+
+    let layout = MemoryLayout {
+        base: u64::MAX - 0xFFF,
+        size: 0x2000,
+    };
+    assert!(matches!(
+        Machine::from_images_in(&images, &Entry::Image(0), layout),
+        Err(MachineError::InvalidMemoryLayout(_, 0x2000))
+    ));
+}
@@ -0,0 +1,162 @@
+//! Runs loads and stores at addresses that are not a multiple of their size under each
+//! misaligned access policy: Emulate and Warn complete them, and Trap raises an address
+//! misaligned exception without touching memory.
+
+use periscvcope::{
+    assembler::{self, Program},
+    machine::{Machine, MachineError, MisalignedAccess},
+};
+
+const POLICIES: [MisalignedAccess; 3] = [
+    MisalignedAccess::Emulate,
+    MisalignedAccess::Warn,
+    MisalignedAccess::Trap,
+];
+
+/* Runs text under policy, with s0 pointing at the bytes 0x00 to 0x0F and s1 at the last
+ * two bytes of a page, 0xAA and 0xBB, before 0xCC and 0xDD on the next one. */
+fn run(policy: MisalignedAccess, text: &str) -> (Program, Machine, Result<(), MachineError>) {
+    let source = format!(
+        "
+        la      s0, bytes
+        la      s1, boundary
+        {text}
+1:      j       1b
+
+        .data
+bytes:  .byte   0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07
+        .byte   0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F
+        .balign 4096
+        .zero   4094
+boundary:
+        .byte   0xAA, 0xBB, 0xCC, 0xDD
+        "
+    );
+    let program = assembler::assemble(&source).unwrap();
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.set_misaligned_access(policy);
+    let result = machine.execute_until_loop().map(|_| ());
+
+    (program, machine, result)
+}
+
+#[test]
+fn policies_are_parsed() {
+    for (policy, name) in POLICIES.iter().zip(["emulate", "warn", "trap"]) {
+        assert_eq!(name.parse::<MisalignedAccess>().unwrap(), *policy);
+    }
+    assert!("ignore".parse::<MisalignedAccess>().is_err());
+    assert_eq!(MisalignedAccess::default(), MisalignedAccess::Emulate);
+}
+
+#[test]
+fn loads() {
+    for (text, expected) in [
+        ("lw a0, 1(s0)", 0x0403_0201),
+        ("lw a0, 3(s0)", 0x0605_0403),
+        ("lh a0, 1(s0)", 0x0201),
+        ("lhu a0, 7(s0)", 0x0807),
+        // crossing into the next page.
+        ("lw a0, 0(s1)", 0xDDCC_BBAAu32 as i32 as i64),
+        ("lh a0, 1(s1)", 0xFFFF_FFFF_FFFF_CCBBu64 as i64),
+        ("lhu a0, 1(s1)", 0xCCBB),
+    ] {
+        for policy in [MisalignedAccess::Emulate, MisalignedAccess::Warn] {
+            let (_, machine, result) = run(policy, text);
+            result.unwrap_or_else(|err| panic!("{policy:?} {text}: {err}"));
+            assert_eq!(machine.registers()[10], expected, "{policy:?} {text}");
+        }
+
+        let (program, _, result) = run(MisalignedAccess::Trap, text);
+        let (base, offset) = offset(&program, text);
+        match result {
+            Err(MachineError::LoadAddressMisaligned(addr)) => {
+                assert_eq!(addr, base + offset, "{text}")
+            }
+            result => panic!("{text}: {result:?}"),
+        }
+    }
+}
+
+#[test]
+fn stores() {
+    for (text, bytes) in [
+        ("sw a0, 1(s0)", [0x00, 0x44, 0x33, 0x22, 0x11, 0x05]),
+        ("sh a0, 3(s0)", [0x00, 0x01, 0x02, 0x44, 0x33, 0x05]),
+    ] {
+        let text = format!("li a0, 0x11223344\n{text}");
+        for policy in [MisalignedAccess::Emulate, MisalignedAccess::Warn] {
+            let (program, machine, result) = run(policy, &text);
+            result.unwrap();
+            let addr = program.symbol("bytes").unwrap();
+            assert_eq!(machine.read_memory(addr, 6).unwrap(), bytes, "{text}");
+        }
+
+        // a trapping store leaves memory as it was.
+        let (program, machine, result) = run(MisalignedAccess::Trap, &text);
+        let addr = program.symbol("bytes").unwrap();
+        assert!(
+            matches!(result, Err(MachineError::StoreAddressMisaligned(a)) if a > addr),
+            "{text}: {result:?}"
+        );
+        assert_eq!(
+            machine.read_memory(addr, 6).unwrap(),
+            [0x00, 0x01, 0x02, 0x03, 0x04, 0x05]
+        );
+    }
+
+    // across a page boundary.
+    let text = "li a0, 0x11223344\nsw a0, 1(s1)";
+    let (program, machine, result) = run(MisalignedAccess::Emulate, text);
+    result.unwrap();
+    let addr = program.symbol("boundary").unwrap();
+    assert_eq!(
+        machine.read_memory(addr, 5).unwrap(),
+        [0xAA, 0x44, 0x33, 0x22, 0x11]
+    );
+}
+
+#[test]
+fn aligned_accesses_never_trap() {
+    let text =
+        "lw a0, 4(s0)\nlh a1, 2(s0)\nlb a2, 3(s0)\nsw a0, 8(s0)\nsh a1, 14(s0)\nsb a2, 1(s1)";
+    let (_, machine, result) = run(MisalignedAccess::Trap, text);
+    result.unwrap();
+    assert_eq!(machine.registers()[10..=12], [0x0706_0504, 0x0302, 0x03]);
+}
+
+#[test]
+fn traps_reach_the_handler() {
+    // mcause is 4 for loads and 6 for stores, and mtval holds the address.
+    let handler = "
+        la      t0, handler
+        csrw    mtvec, t0
+        j       2f
+handler:
+        csrr    a0, mcause
+        csrr    a1, mtval
+3:      j       3b
+2:
+    ";
+    for (text, cause, offset) in [("lw a2, 2(s0)", 4, 2), ("sh a2, 5(s0)", 6, 5)] {
+        let (program, machine, result) = run(MisalignedAccess::Trap, &format!("{handler}\n{text}"));
+        result.unwrap();
+        let bytes = program.symbol("bytes").unwrap() as i64;
+        assert_eq!(
+            machine.registers()[10..=11],
+            [cause, bytes + offset],
+            "{text}"
+        );
+    }
+}
+
+/* The label the access in text is relative to and its offset. */
+fn offset(program: &Program, text: &str) -> (u64, u64) {
+    let (offset, base) = text.split_once(", ").unwrap().1.split_once('(').unwrap();
+    let label = match base {
+        "s0)" => "bytes",
+        _ => "boundary",
+    };
+
+    (program.symbol(label).unwrap(), offset.parse().unwrap())
+}
@@ -0,0 +1,358 @@
+//! Runs S-mode programs under Sv32: page table walks and the faults they raise, the
+//! accessed and dirty bits the walker sets, megapage alignment, and what sfence.vma
+//! drops from the TLB. Without trap vectors, faults end execution with their error.
+
+use periscvcope::{
+    assembler::{self, Program},
+    machine::{Machine, MachineError},
+};
+
+/* PTE bits. */
+const V: u32 = 1 << 0;
+const R: u32 = 1 << 1;
+const W: u32 = 1 << 2;
+const X: u32 = 1 << 3;
+const U: u32 = 1 << 4;
+const G: u32 = 1 << 5;
+const A: u32 = 1 << 6;
+const D: u32 = 1 << 7;
+
+/* Where `table` maps pages, through entry 1 of the root table. */
+const BASE: u64 = 0x40_0000;
+
+/// A page table entry the program writes before entering S-mode.
+struct Pte {
+    /// `root` or `table`.
+    table: &'static str,
+    index: u32,
+    /// A label to map, or a physical page number.
+    target: &'static str,
+    flags: u32,
+}
+
+fn leaf(index: u32, target: &'static str, flags: u32) -> Pte {
+    Pte {
+        table: "table",
+        index,
+        target,
+        flags,
+    }
+}
+
+/* A program that maps all of memory one to one with a megapage, so that code and page
+ * tables stay where they are, maps the ptes, and runs text in S-mode with ASID 1.
+ * page0 and page1 start with 0x11111111 and 0x22222222. */
+fn program(ptes: &[Pte], text: &str) -> Program {
+    let mut setup = vec![
+        // S-mode may access all of memory.
+        "li t0, -1".to_string(),
+        "csrw pmpaddr0, t0".into(),
+        "li t0, 0x1F".into(),
+        "csrw pmpcfg0, t0".into(),
+        "la t0, root".into(),
+        format!("li t1, {}", V | R | W | X | A | D),
+        "sw t1, 0(t0)".into(),
+        "la t1, table".into(),
+        "srli t1, t1, 12".into(),
+        "slli t1, t1, 10".into(),
+        format!("ori t1, t1, {V}"),
+        "sw t1, 4(t0)".into(),
+    ];
+    for pte in ptes {
+        let target = match pte.target.parse::<u32>() {
+            Ok(ppn) => format!("li t1, {}", ppn << 12),
+            Err(_) => format!("la t1, {}", pte.target),
+        };
+        setup.extend([
+            format!("la t0, {}", pte.table),
+            target,
+            "srli t1, t1, 12".into(),
+            "slli t1, t1, 10".into(),
+            format!("ori t1, t1, {}", pte.flags),
+            format!("sw t1, {}(t0)", 4 * pte.index),
+        ]);
+    }
+    setup.extend([
+        "la t0, root".into(),
+        "srli t0, t0, 12".into(),
+        "li t1, 0x80400000".into(),
+        "or t0, t0, t1".into(),
+        "csrw satp, t0".into(),
+        // mret to S-mode.
+        "li t0, 0x1800".into(),
+        "csrc mstatus, t0".into(),
+        "li t0, 0x800".into(),
+        "csrs mstatus, t0".into(),
+        "la t0, supervisor".into(),
+        "csrw mepc, t0".into(),
+        "mret".into(),
+    ]);
+
+    let source = format!(
+        "
+        {}
+supervisor:
+        {text}
+1:      j       1b
+
+        .data
+        .balign 4096
+root:   .zero   4096
+table:  .zero   4096
+page0:  .word   0x11111111
+        .zero   4092
+page1:  .word   0x22222222
+        .zero   4092
+        ",
+        setup.join("\n")
+    );
+
+    assembler::assemble(&source).unwrap()
+}
+
+fn run(ptes: &[Pte], text: &str) -> Result<(Program, Machine), MachineError> {
+    let program = program(ptes, text);
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.execute_until_loop()?;
+
+    Ok((program, machine))
+}
+
+fn word(machine: &Machine, addr: u64) -> u32 {
+    u32::from_le_bytes(machine.read_memory(addr, 4).unwrap().try_into().unwrap())
+}
+
+#[test]
+fn translation() {
+    let ptes = [
+        leaf(0, "page0", V | R | W | A | D),
+        leaf(1, "page1", V | R | A),
+        leaf(2, "page0", V | R | A),
+    ];
+    let text = "
+        li      s0, 0x400000
+        li      s1, 0x401000
+        li      s2, 0x402000
+        lw      a0, 0(s0)
+        lw      a1, 0(s1)
+        sw      a1, 8(s0)
+        lw      a2, 8(s2)
+    ";
+    let (program, machine) = run(&ptes, text).unwrap();
+
+    let registers = machine.registers();
+    assert_eq!(registers[10..=12], [0x1111_1111, 0x2222_2222, 0x2222_2222]);
+    // the store went to the physical page.
+    let page0 = program.symbol("page0").unwrap();
+    assert_eq!(word(&machine, page0 + 8), 0x2222_2222);
+}
+
+#[test]
+fn page_faults() {
+    let rw = V | R | W | A | D;
+    for (flags, text, expected) in [
+        (0, "lw a0, 0(s0)", MachineError::LoadPageFault(BASE)),
+        (
+            V | R | A,
+            "sw a0, 4(s0)",
+            MachineError::StorePageFault(BASE + 4),
+        ),
+        // W without R is reserved.
+        (
+            V | W | A | D,
+            "lw a0, 0(s0)",
+            MachineError::LoadPageFault(BASE),
+        ),
+        (rw, "jr s0", MachineError::InstructionPageFault(BASE)),
+        // S-mode only reaches user pages with SUM, and never executes them.
+        (rw | U, "lw a0, 0(s0)", MachineError::LoadPageFault(BASE)),
+        (
+            rw | X | U,
+            "li t0, 0x40000\ncsrs sstatus, t0\njr s0",
+            MachineError::InstructionPageFault(BASE),
+        ),
+        // nothing is mapped after the first page, or after the table.
+        (
+            rw,
+            "li s1, 0x401000\nlw a0, 0(s1)",
+            MachineError::LoadPageFault(BASE + 0x1000),
+        ),
+        (
+            rw,
+            "li s1, 0x800000\nlw a0, 0(s1)",
+            MachineError::LoadPageFault(0x80_0000),
+        ),
+    ] {
+        let text = format!("li s0, {BASE}\n{text}");
+        match run(&[leaf(0, "page0", flags)], &text) {
+            Err(err) => assert_eq!(err.to_string(), expected.to_string(), "{text}"),
+            Ok(_) => panic!("{text}: no fault"),
+        }
+    }
+
+    // with SUM, S-mode loads from and stores to user pages.
+    let text =
+        format!("li t0, 0x40000\ncsrs sstatus, t0\nli s0, {BASE}\nlw a0, 0(s0)\nsw a0, 4(s0)");
+    let (_, machine) = run(&[leaf(0, "page0", rw | U)], &text).unwrap();
+    assert_eq!(machine.registers()[10], 0x1111_1111);
+}
+
+#[test]
+fn accessed_and_dirty_bits() {
+    let pte = |machine: &Machine, program: &Program, index: u64| {
+        word(machine, program.symbol("table").unwrap() + 4 * index)
+    };
+    let ptes = [
+        leaf(0, "page0", V | R | W),
+        leaf(1, "page1", V | R | W),
+        leaf(2, "page1", V | R | W),
+    ];
+    let text = format!(
+        "
+        li      s0, {BASE}
+        li      s1, {}
+        lw      a0, 0(s0)
+        sw      a0, 0(s1)
+        ",
+        BASE + 0x1000
+    );
+    let (assembled, machine) = run(&ptes, &text).unwrap();
+
+    // a load sets A, a store sets A and D, and unused entries keep neither.
+    assert_eq!(pte(&machine, &assembled, 0) & (A | D), A);
+    assert_eq!(pte(&machine, &assembled, 1) & (A | D), A | D);
+    assert_eq!(pte(&machine, &assembled, 2) & (A | D), 0);
+    // the rest of the entry is unchanged.
+    let page0 = assembled.symbol("page0").unwrap() as u32;
+    assert_eq!(
+        pte(&machine, &assembled, 0) & !(A | D),
+        page0 >> 2 | V | R | W
+    );
+
+    // a faulting store sets neither.
+    let assembled = program(
+        &[leaf(0, "page0", V | R)],
+        &format!("li s0, {BASE}\nsw a0, 0(s0)"),
+    );
+    let mut machine = Machine::from_program(&assembled).unwrap();
+    assert!(matches!(
+        machine.execute_until_loop(),
+        Err(MachineError::StorePageFault(BASE))
+    ));
+    assert_eq!(pte(&machine, &assembled, 0) & (A | D), 0);
+}
+
+#[test]
+fn megapages() {
+    let megapage = |ppn: &'static str| Pte {
+        table: "root",
+        index: 2,
+        target: ppn,
+        flags: V | R | W | A | D,
+    };
+    let text = "li s0, 0x800000\nla s1, page0\nadd s0, s0, s1\nlw a0, 0(s0)";
+
+    // the megapage at 8 MiB maps physical memory from 0.
+    let (_, machine) = run(&[megapage("0")], text).unwrap();
+    assert_eq!(machine.registers()[10], 0x1111_1111);
+
+    // its physical page number must be a multiple of 1024 pages.
+    let result = run(&[megapage("1")], text);
+    assert!(
+        matches!(result, Err(MachineError::LoadPageFault(addr)) if addr >> 22 == 2),
+        "{:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn sfence_vma() {
+    let ptes = [leaf(0, "page0", V | R | W | A | D)];
+    // points entry 0 of the table at page1, through the megapage mapping it one to one.
+    let remap = format!(
+        "
+        la      t0, table
+        la      t1, page1
+        srli    t1, t1, 12
+        slli    t1, t1, 10
+        ori     t1, t1, {}
+        sw      t1, 0(t0)
+        ",
+        V | R | W | A | D
+    );
+    let text = format!(
+        "
+        li      s0, {BASE}
+        li      s1, {}
+        lw      a0, 0(s0)
+        {remap}
+        lw      a1, 0(s0)
+        sfence.vma s1, zero
+        lw      a2, 0(s0)
+        li      t2, 2
+        sfence.vma s0, t2
+        lw      a3, 0(s0)
+        sfence.vma s0, zero
+        lw      a4, 0(s0)
+        ",
+        BASE + 0x1000
+    );
+    let (_, machine) = run(&ptes, &text).unwrap();
+
+    // the TLB keeps the old entry until sfence.vma names its page, or its ASID.
+    let registers = machine.registers();
+    assert_eq!(
+        registers[10..=14],
+        [
+            0x1111_1111,
+            0x1111_1111,
+            0x1111_1111,
+            0x1111_1111,
+            0x2222_2222
+        ]
+    );
+}
+
+#[test]
+fn sfence_vma_by_address_space() {
+    for (flags, asid, expected) in [
+        // entries of other address spaces stay, those of ASID 1 go.
+        (0, 2, 0x1111_1111),
+        (0, 1, 0x2222_2222),
+        // global entries belong to every address space, and stay.
+        (G, 1, 0x1111_1111),
+    ] {
+        let ptes = [leaf(0, "page0", V | R | W | A | D | flags)];
+        let text = format!(
+            "
+            li      s0, {BASE}
+            lw      a0, 0(s0)
+            la      t0, table
+            la      t1, page1
+            srli    t1, t1, 12
+            slli    t1, t1, 10
+            ori     t1, t1, {}
+            sw      t1, 0(t0)
+            li      t2, {asid}
+            sfence.vma zero, t2
+            lw      a1, 0(s0)
+            ",
+            V | R | W | A | D | flags
+        );
+        let (_, machine) = run(&ptes, &text).unwrap();
+        assert_eq!(
+            machine.registers()[11],
+            expected,
+            "flags {flags:#x}, asid {asid}"
+        );
+    }
+
+    // and a full flush drops them too.
+    let ptes = [leaf(0, "page0", V | R | W | A | D | G)];
+    let text = format!(
+        "li s0, {BASE}\nlw a0, 0(s0)\nla t0, table\nla t1, page1\nsrli t1, t1, 12\nslli t1, t1, 10\nori t1, t1, {}\nsw t1, 0(t0)\nsfence.vma zero, zero\nlw a1, 0(s0)",
+        V | R | W | A | D | G
+    );
+    let (_, machine) = run(&ptes, &text).unwrap();
+    assert_eq!(machine.registers()[11], 0x2222_2222);
+}
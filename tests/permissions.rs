@@ -0,0 +1,97 @@
+//! Loads assembled ELF files, some with the p_flags of their data segment changed, and
+//! checks that accesses their segments do not allow raise access faults: stores to
+//! segments without W, and fetches from segments without X.
+
+use periscvcope::{
+    assembler::{self, Program},
+    machine::{Machine, MachineError},
+};
+
+/* Runs text with s0 pointing at a word in .data, followed by a ret. */
+fn program(text: &str) -> Program {
+    let source = format!(
+        "
+        la      s0, value
+        {text}
+1:      j       1b
+
+        .data
+value:  .word   0x12345678
+        ret
+        "
+    );
+
+    assembler::assemble(&source).unwrap()
+}
+
+/* The ELF file of program, with the p_flags of its data segment replaced. */
+fn with_data_flags(program: &Program, flags: u32) -> Vec<u8> {
+    let mut data = program.to_elf();
+    let field = |data: &[u8], at: usize, len: usize| {
+        data[at..at + len]
+            .iter()
+            .rev()
+            .fold(0, |value, &byte| value << 8 | byte as usize)
+    };
+
+    // ELF32: e_phoff, e_phentsize and e_phnum, and p_vaddr and p_flags in each entry.
+    let phoff = field(&data, 0x1C, 4);
+    let phentsize = field(&data, 0x2A, 2);
+    let phnum = field(&data, 0x2C, 2);
+    let phdr = (0..phnum)
+        .map(|i| phoff + i * phentsize)
+        .find(|&phdr| field(&data, phdr + 8, 4) as u64 == program.data.base)
+        .unwrap();
+    data[phdr + 24..phdr + 28].copy_from_slice(&flags.to_le_bytes());
+
+    data
+}
+
+fn run(data: &[u8]) -> Result<Machine, MachineError> {
+    let mut machine = Machine::new(data)?;
+    machine.execute_until_loop()?;
+
+    Ok(machine)
+}
+
+#[test]
+fn stores_need_write_permission() {
+    let store = program("li t0, 1\nsw t0, 0(s0)\nlw a0, 0(s0)");
+    let value = store.symbol("value").unwrap();
+
+    // .data is readable and writable.
+    let machine = run(&store.to_elf()).unwrap();
+    assert_eq!(machine.registers()[10], 1);
+
+    // with only R, loads still work and stores fault.
+    let load = program("lw a0, 0(s0)");
+    let machine = run(&with_data_flags(&load, elf::abi::PF_R)).unwrap();
+    assert_eq!(machine.registers()[10], 0x1234_5678);
+    assert!(matches!(
+        run(&with_data_flags(&store, elf::abi::PF_R)),
+        Err(MachineError::StoreAccessFault(addr)) if addr == value
+    ));
+
+    // .text is not writable either.
+    let program = program("auipc t0, 0\nsw zero, 0(t0)");
+    let auipc = program.text.base + 8;
+    assert!(matches!(
+        run(&program.to_elf()),
+        Err(MachineError::StoreAccessFault(addr)) if addr == auipc
+    ));
+}
+
+#[test]
+fn fetches_need_execute_permission() {
+    // the ret after value is in .data, which is not executable.
+    let program = program("addi t0, s0, 4\njalr t0");
+    let ret = program.symbol("value").unwrap() + 4;
+    assert!(matches!(
+        run(&program.to_elf()),
+        Err(MachineError::InstructionAccessFault(addr)) if addr == ret
+    ));
+
+    // with X added, it runs and returns.
+    let flags = elf::abi::PF_R | elf::abi::PF_W | elf::abi::PF_X;
+    run(&with_data_flags(&program, flags)).unwrap();
+}
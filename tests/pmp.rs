@@ -0,0 +1,268 @@
+//! Configures physical memory protection from M-mode and runs U-mode code against it:
+//! TOR, NA4 and NAPOT regions, the lowest matching entry deciding, accesses without a
+//! matching entry, and locked entries. Without trap vectors, faults end execution with
+//! their error.
+
+use periscvcope::{
+    assembler::{self, Program},
+    machine::{Machine, MachineError},
+};
+
+/* pmpNcfg fields. */
+const R: u32 = 1 << 0;
+const W: u32 = 1 << 1;
+const X: u32 = 1 << 2;
+const TOR: u32 = 1 << 3;
+const NA4: u32 = 2 << 3;
+const NAPOT: u32 = 3 << 3;
+const L: u32 = 1 << 7;
+
+/* Entry 0 of these lets U-mode run the code below buffer. */
+const CODE: &str = "
+        la      t0, buffer
+        srli    t0, t0, 2
+        csrw    pmpaddr0, t0
+";
+
+/* Runs setup in M-mode, then text in U-mode. buffer is a page of data starting with
+ * 0x12345678. */
+fn run(setup: &str, text: &str) -> Result<(Program, Machine), MachineError> {
+    let source = format!(
+        "
+        {setup}
+        li      t0, 0x1800
+        csrc    mstatus, t0
+        la      t0, user
+        csrw    mepc, t0
+        mret
+user:
+        {text}
+1:      j       1b
+
+        .data
+        .balign 4096
+buffer: .word   0x12345678
+        .zero   4092
+        "
+    );
+    let program = assembler::assemble(&source).unwrap();
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.execute_until_loop()?;
+
+    Ok((program, machine))
+}
+
+/* Checks that text faults with expected, given the offset of the access from buffer. */
+fn fails(setup: &str, text: &str, expected: fn(u64) -> MachineError, offset: u64) {
+    match run(setup, text) {
+        Err(err) => {
+            let program = assembler::assemble(&format!("{setup}\n.data\n.balign 4096\nbuffer:"));
+            let buffer = program.unwrap().symbol("buffer").unwrap();
+            assert_eq!(
+                err.to_string(),
+                expected(buffer + offset).to_string(),
+                "{text}"
+            );
+        }
+        Ok(_) => panic!("{text}: no fault"),
+    }
+}
+
+#[test]
+fn no_matching_entry() {
+    // with every entry off, M-mode runs and U-mode cannot even fetch.
+    let result = run("", "nop");
+    assert!(
+        matches!(result, Err(MachineError::InstructionAccessFault(_))),
+        "{:?}",
+        result.map(|_| ())
+    );
+
+    // with entries in use, unmatched accesses fail.
+    let setup = format!("{CODE}\nli t0, {}\ncsrw pmpcfg0, t0", TOR | R | X);
+    let (_, machine) = run(&setup, "li a0, 1").unwrap();
+    assert_eq!(machine.registers()[10], 1);
+    fails(
+        &setup,
+        "la s0, buffer\nlw a0, 0(s0)",
+        MachineError::LoadAccessFault,
+        0,
+    );
+}
+
+#[test]
+fn top_of_range() {
+    // entry 1 covers the first eight bytes of buffer, from the address of entry 0.
+    let setup = format!(
+        "
+        {CODE}
+        la      t0, buffer + 8
+        srli    t0, t0, 2
+        csrw    pmpaddr1, t0
+        li      t0, {}
+        csrw    pmpcfg0, t0
+        ",
+        (TOR | R | X) | (TOR | R) << 8
+    );
+    let (_, machine) = run(&setup, "la s0, buffer\nlw a0, 0(s0)\nlw a1, 4(s0)").unwrap();
+    assert_eq!(machine.registers()[10], 0x1234_5678);
+
+    fails(
+        &setup,
+        "la s0, buffer\nsw a0, 0(s0)",
+        MachineError::StoreAccessFault,
+        0,
+    );
+    fails(
+        &setup,
+        "la s0, buffer\nlw a0, 8(s0)",
+        MachineError::LoadAccessFault,
+        8,
+    );
+    // an access must lie entirely in the entry that matches it.
+    fails(
+        &setup,
+        "la s0, buffer\nlw a0, 6(s0)",
+        MachineError::LoadAccessFault,
+        6,
+    );
+    // code is not executable in the region.
+    fails(
+        &setup,
+        "la s0, buffer\njr s0",
+        MachineError::InstructionAccessFault,
+        0,
+    );
+}
+
+#[test]
+fn naturally_aligned_four_bytes() {
+    let setup = format!(
+        "
+        {CODE}
+        la      t0, buffer + 4
+        srli    t0, t0, 2
+        csrw    pmpaddr1, t0
+        li      t0, {}
+        csrw    pmpcfg0, t0
+        ",
+        (TOR | R | X) | (NA4 | R | W) << 8
+    );
+    let (program, machine) = run(&setup, "la s0, buffer\nli a0, 42\nsw a0, 4(s0)").unwrap();
+    let buffer = program.symbol("buffer").unwrap();
+    assert_eq!(
+        machine.read_memory(buffer + 4, 4).unwrap(),
+        42u32.to_le_bytes()
+    );
+
+    fails(
+        &setup,
+        "la s0, buffer\nlw a0, 0(s0)",
+        MachineError::LoadAccessFault,
+        0,
+    );
+    fails(
+        &setup,
+        "la s0, buffer\nlw a0, 8(s0)",
+        MachineError::LoadAccessFault,
+        8,
+    );
+    fails(
+        &setup,
+        "la s0, buffer\nsw a0, 6(s0)",
+        MachineError::StoreAccessFault,
+        6,
+    );
+}
+
+#[test]
+fn naturally_aligned_power_of_two() {
+    // the trailing ones of pmpaddr2 make it cover the 4 KiB of buffer, and entry 1 takes
+    // its first word away.
+    let setup = format!(
+        "
+        {CODE}
+        la      t0, buffer
+        srli    t0, t0, 2
+        csrw    pmpaddr1, t0
+        ori     t0, t0, 0x1FF
+        csrw    pmpaddr2, t0
+        li      t0, {}
+        csrw    pmpcfg0, t0
+        ",
+        (TOR | R | X) | NA4 << 8 | (NAPOT | R) << 16
+    );
+    let text = "la s0, buffer\nlw a0, 4(s0)\nli t0, 4092\nadd s1, s0, t0\nlw a1, 0(s1)";
+    let (_, machine) = run(&setup, text).unwrap();
+    assert_eq!(machine.registers()[10..=11], [0, 0]);
+
+    fails(
+        &setup,
+        "la s0, buffer\nlw a0, 0(s0)",
+        MachineError::LoadAccessFault,
+        0,
+    );
+    fails(
+        &setup,
+        "la s0, buffer\nsw a0, 4(s0)",
+        MachineError::StoreAccessFault,
+        4,
+    );
+    let text = "la s0, buffer\nli t0, 4096\nadd s0, s0, t0\nlw a0, 0(s0)";
+    fails(&setup, text, MachineError::LoadAccessFault, 4096);
+}
+
+#[test]
+fn locked_entries() {
+    // a locked entry holds M-mode to its permissions, and ignores writes.
+    let setup = format!(
+        "
+        la      t0, buffer
+        srli    t0, t0, 2
+        csrw    pmpaddr0, t0
+        li      t0, {}
+        csrw    pmpcfg0, t0
+        csrw    pmpcfg0, zero
+        csrw    pmpaddr0, zero
+        csrr    a0, pmpcfg0
+        csrr    a1, pmpaddr0
+        la      s0, buffer
+        lw      a2, 0(s0)
+        ",
+        NA4 | R | L
+    );
+    let text = format!("{setup}\n2: j 2b");
+    let program = assembler::assemble(&format!("{text}\n.data\n.balign 4096\nbuffer: .word 7"));
+    let program = program.unwrap();
+    let buffer = program.symbol("buffer").unwrap() as i64;
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.execute_until_loop().unwrap();
+    let registers = machine.registers();
+    assert_eq!(registers[10..=12], [(NA4 | R | L) as i64, buffer >> 2, 7]);
+
+    let result = run(&format!("{setup}\nsw a2, 0(s0)"), "");
+    assert!(
+        matches!(result, Err(MachineError::StoreAccessFault(addr)) if addr as i64 == buffer),
+        "{:?}",
+        result.map(|_| ())
+    );
+
+    // a locked TOR entry also locks the address below it.
+    let setup = format!(
+        "
+        li      t0, 0x400
+        csrw    pmpaddr0, t0
+        li      t0, 0x800
+        csrw    pmpaddr1, t0
+        li      t0, {}
+        csrw    pmpcfg0, t0
+        csrw    pmpaddr0, zero
+        csrr    a0, pmpaddr0
+2:      j       2b
+        ",
+        (TOR | R | W | X | L) << 8
+    );
+    let mut machine = Machine::from_program(&assembler::assemble(&setup).unwrap()).unwrap();
+    machine.execute_until_loop().unwrap();
+    assert_eq!(machine.registers()[10], 0x400);
+}
@@ -0,0 +1,166 @@
+//! Runs RV64 programs: the *w instructions compute on the low 32 bits and sign-extend
+//! the result, while the others use all 64 bits. The assembler only takes RV32, so RV64
+//! instructions are encoded as `.word`s.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    assembler,
+    instruction::{Instruction, definitions::Op, formats::Format},
+    isa::{Isa, Xlen},
+    machine::{Machine, MachineError},
+};
+
+const MIN: i64 = i32::MIN as i64;
+
+/* An RV64 instruction as a .word, with unused fields left 0. */
+fn word(op: Op, rd: u8, rs1: u8, rs2: u8, imm: i32) -> String {
+    let rs2 = match op.format() {
+        Format::R | Format::S => rs2,
+        _ => 0,
+    };
+    let raw = Instruction::encode(op, u5::new(rd), u5::new(rs1), u5::new(rs2), imm, Xlen::Rv64);
+
+    format!(".word {:#010x}", raw.unwrap())
+}
+
+/* Runs text on RV64 with the doublewords a and b at s0, up to the loop after it. */
+fn run(text: &str, a: i64, b: i64) -> Result<Machine, MachineError> {
+    let source = format!(
+        "
+        la      s0, operands
+        {}
+        {}
+        {text}
+1:      j       1b
+        .data
+operands: .dword {a:#x}, {b:#x}, 0
+        ",
+        word(Op::ld, 11, 8, 0, 0),
+        word(Op::ld, 12, 8, 0, 8),
+    );
+    let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+    machine.set_isa("rv64im".parse::<Isa>().unwrap()).unwrap();
+    machine.execute_until_loop()?;
+
+    Ok(machine)
+}
+
+/* Checks `op a0, a1, a2` for each (a1, a2, a0), or `op a0, a1, imm` with a2 as imm. */
+fn check(op: Op, cases: &[(i64, i64, i64)]) {
+    for &(a, b, expected) in cases {
+        let text = match op.format() {
+            Format::R => word(op, 10, 11, 12, 0),
+            _ => word(op, 10, 11, 0, b as i32),
+        };
+        let machine = run(&text, a, b).unwrap();
+        assert_eq!(machine.registers()[10], expected, "{op} {a:#x}, {b:#x}");
+    }
+}
+
+#[test]
+fn word_arithmetic() {
+    check(
+        Op::addw,
+        &[
+            (0x7FFF_FFFF, 1, MIN),
+            (0x1234_5678_0000_0001, 1, 2),
+            (-1, 0, -1),
+        ],
+    );
+    check(
+        Op::subw,
+        &[(0, 1, -1), (i64::MIN, 0, 0), (MIN, 1, 0x7FFF_FFFF)],
+    );
+    check(Op::addiw, &[(0x7FFF_FFFF, 1, MIN), (0x1_0000_0000, -1, -1)]);
+}
+
+#[test]
+fn word_shifts() {
+    // shift amounts use 5 bits, and only the low word is shifted in.
+    check(
+        Op::sllw,
+        &[
+            (1, 31, MIN),
+            (1, 32, 1),
+            (1, 63, MIN),
+            (0x1_0000_0001, 1, 2),
+        ],
+    );
+    check(
+        Op::srlw,
+        &[
+            (MIN, 31, 1),
+            (-1, 0, -1),
+            (0x8000_0000, 1, 0x4000_0000),
+            (-1, 32, -1),
+        ],
+    );
+    check(Op::sraw, &[(0x8000_0000, 31, -1), (0x1_7FFF_FFFF, 30, 1)]);
+    check(Op::slliw, &[(1, 31, MIN), (0xFFFF_FFFF, 4, -16)]);
+    check(Op::srliw, &[(-1, 4, 0x0FFF_FFFF), (0x1_0000_0010, 4, 1)]);
+    check(Op::sraiw, &[(0x8000_0000, 4, -0x0800_0000)]);
+}
+
+#[test]
+fn word_multiplication_and_division() {
+    check(
+        Op::mulw,
+        &[
+            (0x10000, 0x10000, 0),
+            (0x1_0000_0003, 5, 15),
+            (0x4000_0000, 2, MIN),
+        ],
+    );
+    check(
+        Op::divw,
+        &[(MIN, -1, MIN), (42, 0, -1), (0x1_0000_0008, 2, 4)],
+    );
+    check(
+        Op::divuw,
+        &[(-1, 2, 0x7FFF_FFFF), (MIN, 0, -1), (0x8000_0000, 1, MIN)],
+    );
+    check(Op::remw, &[(MIN, -1, 0), (-7, 0, -7), (-7, 2, -1)]);
+    check(Op::remuw, &[(5, 0, 5), (-1, 10, 5), (0x1_0000_0005, 0, 5)]);
+}
+
+#[test]
+fn full_width() {
+    // the same operands give 64-bit results without w.
+    check(Op::add, &[(0x7FFF_FFFF, 1, 0x8000_0000)]);
+    check(Op::addi, &[(0x7FFF_FFFF, 1, 0x8000_0000)]);
+    check(Op::sll, &[(1, 63, i64::MIN), (1, 64, 1)]);
+    check(Op::srl, &[(-1, 32, 0xFFFF_FFFF), (i64::MIN, 63, 1)]);
+    check(Op::sra, &[(i64::MIN, 63, -1)]);
+    check(Op::slli, &[(1, 40, 1 << 40)]);
+    check(Op::srli, &[(-1, 60, 0xF)]);
+    check(Op::mul, &[(0x1_0000_0000, 0x10, 0x10_0000_0000)]);
+}
+
+#[test]
+fn loads_and_stores() {
+    let text = [
+        word(Op::lw, 13, 8, 0, 0),
+        word(Op::lwu, 14, 8, 0, 0),
+        word(Op::sd, 0, 8, 12, 16),
+        word(Op::ld, 15, 8, 0, 16),
+        word(Op::lwu, 16, 8, 0, 20),
+    ]
+    .join("\n");
+    let machine = run(&text, 0xFFFF_FFFF, 0x1234_5678_9ABC_DEF0).unwrap();
+
+    let registers = machine.registers();
+    assert_eq!(registers[13], -1);
+    assert_eq!(registers[14], 0xFFFF_FFFF);
+    assert_eq!(registers[15], 0x1234_5678_9ABC_DEF0);
+    assert_eq!(registers[16], 0x1234_5678);
+}
+
+#[test]
+fn word_instructions_are_rv64_only() {
+    let source = format!("{}\n1: j 1b", word(Op::addw, 10, 11, 12, 0));
+    let mut machine = Machine::from_program(&assembler::assemble(&source).unwrap()).unwrap();
+    assert!(matches!(
+        machine.execute_until_loop(),
+        Err(MachineError::IllegalInstruction(0x1000, _))
+    ));
+}
@@ -0,0 +1,163 @@
+//! Runs arithmetic with the overflow sanitizer: it records signed overflow of add, sub,
+//! mul, div and their W forms, while results still wrap as the ISA specifies.
+
+use bilge::prelude::u5;
+use periscvcope::{
+    assembler::{self, Program},
+    instruction::{Instruction, definitions::Op},
+    isa::{Isa, Xlen},
+    machine::Machine,
+};
+
+const MIN: i64 = i32::MIN as i64;
+const MAX: i64 = i32::MAX as i64;
+
+/* Runs `op a0, a1, a2` on RV32, at the label `at`. */
+fn run(op: &str, a: i64, b: i64, sanitizer: bool) -> (Program, Machine) {
+    let source = format!(
+        "
+        li      a1, {a}
+        li      a2, {b}
+at:     {op}    a0, a1, a2
+1:      j       1b
+        "
+    );
+    let program = assembler::assemble(&source).unwrap();
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.set_sanitizer(sanitizer);
+    machine.execute_until_loop().unwrap();
+
+    (program, machine)
+}
+
+#[test]
+fn overflow_is_recorded() {
+    for (op, a, b, wrapped) in [
+        ("add", MAX, 1, MIN),
+        ("add", MIN, -1, MAX),
+        ("sub", MIN, 1, MAX),
+        ("sub", 0, MIN, MIN),
+        ("mul", 0x10000, 0x10000, 0),
+        ("mul", MIN, -1, MIN),
+        ("div", MIN, -1, MIN),
+    ] {
+        let (program, machine) = run(op, a, b, true);
+        assert_eq!(machine.registers()[10], wrapped, "{op} {a}, {b}");
+        assert_eq!(
+            machine.overflows(),
+            [program.symbol("at").unwrap()],
+            "{op} {a}, {b}"
+        );
+
+        // without the sanitizer, nothing is recorded and the result is the same.
+        let (_, machine) = run(op, a, b, false);
+        assert_eq!(machine.registers()[10], wrapped);
+        assert!(machine.overflows().is_empty());
+    }
+}
+
+#[test]
+fn results_in_range_are_not_recorded() {
+    for (op, a, b) in [
+        ("add", MAX, 0),
+        ("add", -1, -1),
+        ("sub", MIN, 0),
+        ("sub", -1, MAX),
+        ("mul", 0x8000, -0x10000),
+        ("mulh", MAX, MAX),
+        ("mulhu", -1, -1),
+        // division by zero has a defined result, and is not an overflow.
+        ("div", 1, 0),
+        ("divu", MIN, -1),
+        ("rem", MIN, -1),
+        // unsigned arithmetic and addi are left alone.
+        ("sltu", -1, 0),
+        ("sll", MAX, 31),
+    ] {
+        let (_, machine) = run(op, a, b, true);
+        assert!(machine.overflows().is_empty(), "{op} {a}, {b}");
+    }
+
+    // addi also builds constants and adjusts pointers.
+    let source = "li a0, 0x7FFFFFFF\naddi a0, a0, 1\n1: j 1b";
+    let mut machine = Machine::from_program(&assembler::assemble(source).unwrap()).unwrap();
+    machine.set_sanitizer(true);
+    machine.execute_until_loop().unwrap();
+    assert_eq!(machine.registers()[10], MIN);
+    assert!(machine.overflows().is_empty());
+}
+
+#[test]
+fn every_overflow_is_recorded_until_reset() {
+    let source = "
+        li      a1, 0x7FFFFFFF
+        li      s0, 3
+loop:
+over:   add     a0, a1, a1
+        addi    s0, s0, -1
+        bnez    s0, loop
+1:      j       1b
+    ";
+    let program = assembler::assemble(source).unwrap();
+    let mut machine = Machine::from_program(&program).unwrap();
+    machine.set_sanitizer(true);
+    machine.execute_until_loop().unwrap();
+    assert_eq!(machine.overflows(), [program.symbol("over").unwrap(); 3]);
+
+    machine.reset();
+    assert!(machine.overflows().is_empty());
+}
+
+#[test]
+fn word_operations_check_32_bits() {
+    for (op, a, b, overflows) in [
+        (Op::addw, MAX, 1, true),
+        (Op::addw, MAX, 0, false),
+        (Op::addiw, MAX, 1, true),
+        (Op::subw, MIN, 1, true),
+        (Op::subw, -1, MIN, false),
+        (Op::mulw, 0x10000, 0x8000, true),
+        (Op::divw, MIN, -1, true),
+        // only the low 32 bits of the operands count.
+        (Op::addw, 0x1_0000_0000, 1, false),
+        // and 64-bit operations overflow at 64 bits.
+        (Op::add, MAX, 1, false),
+        (Op::add, i64::MAX, 1, true),
+    ] {
+        let instruction = match op {
+            Op::addiw => word(op, 10, 11, 0, b as i32),
+            _ => word(op, 10, 11, 12, 0),
+        };
+        let source = format!(
+            "
+            la      s0, operands
+            {}
+            {}
+at:         {instruction}
+1:          j       1b
+            .data
+operands:   .dword  {a:#x}, {b:#x}
+            ",
+            word(Op::ld, 11, 8, 0, 0),
+            word(Op::ld, 12, 8, 0, 8),
+        );
+        let program = assembler::assemble(&source).unwrap();
+        let mut machine = Machine::from_program(&program).unwrap();
+        machine.set_isa("rv64im".parse::<Isa>().unwrap()).unwrap();
+        machine.set_sanitizer(true);
+        machine.execute_until_loop().unwrap();
+
+        let expected: &[u64] = match overflows {
+            true => &[program.symbol("at").unwrap()],
+            false => &[],
+        };
+        assert_eq!(machine.overflows(), expected, "{op} {a:#x}, {b:#x}");
+    }
+}
+
+/* An RV64 instruction as a .word. */
+fn word(op: Op, rd: u8, rs1: u8, rs2: u8, imm: i32) -> String {
+    let raw = Instruction::encode(op, u5::new(rd), u5::new(rs1), u5::new(rs2), imm, Xlen::Rv64);
+
+    format!(".word {:#010x}", raw.unwrap())
+}